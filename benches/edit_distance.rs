@@ -0,0 +1,61 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rustc_edit_distance::edit_distance;
+
+const IDENTIFIERS: &[(&str, &str)] = &[
+    ("find_best_match", "find_best_mtach"),
+    ("HashMap", "HashSet"),
+    ("edit_distance_with_substrings", "edit_distance_substrings"),
+    ("println", "printnl"),
+    ("std::collections::BTreeMap", "std::collection::BtreeMap"),
+];
+
+/// The same pairs with every letter moved to the Cyrillic block, which is above 255 and so
+/// doesn't take the bit-parallel path.
+fn cyrillic(s: &str) -> String {
+    s.chars()
+        .map(|c| match c {
+            'a'..='z' => char::from_u32(0x430 + (c as u32 - 'a' as u32)).unwrap(),
+            'A'..='Z' => char::from_u32(0x410 + (c as u32 - 'A' as u32)).unwrap(),
+            _ => c,
+        })
+        .collect()
+}
+
+fn identifiers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("identifiers");
+    group.bench_function("ascii", |bench| {
+        bench.iter(|| {
+            for &(a, b) in IDENTIFIERS {
+                black_box(edit_distance(black_box(a), black_box(b), usize::MAX));
+            }
+        })
+    });
+
+    let cyrillic = IDENTIFIERS
+        .iter()
+        .map(|&(a, b)| (cyrillic(a), cyrillic(b)))
+        .collect::<Vec<_>>();
+    group.bench_function("cyrillic", |bench| {
+        bench.iter(|| {
+            for (a, b) in &cyrillic {
+                black_box(edit_distance(black_box(a), black_box(b), usize::MAX));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn long_inputs(c: &mut Criterion) {
+    let a = "/home/user/projects/rustc-edit-distance/target/debug/build/".repeat(8);
+    let b = a.replace("debug", "bedug");
+    let mut group = c.benchmark_group("long_inputs");
+    for (name, limit) in [("small_limit", 2), ("no_limit", usize::MAX)] {
+        group.bench_function(name, |bench| {
+            bench.iter(|| black_box(edit_distance(black_box(&a), black_box(&b), limit)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, identifiers, long_inputs);
+criterion_main!(benches);
@@ -0,0 +1,57 @@
+/*
+ * The C interface of rustc-edit-distance, built with the `ffi` feature.
+ *
+ * Every string is a NUL-terminated UTF-8 string owned by the caller. It is only read during
+ * the call, and never kept or freed. Nothing is allocated for the caller either: results are
+ * written through the pointers it passes, and a best match is returned as an index into its
+ * array. Every function returns a status, one of the RED_* constants, and only writes a
+ * result for RED_OK.
+ *
+ * Keep in sync with src/ffi.rs.
+ */
+
+#ifndef RUSTC_EDIT_DISTANCE_H
+#define RUSTC_EDIT_DISTANCE_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The call succeeded, and its result was written. */
+#define RED_OK 0
+/* The distance exceeds the limit, or no candidate matches. */
+#define RED_NOT_FOUND 1
+/* A pointer that has to be non-null is null. */
+#define RED_NULL_POINTER (-1)
+/* A string isn't valid UTF-8. */
+#define RED_INVALID_UTF8 (-2)
+
+/* Passed as the distance of red_find_best_match to use the default one. */
+#define RED_DEFAULT_DISTANCE SIZE_MAX
+
+/*
+ * Finds the edit distance between a and b, and writes it to distance.
+ * Returns RED_NOT_FOUND if the distance exceeds limit.
+ */
+int32_t red_edit_distance(const char *a, const char *b, size_t limit, size_t *distance);
+
+/* Finds the similarity ratio of a and b, from 0 to 1, and writes it to similarity. */
+int32_t red_similarity(const char *a, const char *b, double *similarity);
+
+/*
+ * Finds the candidate that best matches lookup, as find_best_match_for_name does, and writes
+ * its index in candidates to index. dist is the greatest edit distance of a match, or
+ * RED_DEFAULT_DISTANCE. candidates may only be null if len is 0.
+ * Returns RED_NOT_FOUND if no candidate matches.
+ */
+int32_t red_find_best_match(const char *const *candidates, size_t len, const char *lookup,
+                            size_t dist, size_t *index);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* RUSTC_EDIT_DISTANCE_H */
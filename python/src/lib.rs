@@ -0,0 +1,62 @@
+//! Python bindings of `rustc-edit-distance`, as the `rustc_edit_distance` module.
+
+use pyo3::prelude::*;
+use rustc_edit_distance::{find_best_match_for_name_with, MatchOptions};
+
+/// The edit distance between `a` and `b`, or `None` if it exceeds `limit`.
+#[pyfunction]
+#[pyo3(signature = (a, b, limit=None))]
+fn edit_distance(a: &str, b: &str, limit: Option<usize>) -> Option<usize> {
+    rustc_edit_distance::edit_distance(a, b, limit.unwrap_or(usize::MAX))
+}
+
+/// The score of `a` and `b` that makes a substring of the other string a near match, as rustc
+/// scores substrings for its suggestions, or `None` if it exceeds `limit`.
+#[pyfunction]
+#[pyo3(signature = (a, b, limit=None))]
+fn edit_distance_with_substrings(a: &str, b: &str, limit: Option<usize>) -> Option<usize> {
+    rustc_edit_distance::edit_distance_with_substrings(a, b, limit.unwrap_or(usize::MAX))
+}
+
+/// The edit distance from `lookup` to each of `candidates`, in order, each `None` if it exceeds
+/// `limit`.
+#[pyfunction]
+#[pyo3(signature = (lookup, candidates, limit=None))]
+fn edit_distances(
+    py: Python<'_>,
+    lookup: &str,
+    candidates: Vec<String>,
+    limit: Option<usize>,
+) -> Vec<Option<usize>> {
+    py.allow_threads(|| {
+        let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+        rustc_edit_distance::edit_distances(lookup, &candidates, limit.unwrap_or(usize::MAX))
+    })
+}
+
+/// The candidate that best matches `lookup`, as rustc finds it for "did you mean"
+/// suggestions, or `None`.
+#[pyfunction]
+#[pyo3(signature = (candidates, lookup, dist=None))]
+fn find_best_match_for_name(
+    candidates: Vec<String>,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<String> {
+    let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+    let mut options = MatchOptions::new();
+    if let Some(dist) = dist {
+        options = options.max_distance(dist);
+    }
+    find_best_match_for_name_with(&candidates, lookup, &options).map(String::from)
+}
+
+#[pymodule]
+#[pyo3(name = "rustc_edit_distance")]
+fn rustc_edit_distance_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(edit_distance, module)?)?;
+    module.add_function(wrap_pyfunction!(edit_distance_with_substrings, module)?)?;
+    module.add_function(wrap_pyfunction!(edit_distances, module)?)?;
+    module.add_function(wrap_pyfunction!(find_best_match_for_name, module)?)?;
+    Ok(())
+}
@@ -0,0 +1,19 @@
+import rustc_edit_distance as red
+
+
+def test_edit_distance():
+    assert red.edit_distance("kitten", "sitting") == 3
+    assert red.edit_distance("kitten", "sitting", limit=2) is None
+
+
+def test_edit_distance_with_substrings():
+    assert red.edit_distance_with_substrings("std", "stdio") == 1
+
+
+def test_edit_distances():
+    assert red.edit_distances("stauts", ["status", "start", "show"], limit=2) == [1, 2, None]
+
+
+def test_find_best_match_for_name():
+    assert red.find_best_match_for_name(["width", "length"], "lenght") == "length"
+    assert red.find_best_match_for_name(["width", "length"], "xyz") is None
@@ -0,0 +1,62 @@
+use alloc::{vec, vec::Vec};
+
+use crate::{words::is_word_start, Chars};
+
+/// Scores how well `abbreviation` abbreviates `candidate`, e.g. `"hm"` for `"HashMap"` or
+/// `"btm"` for `"BTreeMap"`, which are far apart by edit distance.
+///
+/// `abbreviation` has to be a subsequence of `candidate` that starts with its first char,
+/// ignoring case. Of the ways to pick its chars out of `candidate`, the score is the fewest
+/// chars that don't start a word: a word of `snake_case`, `kebab-case`, `camelCase` or
+/// `PascalCase`, or a run of digits. So `0` means that `abbreviation` is made of initials
+/// only, and `"hmap"` scores 2 against `"HashMap"`.
+///
+/// Returns `None` if `abbreviation` is empty or isn't an abbreviation of `candidate`.
+///
+/// ```
+/// use rustc_edit_distance::abbreviation_score;
+///
+/// assert_eq!(abbreviation_score("hm", "HashMap"), Some(0));
+/// assert_eq!(abbreviation_score("btm", "BTreeMap"), Some(0));
+/// assert_eq!(abbreviation_score("fbr", "foo_bar"), Some(1));
+/// assert_eq!(abbreviation_score("mh", "HashMap"), None);
+/// ```
+pub fn abbreviation_score(abbreviation: &str, candidate: &str) -> Option<usize> {
+    let abbreviation = abbreviation.chars().collect::<Chars>();
+    let candidate = candidate.chars().collect::<Chars>();
+    let (&first, rest) = abbreviation.split_first()?;
+    if !candidate
+        .first()
+        .map_or(false, |&c| eq_ignore_case(c, first))
+    {
+        return None;
+    }
+
+    // What each char costs, i.e. 0 at the start of a word and 1 elsewhere.
+    let costs = (0..candidate.len())
+        .map(|i| usize::from(!is_word_start(&candidate, i)))
+        .collect::<Vec<_>>();
+
+    // `row[j]` is the smallest cost of picking the chars of `abbreviation` so far out of the
+    // first `j` chars of `candidate`. The first char is always the first of `candidate`.
+    let mut row = vec![Some(costs[0]); candidate.len() + 1];
+    row[0] = None;
+    for &a in rest {
+        let mut next: Vec<Option<usize>> = vec![None; candidate.len() + 1];
+        for j in 1..=candidate.len() {
+            let picked = row[j - 1]
+                .filter(|_| eq_ignore_case(candidate[j - 1], a))
+                .map(|cost| cost + costs[j - 1]);
+            next[j] = match (next[j - 1], picked) {
+                (Some(skipped), Some(picked)) => Some(skipped.min(picked)),
+                (skipped, picked) => skipped.or(picked),
+            };
+        }
+        row = next;
+    }
+    row[candidate.len()]
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
@@ -0,0 +1,460 @@
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+/// The scores of aligning chars with each other, or with a gap, for [`global_align`] and
+/// [`local_align`].
+///
+/// Unlike the costs of [`EditCosts`](crate::EditCosts), scores are better the higher they
+/// are, and are usually negative for mismatches and gaps. A pair of chars scores what was set
+/// for it with [`ScoringScheme::score`], e.g. from a substitution matrix like BLOSUM62, and
+/// otherwise the match or mismatch score.
+///
+/// ```
+/// use rustc_edit_distance::ScoringScheme;
+///
+/// let scheme = ScoringScheme::new(2, -1, -2).score('A', 'G', 1);
+/// assert_eq!(scheme.substitution('A', 'A'), 2);
+/// assert_eq!(scheme.substitution('G', 'A'), 1);
+/// assert_eq!(scheme.substitution('A', 'T'), -1);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScoringScheme {
+    match_score: i32,
+    mismatch_score: i32,
+    gap: i32,
+    gap_extension: i32,
+    /// The scores set for pairs of chars, with the smaller char first.
+    pairs: BTreeMap<(char, char), i32>,
+}
+
+impl ScoringScheme {
+    /// Scores `match_score` for equal chars, `mismatch_score` for different ones, and `gap` for
+    /// each char aligned with a gap.
+    pub fn new(match_score: i32, mismatch_score: i32, gap: i32) -> Self {
+        ScoringScheme {
+            match_score,
+            mismatch_score,
+            gap,
+            gap_extension: gap,
+            pairs: BTreeMap::new(),
+        }
+    }
+
+    /// Scores `score` for aligning `a` with `b`, either way around.
+    pub fn score(mut self, a: char, b: char, score: i32) -> Self {
+        self.pairs.insert(ordered(a, b), score);
+        self
+    }
+
+    /// Scores [affine gaps]: `gap` only for the first char of a run of chars aligned with gaps,
+    /// and `extend` for each of the others, e.g. to score a run of missing chars higher than as
+    /// many gaps scattered about, as by Gotoh's algorithm. Defaults to `gap`.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{global_align, ScoringScheme};
+    ///
+    /// let scheme = ScoringScheme::new(1, -1, -3);
+    /// let affine = scheme.clone().gap_extension(-1);
+    /// assert_eq!(global_align("ACGTTA", "ACA", &scheme).score, -6);
+    /// assert_eq!(global_align("ACGTTA", "ACA", &affine).score, -2);
+    /// ```
+    ///
+    /// [affine gaps]: https://en.wikipedia.org/wiki/Gap_penalty#Affine
+    pub fn gap_extension(mut self, extend: i32) -> Self {
+        self.gap_extension = extend;
+        self
+    }
+
+    /// Returns the score of aligning `a` with `b`.
+    pub fn substitution(&self, a: char, b: char) -> i32 {
+        match self.pairs.get(&ordered(a, b)) {
+            Some(&score) => score,
+            None if a == b => self.match_score,
+            None => self.mismatch_score,
+        }
+    }
+
+    /// Returns the score of aligning a char with a gap, at the start of a run of gaps.
+    pub fn gap(&self) -> i32 {
+        self.gap
+    }
+
+    /// Returns the score of a run of `len` chars aligned with gaps.
+    pub fn gap_run(&self, len: usize) -> i64 {
+        match len {
+            0 => 0,
+            len => i64::from(self.gap) + (len as i64 - 1) * i64::from(self.gap_extension),
+        }
+    }
+}
+
+impl Default for ScoringScheme {
+    /// Scores 1 for a match, and -1 for a mismatch or a gap.
+    fn default() -> Self {
+        ScoringScheme::new(1, -1, -1)
+    }
+}
+
+fn ordered(a: char, b: char) -> (char, char) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// The best-scoring alignment of two strings, as found by [`global_align`], or of two of their
+/// substrings, as found by [`local_align`].
+///
+/// Indices are in chars, not bytes.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Alignment {
+    /// The sum of the scores of the columns of the alignment.
+    pub score: i64,
+    /// The chars of `a` that are aligned, which are all of them for a global alignment.
+    pub a: Range<usize>,
+    /// The chars of `b` that are aligned, which are all of them for a global alignment.
+    pub b: Range<usize>,
+    /// The columns of the alignment, in order: the index of a char of `a` and one of `b`
+    /// aligned with each other, or one of them aligned with a gap, as `None`.
+    pub columns: Vec<(Option<usize>, Option<usize>)>,
+}
+
+impl Alignment {
+    /// Renders the alignment of `a` and `b`, which are the strings it was found for, as two
+    /// strings of the same number of chars, with `gap` for the gaps.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{global_align, ScoringScheme};
+    ///
+    /// let alignment = global_align("GATTACA", "GCATGCU", &ScoringScheme::default());
+    /// assert_eq!(alignment.score, 0);
+    /// assert_eq!(alignment.gapped("GATTACA", "GCATGCU", '-'), ("G-ATTACA".into(), "GCA-TGCU".into()));
+    /// ```
+    pub fn gapped(&self, a: &str, b: &str, gap: char) -> (String, String) {
+        let a = a.chars().collect::<Vec<_>>();
+        let b = b.chars().collect::<Vec<_>>();
+        let render = |s: &[char], i: Option<usize>| i.map_or(gap, |i| s[i]);
+        self.columns
+            .iter()
+            .map(|&(i, j)| (render(&a, i), render(&b, j)))
+            .unzip()
+    }
+}
+
+/// Finds the [Needleman-Wunsch] global alignment of `a` and `b`: the way of lining up all of
+/// their chars, with gaps in between, whose columns have the highest total score by `scheme`.
+///
+/// When several alignments score the same, the one returned prefers aligning chars with each
+/// other, then a char of `a` with a gap, matching from the ends of the strings.
+///
+/// This takes time and memory proportional to the product of the lengths of the strings.
+///
+/// ```
+/// use rustc_edit_distance::{global_align, ScoringScheme};
+///
+/// let alignment = global_align("ACGT", "AGT", &ScoringScheme::new(1, -1, -2));
+/// assert_eq!(alignment.score, 1);
+/// assert_eq!(alignment.columns, [(Some(0), Some(0)), (Some(1), None), (Some(2), Some(1)), (Some(3), Some(2))]);
+/// ```
+///
+/// [Needleman-Wunsch]: https://en.wikipedia.org/wiki/Needleman%E2%80%93Wunsch_algorithm
+pub fn global_align(a: &str, b: &str, scheme: &ScoringScheme) -> Alignment {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    align(&a, &b, scheme, false)
+}
+
+/// Like [`global_align`], but takes memory proportional to the length of the shorter string
+/// instead of the product of both lengths, e.g. for whole documents.
+///
+/// The alignment is found by [Hirschberg's algorithm], as extended to affine gaps by Myers and
+/// Miller: the scores of both halves of `a` against every split of `b` are found a row at a
+/// time, and the halves are aligned around the best split, down to single chars. A split can
+/// fall in the middle of a run of gaps, which then opens only once. This takes about twice as
+/// long as [`global_align`], and when several alignments score the same, it may return another
+/// one.
+///
+/// ```
+/// use rustc_edit_distance::{global_align, global_align_linear_space, ScoringScheme};
+///
+/// let scheme = ScoringScheme::new(1, -1, -2);
+/// let alignment = global_align_linear_space("ACGT", "AGT", &scheme);
+/// assert_eq!(alignment, global_align("ACGT", "AGT", &scheme));
+///
+/// let affine = scheme.gap_extension(-1);
+/// let alignment = global_align_linear_space("ACGTTA", "ACA", &affine);
+/// assert_eq!(alignment.score, global_align("ACGTTA", "ACA", &affine).score);
+/// ```
+///
+/// [Hirschberg's algorithm]: https://en.wikipedia.org/wiki/Hirschberg%27s_algorithm
+pub fn global_align_linear_space(a: &str, b: &str, scheme: &ScoringScheme) -> Alignment {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    // The rows are as long as `b`, so it should be the shorter string. Scores are the same
+    // either way around.
+    let swapped = a.len() < b.len();
+    let (a, b) = if swapped { (&b, &a) } else { (&a, &b) };
+    let opening = gap_scores(scheme).0;
+    let score = last_rows(a, b, scheme, opening).0[b.len()];
+    let mut columns = Vec::with_capacity(a.len() + b.len());
+    myers_miller(&mut columns, a, b, (0, 0), opening, opening, scheme);
+    let (mut a_chars, mut b_chars) = (0..a.len(), 0..b.len());
+    if swapped {
+        for column in &mut columns {
+            *column = (column.1, column.0);
+        }
+        core::mem::swap(&mut a_chars, &mut b_chars);
+    }
+    Alignment {
+        score,
+        a: a_chars,
+        b: b_chars,
+        columns,
+    }
+}
+
+/// Low enough to never be the best score, and high enough for adding scores to not overflow.
+const NONE: i64 = i64::MIN / 4;
+
+/// Returns what opening a run of gaps scores on top of extending it, and what extending it
+/// scores, so that a run of `len` gaps scores `opening + len * extend`.
+fn gap_scores(scheme: &ScoringScheme) -> (i64, i64) {
+    let extend = i64::from(scheme.gap_extension);
+    (i64::from(scheme.gap) - extend, extend)
+}
+
+/// Pushes the columns of the global alignment of `a` and `b` onto `columns`, with `offset`
+/// added to their indices, in memory proportional to the length of `b`.
+///
+/// A run of chars of `a` aligned with gaps at the start scores `start` instead of the usual
+/// opening score, and one at the end scores `end`, which is 0 where it carries on a run of the
+/// caller's.
+fn myers_miller(
+    columns: &mut Vec<(Option<usize>, Option<usize>)>,
+    a: &[char],
+    b: &[char],
+    offset: (usize, usize),
+    start: i64,
+    end: i64,
+    scheme: &ScoringScheme,
+) {
+    let a_gaps = |range: Range<usize>| range.map(move |i| (Some(offset.0 + i), None));
+    let b_gaps = |range: Range<usize>| range.map(move |j| (None, Some(offset.1 + j)));
+    if a.is_empty() || b.is_empty() {
+        columns.extend(a_gaps(0..a.len()).chain(b_gaps(0..b.len())));
+        return;
+    }
+    let (opening, extend) = gap_scores(scheme);
+    let m = b.len();
+    if a.len() == 1 {
+        // The char is aligned with one of `b`, with runs of gaps around it, or with a gap of
+        // its own, before or after a run. Opening more runs only helps if opening one scores
+        // above 0.
+        let run = |len: usize| match len {
+            0 => 0,
+            len if opening > 0 => len as i64 * (opening + extend),
+            len => opening + len as i64 * extend,
+        };
+        let aligned =
+            |k: usize| run(k) + i64::from(scheme.substitution(a[0], b[k])) + run(m - k - 1);
+        let k = (0..m).rev().max_by_key(|&k| aligned(k)).unwrap_or(0);
+        let (first, last) = (start.max(opening), end.max(opening));
+        if aligned(k) >= first.max(last) + extend + run(m) {
+            columns.extend(b_gaps(0..k));
+            columns.push((Some(offset.0), Some(offset.1 + k)));
+            columns.extend(b_gaps(k + 1..m));
+        } else if first >= last {
+            columns.extend(a_gaps(0..1).chain(b_gaps(0..m)));
+        } else {
+            columns.extend(b_gaps(0..m).chain(a_gaps(0..1)));
+        }
+        return;
+    }
+
+    // The scores of the first half of `a` against the prefixes of `b`, and of the second half
+    // backwards against the suffixes of `b` backwards: `after[k]` is the score against the
+    // last `k` chars. The gap rows are the scores that end in a char of `a` aligned with a
+    // gap, and two such alignments join into one run, which opens once.
+    let mid = a.len() / 2;
+    let (before, before_gaps) = last_rows(&a[..mid], b, scheme, start);
+    let (after, after_gaps) = {
+        let reversed = |s: &[char]| s.iter().rev().copied().collect::<Vec<_>>();
+        last_rows(&reversed(&a[mid..]), &reversed(b), scheme, end)
+    };
+    let mut best = (0, false, before[0] + after[m]);
+    for j in 0..=m {
+        if before[j] + after[m - j] > best.2 {
+            best = (j, false, before[j] + after[m - j]);
+        }
+        if before_gaps[j] + after_gaps[m - j] - opening > best.2 {
+            best = (j, true, before_gaps[j] + after_gaps[m - j] - opening);
+        }
+    }
+
+    let j = best.0;
+    if best.1 {
+        // The run spans the last char of the first half and the first of the second.
+        myers_miller(columns, &a[..mid - 1], &b[..j], offset, start, 0, scheme);
+        columns.extend(a_gaps(mid - 1..mid + 1));
+        let offset = (offset.0 + mid + 1, offset.1 + j);
+        myers_miller(columns, &a[mid + 1..], &b[j..], offset, 0, end, scheme);
+    } else {
+        myers_miller(columns, &a[..mid], &b[..j], offset, start, opening, scheme);
+        let offset = (offset.0 + mid, offset.1 + j);
+        myers_miller(columns, &a[mid..], &b[j..], offset, opening, end, scheme);
+    }
+}
+
+/// Returns the best scores of aligning all of `a` with each prefix of `b`, and the best of
+/// those that end in a char of `a` aligned with a gap, where a run of such gaps at the start
+/// scores `start` to open.
+fn last_rows(a: &[char], b: &[char], scheme: &ScoringScheme, start: i64) -> (Vec<i64>, Vec<i64>) {
+    let (opening, extend) = gap_scores(scheme);
+    let mut scores = vec![0; b.len() + 1];
+    let mut a_gaps = vec![NONE; b.len() + 1];
+    a_gaps[0] = start;
+    let mut b_gap = NONE;
+    for j in 1..=b.len() {
+        b_gap = (scores[j - 1] + opening + extend).max(b_gap + extend);
+        scores[j] = b_gap;
+    }
+    for &x in a {
+        let mut diagonal = scores[0];
+        a_gaps[0] = (scores[0] + opening + extend).max(a_gaps[0] + extend);
+        scores[0] = a_gaps[0];
+        b_gap = NONE;
+        for (j, &y) in b.iter().enumerate() {
+            a_gaps[j + 1] = (scores[j + 1] + opening + extend).max(a_gaps[j + 1] + extend);
+            b_gap = (scores[j] + opening + extend).max(b_gap + extend);
+            let score = (diagonal + i64::from(scheme.substitution(x, y)))
+                .max(a_gaps[j + 1])
+                .max(b_gap);
+            diagonal = scores[j + 1];
+            scores[j + 1] = score;
+        }
+    }
+    (scores, a_gaps)
+}
+
+/// Finds the [Smith-Waterman] local alignment of `a` and `b`: the substrings of each, and the
+/// way of lining them up, whose columns have the highest total score by `scheme`, e.g. to find
+/// the most similar region of two strings.
+///
+/// The substrings are in [`Alignment::a`] and [`Alignment::b`]. They are empty, with a score
+/// of 0, if no pair of chars scores above 0. When several alignments score the same, the one
+/// returned is the one that ends first in `a`, then in `b`, with the same preferences for its
+/// columns as in [`global_align`].
+///
+/// This takes time and memory proportional to the product of the lengths of the strings.
+///
+/// ```
+/// use rustc_edit_distance::{local_align, ScoringScheme};
+///
+/// let (a, b) = ("xxxxGATTACAyy", "zzGATCACAzzzz");
+/// let alignment = local_align(a, b, &ScoringScheme::new(2, -1, -2));
+/// assert_eq!(alignment.a, 4..11);
+/// assert_eq!(alignment.b, 2..9);
+/// assert_eq!(alignment.score, 11);
+/// assert_eq!(alignment.gapped(a, b, '-'), ("GATTACA".into(), "GATCACA".into()));
+/// ```
+///
+/// [Smith-Waterman]: https://en.wikipedia.org/wiki/Smith%E2%80%93Waterman_algorithm
+pub fn local_align(a: &str, b: &str, scheme: &ScoringScheme) -> Alignment {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    align(&a, &b, scheme, true)
+}
+
+/// Aligns all of `a` and `b`, or, if `local`, the best-scoring substrings of them, by Gotoh's
+/// algorithm.
+fn align(a: &[char], b: &[char], scheme: &ScoringScheme, local: bool) -> Alignment {
+    let (open, extend) = (i64::from(scheme.gap), i64::from(scheme.gap_extension));
+    let substitution = |i: usize, j: usize| i64::from(scheme.substitution(a[i], b[j]));
+
+    // `scores[i][j]` is the best score of aligning the first `i` chars of `a` with the first
+    // `j` chars of `b`, or, if `local`, of any of their suffixes, which is never below 0, as
+    // the empty suffixes score 0. `a_gaps` is the best one that ends in a char of `a` aligned
+    // with a gap, and `b_gaps` the best one that ends in a char of `b` aligned with one.
+    let floor = if local { 0 } else { NONE };
+    let mut scores = vec![vec![0; b.len() + 1]; a.len() + 1];
+    let mut a_gaps = vec![vec![NONE; b.len() + 1]; a.len() + 1];
+    let mut b_gaps = a_gaps.clone();
+    for j in 1..=b.len() {
+        b_gaps[0][j] = (scores[0][j - 1] + open).max(b_gaps[0][j - 1] + extend);
+        scores[0][j] = b_gaps[0][j].max(floor);
+    }
+    // Where the best local alignment ends.
+    let mut end = (0, 0);
+    for i in 1..=a.len() {
+        a_gaps[i][0] = (scores[i - 1][0] + open).max(a_gaps[i - 1][0] + extend);
+        scores[i][0] = a_gaps[i][0].max(floor);
+        for j in 1..=b.len() {
+            a_gaps[i][j] = (scores[i - 1][j] + open).max(a_gaps[i - 1][j] + extend);
+            b_gaps[i][j] = (scores[i][j - 1] + open).max(b_gaps[i][j - 1] + extend);
+            scores[i][j] = (scores[i - 1][j - 1] + substitution(i - 1, j - 1))
+                .max(a_gaps[i][j])
+                .max(b_gaps[i][j])
+                .max(floor);
+            if scores[i][j] > scores[end.0][end.1] {
+                end = (i, j);
+            }
+        }
+    }
+    if !local {
+        end = (a.len(), b.len());
+    }
+
+    // Whether the traceback is in a run of gaps, which has to be followed to where it opened.
+    enum State {
+        Any,
+        AGap,
+        BGap,
+    }
+
+    let mut columns = Vec::with_capacity(a.len() + b.len());
+    let (mut i, mut j) = end;
+    let mut state = State::Any;
+    loop {
+        match state {
+            State::Any => {
+                let score = scores[i][j];
+                // A local alignment starts after the last cell where its score was 0.
+                if (local && score == 0) || (i == 0 && j == 0) {
+                    break;
+                }
+                if i > 0 && j > 0 && score == scores[i - 1][j - 1] + substitution(i - 1, j - 1) {
+                    columns.push((Some(i - 1), Some(j - 1)));
+                    i -= 1;
+                    j -= 1;
+                } else if i > 0 && score == a_gaps[i][j] {
+                    state = State::AGap;
+                } else {
+                    state = State::BGap;
+                }
+            }
+            State::AGap => {
+                columns.push((Some(i - 1), None));
+                if a_gaps[i][j] == scores[i - 1][j] + open {
+                    state = State::Any;
+                }
+                i -= 1;
+            }
+            State::BGap => {
+                columns.push((None, Some(j - 1)));
+                if b_gaps[i][j] == scores[i][j - 1] + open {
+                    state = State::Any;
+                }
+                j -= 1;
+            }
+        }
+    }
+    columns.reverse();
+
+    Alignment {
+        score: scores[end.0][end.1],
+        a: i..end.0,
+        b: j..end.1,
+        columns,
+    }
+}
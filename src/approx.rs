@@ -0,0 +1,189 @@
+use alloc::{vec, vec::Vec};
+use core::{cmp, mem, str::CharIndices};
+
+use crate::{bit_parallel, Chars};
+
+/// Where [`find_approx`] found a pattern in a text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ApproxMatch {
+    /// The byte offset in the text where the match starts.
+    pub start: usize,
+    /// The byte offset in the text where the match ends, exclusive.
+    pub end: usize,
+    /// The [`edit_distance`](crate::edit_distance) between the pattern and the match.
+    pub dist: usize,
+}
+
+/// Finds the places in `text` where `pattern` occurs with at most `k` edits, using [Sellers'
+/// algorithm], in order.
+///
+/// This fills in the same table as [`edit_distance`](crate::edit_distance), with the pattern
+/// along one side and the text along the other, except that a match may start anywhere in the
+/// text for free. The text is read lazily, one char at a time, and only a few columns of the
+/// length of the pattern are kept, so the text can be arbitrarily long.
+///
+/// Every end within `k` edits of the pattern is next to others, with a char more or less of
+/// the text, so only the closest match of each run of consecutive ends is returned, the first
+/// one on a tie. Of the matches with the same end and distance, it is the longest one. An
+/// empty pattern matches nowhere.
+///
+/// ```
+/// use rustc_edit_distance::{find_approx, ApproxMatch};
+///
+/// let text = "a neeedle in a haystack, and a nedle";
+/// let matches = find_approx("needle", text, 1).collect::<Vec<_>>();
+/// assert_eq!(
+///     matches,
+///     [
+///         ApproxMatch { start: 2, end: 9, dist: 1 },
+///         ApproxMatch { start: 31, end: 36, dist: 1 },
+///     ]
+/// );
+/// assert_eq!(&text[2..9], "neeedle");
+/// ```
+///
+/// [Sellers' algorithm]: https://en.wikipedia.org/wiki/Approximate_string_matching
+pub fn find_approx<'t>(
+    pattern: &str,
+    text: &'t str,
+    k: usize,
+) -> impl Iterator<Item = ApproxMatch> + 't {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    ApproxMatches {
+        done: pattern.is_empty(),
+        table: Table::new(pattern),
+        k,
+        text: text.char_indices(),
+        best: None,
+    }
+}
+
+/// Whether `haystack` contains a substring within `max_dist` edits of `needle`, as
+/// [`find_approx`] would find.
+///
+/// This is cheaper than [`find_approx`] when only the answer is needed: for needles of up to
+/// 64 chars, the table is computed with the same bit-parallel algorithm as
+/// [`edit_distance`](crate::edit_distance), without tracking where matches start, and the
+/// haystack is only read up to the end of the first match. An empty needle, or one of at most
+/// `max_dist` chars, is contained in everything.
+///
+/// ```
+/// use rustc_edit_distance::fuzzy_contains;
+///
+/// let line = "error: conection refused by host";
+/// assert!(fuzzy_contains(line, "connection refused", 1));
+/// assert!(!fuzzy_contains(line, "connection reset", 1));
+/// ```
+pub fn fuzzy_contains(haystack: &str, needle: &str, max_dist: usize) -> bool {
+    let chars = needle.chars().collect::<Chars>();
+    if chars.len() <= max_dist {
+        return true;
+    }
+    if chars.len() <= bit_parallel::MAX_LEN && chars.iter().all(|&c| u32::from(c) <= 0xff) {
+        return bit_parallel::contains(haystack.chars(), &chars, max_dist);
+    }
+    find_approx(needle, haystack, max_dist).next().is_some()
+}
+
+/// The distance of the closest match that ends at a cell, and the byte offset where it starts.
+/// On a tie, the smaller cell, the match that starts first, is kept.
+type Cell = (usize, usize);
+
+/// The last columns of the table of Sellers' algorithm, with one cell per prefix of the
+/// pattern.
+#[derive(Clone, Debug)]
+pub(crate) struct Table {
+    pattern: Vec<char>,
+    /// The columns of the two chars of the text before, and of the current one.
+    prev_prev: Vec<Cell>,
+    prev: Vec<Cell>,
+    current: Vec<Cell>,
+    /// The char of the text of `prev`, if any.
+    last_char: Option<char>,
+}
+
+impl Table {
+    pub(crate) fn new(pattern: Vec<char>) -> Self {
+        let len = pattern.len() + 1;
+        Table {
+            pattern,
+            // Before the first char of the text, every char of the pattern has to be deleted.
+            prev: (0..len).map(|i| (i, 0)).collect(),
+            prev_prev: vec![(usize::MAX, 0); len],
+            current: vec![(0, 0); len],
+            last_char: None,
+        }
+    }
+
+    /// Moves on to the next char of the text, `t`, which ends at the byte offset `end`, and
+    /// returns the distance of the closest match ending there and where it starts.
+    pub(crate) fn advance(&mut self, t: char, end: usize) -> Cell {
+        // A match starts anywhere for free, so the empty prefix of the pattern is always
+        // matched, with nothing of the text.
+        self.current[0] = (0, end);
+        for i in 1..self.current.len() {
+            let p = self.pattern[i - 1];
+            // There is no cost to substitute a character with itself.
+            let substitution_cost = if p == t { 0 } else { 1 };
+
+            let (d, start) = self.prev[i - 1];
+            // substitution
+            let mut cell = (d + substitution_cost, start);
+            // insertion of the char of the text
+            cell = cmp::min(cell, (self.prev[i].0 + 1, self.prev[i].1));
+            // deletion of the char of the pattern
+            cell = cmp::min(cell, (self.current[i - 1].0 + 1, self.current[i - 1].1));
+            if i > 1 && self.last_char == Some(p) && self.pattern[i - 2] == t {
+                // transposition
+                let (d, start) = self.prev_prev[i - 2];
+                cell = cmp::min(cell, (d + 1, start));
+            }
+            self.current[i] = cell;
+        }
+
+        // Rotate the buffers, reusing the memory.
+        mem::swap(&mut self.prev_prev, &mut self.prev);
+        mem::swap(&mut self.prev, &mut self.current);
+        self.last_char = Some(t);
+        self.prev[self.pattern.len()]
+    }
+}
+
+struct ApproxMatches<'t> {
+    table: Table,
+    k: usize,
+    text: CharIndices<'t>,
+    /// The closest match of the current run of ends within `k` edits.
+    best: Option<ApproxMatch>,
+    done: bool,
+}
+
+impl Iterator for ApproxMatches<'_> {
+    type Item = ApproxMatch;
+
+    fn next(&mut self) -> Option<ApproxMatch> {
+        if self.done {
+            return None;
+        }
+
+        loop {
+            let (offset, t) = match self.text.next() {
+                Some(next) => next,
+                None => {
+                    self.done = true;
+                    return self.best.take();
+                }
+            };
+            let end = offset + t.len_utf8();
+
+            let (dist, start) = self.table.advance(t, end);
+            if dist <= self.k {
+                if self.best.map_or(true, |best| dist < best.dist) {
+                    self.best = Some(ApproxMatch { start, end, dist });
+                }
+            } else if let Some(best) = self.best.take() {
+                return Some(best);
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use alloc::{vec, vec::Vec};
+
+use crate::fill_row;
+
+/// A [Levenshtein automaton]: a state machine that reads a string one char at a time and
+/// accepts it if it is within `max_dist` of a lookup, by [`edit_distance`].
+///
+/// Stepping the automaton through the chars of a trie or an FST, instead of computing the
+/// distance to each string in it, means that a prefix shared by many strings is only read
+/// once, and that a whole subtree can be skipped as soon as
+/// [`can_match`](LevenshteinAutomaton::can_match) is false.
+///
+/// A state is a row of the table of [`edit_distance`] with the one before it, where every
+/// distance over `max_dist` is the same. So there are finitely many states, and they can be
+/// hashed to build a DFA.
+///
+/// ```
+/// use rustc_edit_distance::LevenshteinAutomaton;
+///
+/// let automaton = LevenshteinAutomaton::new("config", 1);
+/// let mut state = automaton.start();
+/// for c in "confg".chars() {
+///     state = automaton.step(&state, c);
+/// }
+/// assert!(automaton.is_match(&state));
+/// assert_eq!(automaton.distance(&state), Some(1));
+///
+/// let state = "cxy".chars().fold(automaton.start(), |s, c| automaton.step(&s, c));
+/// assert!(!automaton.can_match(&state));
+/// ```
+///
+/// [Levenshtein automaton]: https://en.wikipedia.org/wiki/Levenshtein_automaton
+/// [`edit_distance`]: crate::edit_distance
+#[derive(Clone, Debug)]
+pub struct LevenshteinAutomaton {
+    lookup: Vec<char>,
+    max_dist: usize,
+}
+
+/// A state of a [`LevenshteinAutomaton`], after reading some string.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AutomatonState {
+    /// The distances from the string read to each prefix of the lookup, capped at one more
+    /// than `max_dist`.
+    row: Vec<usize>,
+    /// The same for the string without its last char, for transpositions.
+    prev: Vec<usize>,
+    /// The last char read.
+    last: Option<char>,
+}
+
+impl LevenshteinAutomaton {
+    /// Compiles an automaton for the strings within `max_dist` of `lookup`.
+    pub fn new(lookup: &str, max_dist: usize) -> Self {
+        LevenshteinAutomaton {
+            lookup: lookup.chars().collect(),
+            max_dist,
+        }
+    }
+
+    /// Returns the state before any chars are read.
+    pub fn start(&self) -> AutomatonState {
+        let cap = self.cap();
+        AutomatonState {
+            row: (0..=self.lookup.len()).map(|j| j.min(cap)).collect(),
+            prev: vec![cap; self.lookup.len() + 1],
+            last: None,
+        }
+    }
+
+    /// Returns the state after reading `c` in `state`.
+    pub fn step(&self, state: &AutomatonState, c: char) -> AutomatonState {
+        let cap = self.cap();
+        let mut row = vec![0; self.lookup.len() + 1];
+        row[0] = (state.row[0] + 1).min(cap);
+        fill_row(
+            &c,
+            state.last.as_ref(),
+            &self.lookup,
+            &state.prev,
+            &state.row,
+            &mut row,
+        );
+        for cell in &mut row {
+            *cell = (*cell).min(cap);
+        }
+        AutomatonState {
+            row,
+            prev: state.row.clone(),
+            last: Some(c),
+        }
+    }
+
+    /// Returns whether the string read to reach `state` is within `max_dist` of the lookup.
+    pub fn is_match(&self, state: &AutomatonState) -> bool {
+        self.distance(state).is_some()
+    }
+
+    /// Returns the distance from the string read to reach `state` to the lookup, or `None` if
+    /// it exceeds `max_dist`.
+    pub fn distance(&self, state: &AutomatonState) -> Option<usize> {
+        let distance = state.row[self.lookup.len()];
+        (distance <= self.max_dist).then_some(distance)
+    }
+
+    /// Returns whether any string that starts with the string read to reach `state` can be
+    /// within `max_dist` of the lookup. Once it is false, it stays false, so no more chars need
+    /// to be read.
+    pub fn can_match(&self, state: &AutomatonState) -> bool {
+        // No cell of the next row is less than the minimum of this row, or of the row before
+        // plus one for a transposition.
+        state.row.iter().any(|&d| d <= self.max_dist)
+            || state.prev.iter().any(|&d| d < self.max_dist)
+    }
+
+    /// The value of every distance over `max_dist`.
+    fn cap(&self) -> usize {
+        self.max_dist.saturating_add(1)
+    }
+}
@@ -0,0 +1,262 @@
+//! `red`: suggests and filters names from the shell, as the crate scores them.
+//!
+//! ```text
+//! red suggest [OPTIONS] <LOOKUP>
+//! red filter [OPTIONS] <LOOKUP>
+//! ```
+//!
+//! The candidates are read from `--candidates FILE`, or from stdin, one per line. The command
+//! exits with 1 if nothing matches, as `grep` does.
+
+use std::fs;
+use std::io::{self, BufWriter, Read, Write};
+use std::process::ExitCode;
+
+use clap::{value_parser, Arg, ArgAction, ArgMatches, Command};
+use rustc_edit_distance::{
+    default_match_distance, edit_distance_with_algorithm, find_best_matches, DistanceAlgorithm,
+    MatchPhase,
+};
+
+/// The algorithms of `--algorithm`.
+const ALGORITHMS: [&str; 4] = ["rustc", "osa", "levenshtein", "damerau-levenshtein"];
+
+fn cli() -> Command {
+    let args = [
+        Arg::new("lookup")
+            .value_name("LOOKUP")
+            .required(true)
+            .help("The name to match the candidates against"),
+        Arg::new("candidates")
+            .short('c')
+            .long("candidates")
+            .value_name("FILE")
+            .help("Reads the candidates from FILE instead of stdin, one per line"),
+        Arg::new("max-dist")
+            .short('d')
+            .long("max-dist")
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("The largest distance of a match [default: a third of the lookup's length]"),
+        Arg::new("top")
+            .short('n')
+            .long("top")
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("Prints at most N candidates [default: 1 for suggest, all for filter]"),
+        Arg::new("algorithm")
+            .short('a')
+            .long("algorithm")
+            .value_name("ALGORITHM")
+            .value_parser(ALGORITHMS)
+            .default_value("rustc")
+            .help(
+                "How candidates are scored: as rustc suggests names, or by one of the \
+                 distances alone",
+            ),
+        Arg::new("json")
+            .long("json")
+            .action(ArgAction::SetTrue)
+            .help("Prints a JSON array of the candidates, with their distances"),
+    ];
+    Command::new("red")
+        .version(env!("CARGO_PKG_VERSION"))
+        .about("Suggests and filters names by edit distance, as rustc does")
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .subcommand(
+            Command::new("suggest")
+                .about("Prints the candidates that best match the lookup, best first")
+                .args(args.clone()),
+        )
+        .subcommand(
+            Command::new("filter")
+                .about("Prints the candidates within the distance of the lookup, in order")
+                .args(args),
+        )
+}
+
+/// A candidate to print.
+struct Found<'a> {
+    candidate: &'a str,
+    distance: usize,
+    /// The kind of match, for `rustc` suggestions.
+    phase: Option<MatchPhase>,
+}
+
+fn main() -> ExitCode {
+    let matches = cli().get_matches();
+    let (command, args) = matches.subcommand().unwrap();
+
+    let input = match read_candidates(args.get_one::<String>("candidates")) {
+        Ok(input) => input,
+        Err(error) => {
+            eprintln!("red: {error}");
+            return ExitCode::from(2);
+        }
+    };
+    let candidates = input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>();
+    let lookup = args.get_one::<String>("lookup").unwrap();
+    let found = if command == "suggest" {
+        suggest(&candidates, lookup, args)
+    } else {
+        filter(&candidates, lookup, args)
+    };
+
+    match print(&found, args.get_flag("json")) {
+        Ok(()) => {}
+        // E.g. piped into `head`.
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => {}
+        Err(error) => {
+            eprintln!("red: {error}");
+            return ExitCode::from(2);
+        }
+    }
+    if found.is_empty() {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn read_candidates(path: Option<&String>) -> Result<String, String> {
+    match path.map(String::as_str) {
+        None | Some("-") => {
+            let mut input = String::new();
+            io::stdin()
+                .read_to_string(&mut input)
+                .map_err(|error| format!("stdin: {error}"))?;
+            Ok(input)
+        }
+        Some(path) => fs::read_to_string(path).map_err(|error| format!("{path}: {error}")),
+    }
+}
+
+/// The distance of `--algorithm`, or `None` for rustc's matching.
+fn algorithm(args: &ArgMatches) -> Option<DistanceAlgorithm> {
+    match args.get_one::<String>("algorithm").unwrap().as_str() {
+        "osa" => Some(DistanceAlgorithm::Osa),
+        "levenshtein" => Some(DistanceAlgorithm::Levenshtein),
+        "damerau-levenshtein" => Some(DistanceAlgorithm::DamerauLevenshtein),
+        _ => None,
+    }
+}
+
+fn suggest<'a>(candidates: &[&'a str], lookup: &str, args: &ArgMatches) -> Vec<Found<'a>> {
+    let max_dist = args.get_one::<usize>("max-dist").copied();
+    let top = args.get_one::<usize>("top").copied().unwrap_or(1);
+    let algorithm = match algorithm(args) {
+        Some(algorithm) => algorithm,
+        None => {
+            return find_best_matches(candidates, lookup, max_dist, top)
+                .into_iter()
+                .map(|m| Found {
+                    candidate: m.candidate,
+                    distance: m.score,
+                    phase: Some(m.phase),
+                })
+                .collect();
+        }
+    };
+
+    let limit = max_dist.unwrap_or_else(|| default_match_distance(lookup));
+    let mut found = distances(candidates, lookup, limit, algorithm);
+    found.sort_by(|a, b| (a.distance, a.candidate).cmp(&(b.distance, b.candidate)));
+    found.truncate(top);
+    found
+}
+
+fn filter<'a>(candidates: &[&'a str], lookup: &str, args: &ArgMatches) -> Vec<Found<'a>> {
+    let limit = args
+        .get_one::<usize>("max-dist")
+        .copied()
+        .unwrap_or_else(|| default_match_distance(lookup));
+    // rustc's distance, without its other kinds of match.
+    let algorithm = algorithm(args).unwrap_or(DistanceAlgorithm::Osa);
+    let mut found = distances(candidates, lookup, limit, algorithm);
+    if let Some(&top) = args.get_one::<usize>("top") {
+        found.truncate(top);
+    }
+    found
+}
+
+/// The candidates within `limit` of `lookup`, in order.
+fn distances<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    limit: usize,
+    algorithm: DistanceAlgorithm,
+) -> Vec<Found<'a>> {
+    candidates
+        .iter()
+        .filter_map(|&candidate| {
+            let distance = edit_distance_with_algorithm(algorithm, lookup, candidate, limit)?;
+            Some(Found {
+                candidate,
+                distance,
+                phase: None,
+            })
+        })
+        .collect()
+}
+
+fn print(found: &[Found<'_>], json: bool) -> io::Result<()> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    if !json {
+        for found in found {
+            writeln!(out, "{}", found.candidate)?;
+        }
+        return out.flush();
+    }
+
+    write!(out, "[")?;
+    for (i, found) in found.iter().enumerate() {
+        if i > 0 {
+            write!(out, ",")?;
+        }
+        write!(
+            out,
+            "{{\"candidate\":{},\"distance\":{}",
+            json_string(found.candidate),
+            found.distance
+        )?;
+        if let Some(phase) = found.phase {
+            write!(out, ",\"phase\":\"{}\"", phase_name(phase))?;
+        }
+        write!(out, "}}")?;
+    }
+    writeln!(out, "]")?;
+    out.flush()
+}
+
+fn phase_name(phase: MatchPhase) -> &'static str {
+    match phase {
+        MatchPhase::CaseInsensitive => "case-insensitive",
+        MatchPhase::EditDistance => "edit-distance",
+        MatchPhase::SortedWords => "sorted-words",
+        MatchPhase::Abbreviation => "abbreviation",
+        _ => "other",
+    }
+}
+
+/// Quotes `s` as a JSON string.
+fn json_string(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c if u32::from(c) < 0x20 => quoted.push_str(&format!("\\u{:04x}", u32::from(c))),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
@@ -0,0 +1,157 @@
+//! Hyyrö's bit-parallel algorithm for the optimal string alignment distance, an extension of
+//! Myers' algorithm for the Levenshtein distance to adjacent transpositions, and Myers'
+//! algorithm itself.
+//!
+//! One column of the table is packed into the bits of a `u64`, as vertical differences
+//! between its cells, so a whole column is computed with a few integer operations.
+//!
+//! Heikki Hyyrö, "A Bit-Vector Algorithm for Computing Levenshtein and Damerau Edit
+//! Distances", Nordic Journal of Computing 10 (2003).
+
+use core::cmp::Ordering;
+
+/// The longest the shorter input can be, which is the number of bits in a column.
+pub(crate) const MAX_LEN: usize = u64::BITS as usize;
+
+/// Computes the same distance as `dp`, for a nonempty `b` of at most `MAX_LEN` elements that
+/// are all below 256.
+pub(crate) fn edit_distance<T: Copy + Into<u32>>(a: &[T], b: &[T], limit: usize) -> Option<usize> {
+    distance(a, b, limit, true)
+}
+
+/// Computes the Levenshtein distance, for the same inputs as [`edit_distance`].
+pub(crate) fn levenshtein<T: Copy + Into<u32>>(a: &[T], b: &[T], limit: usize) -> Option<usize> {
+    distance(a, b, limit, false)
+}
+
+fn distance<T: Copy + Into<u32>>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    transpositions: bool,
+) -> Option<usize> {
+    let peq = peq(b);
+    let mut column = Column::new(b.len());
+    let mut distance = b.len();
+    for &x in a {
+        // The first row goes up by one in every column.
+        match column.advance(peq[x.into() as usize], transpositions, 1) {
+            Ordering::Greater => distance += 1,
+            Ordering::Less => distance -= 1,
+            Ordering::Equal => {}
+        }
+    }
+
+    (distance <= limit).then_some(distance)
+}
+
+/// Whether some substring of `text` is within `max_dist` of `pattern`, by the distance of
+/// [`edit_distance`], for a nonempty `pattern` of at most `MAX_LEN` chars below 256. Only
+/// reads `text` up to the end of the first such substring.
+pub(crate) fn contains(
+    text: impl IntoIterator<Item = char>,
+    pattern: &[char],
+    max_dist: usize,
+) -> bool {
+    let mut searcher = Searcher::new(pattern);
+    text.into_iter().any(|c| searcher.advance(c) <= max_dist)
+}
+
+/// Finds, for each char of a text in turn, the distance of the substring of the text ending
+/// there that is closest to a pattern, for the same patterns as [`contains`].
+#[derive(Clone, Debug)]
+pub(crate) struct Searcher {
+    peq: [u64; 256],
+    column: Column,
+    distance: usize,
+}
+
+impl Searcher {
+    pub(crate) fn new(pattern: &[char]) -> Self {
+        Searcher {
+            peq: peq(pattern),
+            column: Column::new(pattern.len()),
+            distance: pattern.len(),
+        }
+    }
+
+    /// Moves on to the next char of the text, and returns the distance there.
+    pub(crate) fn advance(&mut self, c: char) -> usize {
+        let pm = self.peq.get(c as usize).copied().unwrap_or(0);
+        // A match may start anywhere, so the first row is all zeros.
+        match self.column.advance(pm, true, 0) {
+            Ordering::Greater => self.distance += 1,
+            Ordering::Less => self.distance -= 1,
+            Ordering::Equal => {}
+        }
+        self.distance
+    }
+}
+
+/// For every element, the positions in `b` it is found at.
+fn peq<T: Copy + Into<u32>>(b: &[T]) -> [u64; 256] {
+    debug_assert!(!b.is_empty() && b.len() <= MAX_LEN);
+
+    let mut peq = [0u64; 256];
+    for (i, &x) in b.iter().enumerate() {
+        peq[x.into() as usize] |= 1 << i;
+    }
+    peq
+}
+
+/// One column of the table, as the differences between its cells.
+#[derive(Clone, Debug)]
+struct Column {
+    /// Vertical positive and negative differences.
+    vp: u64,
+    vn: u64,
+    /// Diagonal zero differences, and the match mask, of the previous column.
+    d0: u64,
+    prev_pm: u64,
+    /// The bit of the last cell.
+    last: u64,
+}
+
+impl Column {
+    /// The first column of a table with `len` rows below the first, in which every cell is one
+    /// more than the one above it.
+    fn new(len: usize) -> Self {
+        Column {
+            vp: !0,
+            vn: 0,
+            d0: 0,
+            prev_pm: 0,
+            last: 1 << (len - 1),
+        }
+    }
+
+    /// Moves on to the next column, where `pm` is the match mask of its element and
+    /// `first_row` how much the first row goes up by, and returns how its last cell compares
+    /// to that of the column before.
+    fn advance(&mut self, pm: u64, transpositions: bool, first_row: u64) -> Ordering {
+        let (vp, vn) = (self.vp, self.vn);
+        // transposition
+        let tr = if transpositions {
+            ((!self.d0 & pm) << 1) & self.prev_pm
+        } else {
+            0
+        };
+        let d0 = (((pm & vp).wrapping_add(vp)) ^ vp) | pm | vn | tr;
+        let hp = vn | !(d0 | vp);
+        let hn = vp & d0;
+        let ordering = if hp & self.last != 0 {
+            Ordering::Greater
+        } else if hn & self.last != 0 {
+            Ordering::Less
+        } else {
+            Ordering::Equal
+        };
+        let hp = (hp << 1) | first_row;
+        let hn = hn << 1;
+        self.vp = hn | !(d0 | hp);
+        self.vn = hp & d0;
+        self.d0 = d0;
+        self.prev_pm = pm;
+        ordering
+    }
+}
@@ -0,0 +1,286 @@
+use alloc::{vec, vec::Vec};
+
+use crate::persist::{IndexDecodeError, Reader, Writer};
+use crate::{damerau_levenshtein, default_match_distance, edit_distance};
+
+/// A [BK-tree] over a fixed set of candidates, for repeated lookups against the same names.
+///
+/// Building the tree computes the edit distance of each candidate to the nodes on its path
+/// once. A lookup then uses the triangle inequality to skip every subtree that can't contain
+/// a candidate closer than the best one found so far, instead of scanning all candidates.
+///
+/// The distances are [`edit_distance`]s by default. Its restricted transpositions can, for rare
+/// inputs, break the triangle inequality, so the edges of the tree are measured by
+/// [`damerau_levenshtein`] instead, which never exceeds it and satisfies the inequality. The
+/// lookups prune by those, and only compute the [`edit_distance`] of the candidates they
+/// visit, so they find exactly what a scan of all candidates would.
+///
+/// [`damerau_levenshtein`]: crate::damerau_levenshtein
+///
+/// [BK-tree]: https://en.wikipedia.org/wiki/BK-tree
+#[derive(Clone, Debug)]
+pub struct BkTree<'a> {
+    nodes: Vec<Node<'a>>,
+    /// The metric of the edges, which lookups prune by.
+    metric: Metric,
+    /// Whether lookups find [`edit_distance`]s, bounded from below by `metric`, rather than
+    /// the distances by `metric` itself.
+    osa: bool,
+}
+
+/// The tag of the binary format of a [`BkTree`]. The first version measured the edges of the
+/// default tree by [`edit_distance`].
+const TAG: &[u8; 4] = b"BKT2";
+
+/// A distance function with the signature of [`edit_distance`].
+type Metric = fn(&str, &str, usize) -> Option<usize>;
+
+#[derive(Clone, Debug)]
+struct Node<'a> {
+    candidate: &'a str,
+    /// Pairs of (distance to `candidate`, index into `BkTree::nodes`).
+    children: Vec<(usize, usize)>,
+}
+
+impl<'a> BkTree<'a> {
+    /// Builds a tree over `candidates`. Duplicate candidates are only stored once.
+    pub fn from_candidates(candidates: &[&'a str]) -> Self {
+        BkTree {
+            osa: true,
+            ..BkTree::from_candidates_with_metric(candidates, damerau_levenshtein)
+        }
+    }
+
+    /// Like [`BkTree::from_candidates`], but measuring distances by `metric`, which is called
+    /// like [`edit_distance`], for both the edges and the lookups. Lookups only find every
+    /// candidate a full scan would if `metric` satisfies the triangle inequality, as e.g.
+    /// [`damerau_levenshtein`](crate::damerau_levenshtein) does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `metric` returns `None` with a limit of `usize::MAX`, here or in a lookup:
+    /// the tree needs the distance of every pair it compares.
+    pub fn from_candidates_with_metric(candidates: &[&'a str], metric: Metric) -> Self {
+        let mut nodes: Vec<Node<'a>> = Vec::with_capacity(candidates.len());
+
+        'candidates: for &candidate in candidates {
+            let new = Node {
+                candidate,
+                children: Vec::new(),
+            };
+            if nodes.is_empty() {
+                nodes.push(new);
+                continue;
+            }
+
+            let mut current = 0;
+            loop {
+                let d = distance(metric, candidate, nodes[current].candidate);
+                if d == 0 {
+                    continue 'candidates;
+                }
+                match nodes[current].children.iter().find(|&&(k, _)| k == d) {
+                    Some(&(_, child)) => current = child,
+                    None => {
+                        let child = nodes.len();
+                        nodes[current].children.push((d, child));
+                        nodes.push(new);
+                        continue 'candidates;
+                    }
+                }
+            }
+        }
+
+        BkTree {
+            nodes,
+            metric,
+            osa: false,
+        }
+    }
+
+    /// Returns the number of distinct candidates in the tree.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Returns `true` if the tree has no candidates.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Finds the candidate with the smallest distance to `lookup`, if it is within `dist`.
+    ///
+    /// `dist` defaults to the same threshold as [`find_best_match_for_name`]. Ties are broken in
+    /// favor of the lexicographically smallest candidate, like the edit distance phase of
+    /// [`find_best_match_for_name`]. Unlike that function, there is no case-insensitive
+    /// substring or sorted-words matching.
+    ///
+    /// [`find_best_match_for_name`]: crate::find_best_match_for_name
+    pub fn find_best_match(&self, lookup: &str, dist: Option<usize>) -> Option<&'a str> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+
+        let mut limit = dist.unwrap_or_else(|| default_match_distance(lookup));
+        let mut best: Option<(usize, &'a str)> = None;
+        let mut stack = vec![0];
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let (bound, d) = self.distances(lookup, node.candidate, limit);
+            if let Some(d) = d {
+                if best.map_or(true, |best| (d, node.candidate) < best) {
+                    best = Some((d, node.candidate));
+                    limit = d;
+                }
+            }
+
+            // Any candidate in the subtree under edge `k` is at distance `k` from this node by
+            // the metric of the edges, so by the triangle inequality it is at least
+            // `|k - bound|` away from `lookup`, and no closer by the distance that is looked up.
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(k, _)| k.abs_diff(bound) <= limit)
+                    .map(|&(_, child)| child),
+            );
+        }
+
+        best.map(|(_, candidate)| candidate)
+    }
+
+    /// Finds every candidate within `max_dist` of `lookup`, with its distance, sorted by
+    /// distance and then lexicographically, as a scan of all candidates would.
+    pub fn query(&self, lookup: &str, max_dist: usize) -> Vec<(&'a str, usize)> {
+        let mut matches = Vec::new();
+        let mut stack = if self.nodes.is_empty() {
+            vec![]
+        } else {
+            vec![0]
+        };
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let (bound, d) = self.distances(lookup, node.candidate, max_dist);
+            if let Some(d) = d {
+                matches.push((node.candidate, d));
+            }
+
+            // As in `find_best_match`, but the radius doesn't shrink.
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|&&(k, _)| k.abs_diff(bound) <= max_dist)
+                    .map(|&(_, child)| child),
+            );
+        }
+
+        matches.sort_unstable_by_key(|&(candidate, d)| (d, candidate));
+        matches
+    }
+
+    /// Encodes the tree in the crate's compact binary format, to load it again with
+    /// [`BkTree::from_bytes`] without computing any distances.
+    ///
+    /// The metric isn't stored, so a tree built with another one has to be loaded with
+    /// [`BkTree::from_bytes_with_metric`].
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new(TAG);
+        writer.usize(self.nodes.len());
+        for node in &self.nodes {
+            writer.str(node.candidate);
+            writer.usize(node.children.len());
+            for &(k, child) in &node.children {
+                writer.usize(k);
+                writer.usize(child);
+            }
+        }
+        writer.finish()
+    }
+
+    /// Loads a tree from bytes returned by [`BkTree::to_bytes`], borrowing its candidates from
+    /// `bytes`, so the bytes can be embedded with `include_bytes!`.
+    ///
+    /// ```
+    /// use rustc_edit_distance::BkTree;
+    ///
+    /// let bytes = BkTree::from_candidates(&["apple", "maple", "grape"]).to_bytes();
+    /// let tree = BkTree::from_bytes(&bytes).unwrap();
+    /// assert_eq!(tree.find_best_match("aple", Some(1)), Some("apple"));
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, IndexDecodeError> {
+        Ok(BkTree {
+            osa: true,
+            ..BkTree::from_bytes_with_metric(bytes, damerau_levenshtein)?
+        })
+    }
+
+    /// Like [`BkTree::from_bytes`], for a tree built with
+    /// [`BkTree::from_candidates_with_metric`] and the same `metric`.
+    ///
+    /// # Panics
+    ///
+    /// Lookups panic if `metric` returns `None` with a limit of `usize::MAX`, as for
+    /// [`BkTree::from_candidates_with_metric`].
+    pub fn from_bytes_with_metric(
+        bytes: &'a [u8],
+        metric: Metric,
+    ) -> Result<Self, IndexDecodeError> {
+        let mut reader = Reader::new(bytes, TAG)?;
+        // Every node takes at least 16 bytes, which bounds the memory of `has_parent` too.
+        let len = reader.index(0..bytes.len() / 16 + 1)?;
+        let mut nodes = Vec::new();
+        // Every node but the root has exactly one parent, so the nodes form a tree, and no
+        // lookup ever visits one twice.
+        let mut has_parent = vec![false; len];
+        for current in 0..len {
+            let candidate = reader.str()?;
+            let mut children = Vec::new();
+            for _ in 0..reader.usize()? {
+                let k = reader.usize()?;
+                // Children come after their parents, so there are no cycles to walk into.
+                let offset = reader.offset();
+                let child = reader.index(current + 1..len)?;
+                if has_parent[child] {
+                    return Err(IndexDecodeError { offset });
+                }
+                has_parent[child] = true;
+                children.push((k, child));
+            }
+            nodes.push(Node {
+                candidate,
+                children,
+            });
+        }
+        // Nor does any node go missing from lookups.
+        if has_parent.iter().skip(1).any(|&has_parent| !has_parent) {
+            return Err(IndexDecodeError {
+                offset: reader.offset(),
+            });
+        }
+        reader.finish()?;
+        Ok(BkTree {
+            nodes,
+            metric,
+            osa: false,
+        })
+    }
+
+    /// Returns the distance of `lookup` to `candidate` by the metric of the edges, and the one
+    /// that is looked up, if it is within `limit`.
+    fn distances(&self, lookup: &str, candidate: &str, limit: usize) -> (usize, Option<usize>) {
+        let bound = distance(self.metric, lookup, candidate);
+        if bound > limit {
+            // The distance that is looked up is at least as large.
+            return (bound, None);
+        }
+        if self.osa {
+            (bound, edit_distance(lookup, candidate, limit))
+        } else {
+            (bound, Some(bound))
+        }
+    }
+}
+
+fn distance(metric: Metric, a: &str, b: &str) -> usize {
+    // Without a limit there is always a distance, for a metric as documented.
+    metric(a, b, usize::MAX).expect("the metric of a `BkTree` returned `None` without a limit")
+}
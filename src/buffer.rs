@@ -0,0 +1,141 @@
+use alloc::vec::Vec;
+
+#[cfg(feature = "simd")]
+use crate::simd;
+use crate::{cells_fit, dp_in, dp_len, edit_distance_fast_in, unit_substitution_cost};
+
+/// Memory for [`edit_distance_in`] to keep between calls, so that comparing many pairs of
+/// long strings allocates only for the longest of them.
+///
+/// Strings of up to 64 chars never allocate, with or without a buffer.
+///
+/// ```
+/// use rustc_edit_distance::{edit_distance_in, EditDistanceBuffer};
+///
+/// let mut buffer = EditDistanceBuffer::new();
+/// let candidates = ["std::collections::hash_map::HashMap", "std::collections::BTreeMap"];
+/// let distances = candidates
+///     .iter()
+///     .map(|c| edit_distance_in(&mut buffer, "std::collections::hash_map::HashMop", c, 3))
+///     .collect::<Vec<_>>();
+/// assert_eq!(distances, [Some(1), None]);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EditDistanceBuffer {
+    a: Vec<char>,
+    b: Vec<char>,
+    rows: RowBuffer,
+}
+
+/// The rows of the table, in cells of whichever type fits the limit.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RowBuffer {
+    narrow: Vec<u8>,
+    wide: Vec<usize>,
+    /// The anti-diagonals of the table, for long strings.
+    #[cfg(feature = "simd")]
+    diagonals: Vec<u32>,
+}
+
+impl EditDistanceBuffer {
+    /// Returns an empty buffer, which allocates once it is first needed.
+    pub fn new() -> Self {
+        EditDistanceBuffer::default()
+    }
+}
+
+/// Like [`edit_distance`](crate::edit_distance), but keeps the chars of the strings and the
+/// rows of the table in `buffer`, reusing the memory of earlier calls.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_distance_in(
+    buffer: &mut EditDistanceBuffer,
+    a: &str,
+    b: &str,
+    limit: usize,
+) -> Option<usize> {
+    if a.is_ascii() && b.is_ascii() {
+        return edit_distance_fast_in(Some(&mut buffer.rows), a.as_bytes(), b.as_bytes(), limit);
+    }
+
+    buffer.a.clear();
+    buffer.a.extend(a.chars());
+    buffer.b.clear();
+    buffer.b.extend(b.chars());
+    edit_distance_fast_in(Some(&mut buffer.rows), &buffer.a, &buffer.b, limit)
+}
+
+/// Finds the [`edit_distance`](crate::edit_distance) from `lookup` to each of `candidates`, in
+/// order.
+///
+/// The chars of `lookup` are decoded once, and one set of rows is shared by all candidates, so
+/// this allocates far less than calling [`edit_distance`](crate::edit_distance) in a loop.
+/// Candidates whose length alone puts them over the limit are skipped without being decoded.
+///
+/// Each distance is `None` if it exceeds the limit.
+///
+/// ```
+/// use rustc_edit_distance::edit_distances;
+///
+/// let distances = edit_distances("stauts", &["status", "start", "show"], 2);
+/// assert_eq!(distances, [Some(1), Some(2), None]);
+/// ```
+pub fn edit_distances(lookup: &str, candidates: &[&str], limit: usize) -> Vec<Option<usize>> {
+    let mut buffer = EditDistanceBuffer::new();
+    buffer.a.extend(lookup.chars());
+    let ascii = lookup.is_ascii();
+
+    candidates
+        .iter()
+        .map(|candidate| {
+            // Counting the chars of a candidate is cheaper than decoding them.
+            if candidate.chars().count().abs_diff(buffer.a.len()) > limit {
+                return None;
+            }
+            if ascii && candidate.is_ascii() {
+                return edit_distance_fast_in(
+                    Some(&mut buffer.rows),
+                    lookup.as_bytes(),
+                    candidate.as_bytes(),
+                    limit,
+                );
+            }
+            buffer.b.clear();
+            buffer.b.extend(candidate.chars());
+            edit_distance_fast_in(Some(&mut buffer.rows), &buffer.a, &buffer.b, limit)
+        })
+        .collect()
+}
+
+impl RowBuffer {
+    /// Like [`dp`](crate::dp), but in these rows, growing them if they are too short.
+    pub(crate) fn dp<T: PartialEq>(&mut self, a: &[T], b: &[T], limit: usize) -> Option<usize> {
+        let len = dp_len(b, limit);
+        if cells_fit::<u8>(a.len(), b.len(), limit) {
+            let rows = resized(&mut self.narrow, len);
+            return dp_in(rows, a, b, limit, false, unit_substitution_cost);
+        }
+        let rows = resized(&mut self.wide, len);
+        dp_in(rows, a, b, limit, false, unit_substitution_cost)
+    }
+
+    /// Like [`simd::edit_distance`], but in these anti-diagonals.
+    #[cfg(feature = "simd")]
+    pub(crate) fn anti_diagonals<T: Copy + Into<u32>>(
+        &mut self,
+        a: &[T],
+        b: &[T],
+        limit: usize,
+    ) -> Option<usize> {
+        let buffer = resized(&mut self.diagonals, simd::buffer_len(a));
+        simd::edit_distance_in(buffer, a, b, limit)
+    }
+}
+
+/// Returns the first `len` cells of `rows`, growing it if it is shorter.
+fn resized<C: Copy + Default>(rows: &mut Vec<C>, len: usize) -> &mut [C] {
+    if rows.len() < len {
+        rows.resize(len, C::default());
+    }
+    &mut rows[..len]
+}
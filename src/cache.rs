@@ -0,0 +1,241 @@
+use alloc::{collections::BTreeMap, sync::Arc};
+use core::cell::RefCell;
+
+use crate::{edit_distance, options, Metric};
+
+/// Remembers the distances of the pairs of strings it was asked about, e.g. for jobs that
+/// compare overlapping batches of strings, and so the same pairs again and again.
+///
+/// The distances are found by a [`Metric`], [`edit_distance`] by default. At most `capacity`
+/// pairs are kept, and the one used least recently is forgotten to make room for a new one.
+/// The strings are interned, so a string in many pairs is stored once.
+///
+/// A distance that was over the limit is only known to be over that limit. It answers
+/// calls with a limit as low or lower, and is computed again for a higher one.
+///
+/// ```
+/// use rustc_edit_distance::{CacheStats, DistanceCache};
+///
+/// let cache = DistanceCache::new(1000);
+/// assert_eq!(cache.distance("kitten", "sitting", 3), Some(3));
+/// assert_eq!(cache.distance("kitten", "sitting", 3), Some(3));
+/// assert_eq!(cache.distance("kitten", "sitting", 2), None);
+/// // Only the first call computed the distance.
+/// assert_eq!(cache.stats(), CacheStats { hits: 2, misses: 1 });
+/// ```
+///
+/// The cache is filled through a shared reference, so it can be used as a [`Metric`] itself,
+/// but not be shared between threads.
+#[derive(Debug)]
+pub struct DistanceCache<M = fn(&str, &str, usize) -> Option<usize>> {
+    metric: M,
+    capacity: usize,
+    inner: RefCell<Inner>,
+}
+
+/// How often a [`DistanceCache`] knew a distance, and how often it had to compute one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    /// The calls answered by the cache.
+    pub hits: u64,
+    /// The calls that computed the distance.
+    pub misses: u64,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    /// The id of every interned string.
+    ids: BTreeMap<Arc<str>, usize>,
+    /// Every interned string by its id, with the number of pairs it is in, so that it can be
+    /// dropped with the last of them.
+    strings: BTreeMap<usize, (Arc<str>, usize)>,
+    next_id: usize,
+    entries: BTreeMap<(usize, usize), Entry>,
+    /// The keys of the entries by when they were last used, least recently first.
+    recency: BTreeMap<u64, (usize, usize)>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    /// The distance, or `None` if it is over `limit`.
+    distance: Option<usize>,
+    limit: usize,
+    used: u64,
+}
+
+impl DistanceCache {
+    /// Returns an empty cache of [`edit_distance`]s that keeps up to `capacity` pairs.
+    pub fn new(capacity: usize) -> Self {
+        DistanceCache::with_metric(edit_distance, capacity)
+    }
+}
+
+impl<M: Metric> DistanceCache<M> {
+    /// Returns an empty cache of the distances by `metric` that keeps up to `capacity` pairs.
+    pub fn with_metric(metric: M, capacity: usize) -> Self {
+        DistanceCache {
+            metric,
+            capacity,
+            inner: RefCell::default(),
+        }
+    }
+
+    /// Returns the distance between `a` and `b`, from the cache if it is there, or `None`
+    /// if it exceeds `limit`.
+    pub fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        let mut inner = self.inner.borrow_mut();
+        if let Some(distance) = inner.get(a, b, limit) {
+            inner.stats.hits += 1;
+            return distance;
+        }
+        inner.stats.misses += 1;
+        // Not borrowed while the metric runs, in case it uses the cache as well.
+        drop(inner);
+
+        let distance = self.metric.distance(a, b, limit);
+        self.inner
+            .borrow_mut()
+            .insert(a, b, limit, distance, self.capacity);
+        distance
+    }
+
+    /// Finds the candidate that best matches `lookup`, as
+    /// [`find_best_match_for_name_with_metric`](crate::find_best_match_for_name_with_metric)
+    /// does with the metric of the cache, taking the distances from the cache.
+    ///
+    /// ```
+    /// use rustc_edit_distance::DistanceCache;
+    ///
+    /// let cache = DistanceCache::new(1000);
+    /// let candidates = ["length", "width", "height"];
+    /// assert_eq!(cache.find_best_match_for_name(&candidates, "lenght", None), Some("length"));
+    /// let misses = cache.stats().misses;
+    /// assert_eq!(cache.find_best_match_for_name(&candidates, "lenght", None), Some("length"));
+    /// assert_eq!(cache.stats().misses, misses);
+    /// ```
+    pub fn find_best_match_for_name<'a>(
+        &self,
+        candidates: &[&'a str],
+        lookup: &str,
+        dist: Option<usize>,
+    ) -> Option<&'a str> {
+        options(dist).best_by(candidates, lookup, self)
+    }
+
+    /// Returns the number of hits and misses so far.
+    pub fn stats(&self) -> CacheStats {
+        self.inner.borrow().stats
+    }
+
+    /// Returns the number of pairs in the cache.
+    pub fn len(&self) -> usize {
+        self.inner.borrow().entries.len()
+    }
+
+    /// Returns whether the cache has no pairs.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the most pairs the cache keeps.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Forgets every pair. The statistics are kept.
+    pub fn clear(&self) {
+        let mut inner = self.inner.borrow_mut();
+        let stats = inner.stats;
+        *inner = Inner {
+            stats,
+            ..Inner::default()
+        };
+    }
+}
+
+impl<M: Metric> Metric for DistanceCache<M> {
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        DistanceCache::distance(self, a, b, limit)
+    }
+}
+
+impl Inner {
+    /// Returns the distance of `a` and `b` within `limit`, if the cache knows it.
+    fn get(&mut self, a: &str, b: &str, limit: usize) -> Option<Option<usize>> {
+        let key = (*self.ids.get(a)?, *self.ids.get(b)?);
+        let entry = self.entries.get_mut(&key)?;
+        let distance = match entry.distance {
+            Some(distance) => (distance <= limit).then_some(distance),
+            None if limit <= entry.limit => None,
+            // Over a lower limit than this one, which doesn't say whether it is over this one.
+            None => return None,
+        };
+        self.recency.remove(&entry.used);
+        self.clock += 1;
+        entry.used = self.clock;
+        self.recency.insert(self.clock, key);
+        Some(distance)
+    }
+
+    fn insert(&mut self, a: &str, b: &str, limit: usize, distance: Option<usize>, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        let used = self.clock;
+        let new = Entry {
+            distance,
+            limit,
+            used,
+        };
+
+        // A pair that was only known to be over a lower limit.
+        if let (Some(&a_id), Some(&b_id)) = (self.ids.get(a), self.ids.get(b)) {
+            if let Some(entry) = self.entries.get_mut(&(a_id, b_id)) {
+                self.recency.remove(&entry.used);
+                *entry = new;
+                self.recency.insert(used, (a_id, b_id));
+                return;
+            }
+        }
+
+        if self.entries.len() >= capacity {
+            let oldest = self.recency.iter().next().map(|(&used, &key)| (used, key));
+            if let Some((oldest, (a_id, b_id))) = oldest {
+                self.recency.remove(&oldest);
+                self.entries.remove(&(a_id, b_id));
+                self.release(a_id);
+                self.release(b_id);
+            }
+        }
+        let key = (self.intern(a), self.intern(b));
+        self.entries.insert(key, new);
+        self.recency.insert(used, key);
+    }
+
+    /// Returns the id of `s`, interning it if it isn't yet, for a new pair it is in.
+    fn intern(&mut self, s: &str) -> usize {
+        if let Some(&id) = self.ids.get(s) {
+            self.strings.get_mut(&id).unwrap().1 += 1;
+            return id;
+        }
+        let id = self.next_id;
+        self.next_id += 1;
+        let s = Arc::<str>::from(s);
+        self.ids.insert(s.clone(), id);
+        self.strings.insert(id, (s, 1));
+        id
+    }
+
+    /// Drops the string of `id` from a pair, and from the cache if it was its last.
+    fn release(&mut self, id: usize) {
+        let (s, pairs) = self.strings.get_mut(&id).unwrap();
+        *pairs -= 1;
+        if *pairs == 0 {
+            self.ids.remove(&**s);
+            self.strings.remove(&id);
+        }
+    }
+}
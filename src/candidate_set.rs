@@ -0,0 +1,101 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{case_fold, default_match_distance, edit_distance, is_folded_match, sort_by_words};
+
+/// A set of candidates prepared for many lookups with
+/// [`find_best_match_for_name`](crate::find_best_match_for_name), e.g. a symbol table.
+///
+/// Every lookup folds the case of every candidate and splits it into words, which allocates.
+/// A `CandidateSet` does that once, when it is built, so a lookup only allocates for the
+/// lookup itself, and skips the candidates whose length is too different from it.
+///
+/// ```
+/// use rustc_edit_distance::CandidateSet;
+///
+/// let set = CandidateSet::new(&["push_back", "push_front", "pop_back", "pop_front"]);
+/// assert_eq!(set.best("pop_frnt", None), Some("pop_front"));
+/// assert_eq!(set.best("POP", None), Some("pop_back"));
+/// ```
+#[derive(Clone, Debug)]
+pub struct CandidateSet<'a> {
+    candidates: Vec<Candidate<'a>>,
+}
+
+#[derive(Clone, Debug)]
+struct Candidate<'a> {
+    name: &'a str,
+    folded: String,
+    /// The number of chars of `name`.
+    len: usize,
+    sorted_words: Vec<&'a str>,
+}
+
+impl<'a> CandidateSet<'a> {
+    /// Prepares `candidates` for lookups, in the order given.
+    pub fn new(candidates: &[&'a str]) -> Self {
+        let candidates = candidates
+            .iter()
+            .map(|&name| Candidate {
+                name,
+                folded: case_fold(name),
+                len: name.chars().count(),
+                sorted_words: sort_by_words(name, &['_']),
+            })
+            .collect();
+        CandidateSet { candidates }
+    }
+
+    /// Returns the number of candidates.
+    pub fn len(&self) -> usize {
+        self.candidates.len()
+    }
+
+    /// Returns `true` if there are no candidates.
+    pub fn is_empty(&self) -> bool {
+        self.candidates.is_empty()
+    }
+
+    /// Finds the candidate that best matches `lookup`, exactly as
+    /// [`find_best_match_for_name`](crate::find_best_match_for_name) of the candidates would.
+    pub fn best(&self, lookup: &str, dist: Option<usize>) -> Option<&'a str> {
+        if lookup.is_empty() {
+            return self
+                .candidates
+                .iter()
+                .map(|c| c.name)
+                .find(|c| c.is_empty());
+        }
+
+        let lookup_folded = case_fold(lookup);
+        let lookup_len = lookup.chars().count();
+        let lookup_sorted_by_words = sort_by_words(lookup, &['_']);
+
+        let mut dist = dist.unwrap_or_else(|| default_match_distance(lookup));
+        let mut best = None;
+        let mut sorted_words_match = None;
+        for c in &self.candidates {
+            if is_folded_match(&c.folded, &lookup_folded) {
+                return Some(c.name);
+            }
+
+            // No need to look at the chars if the lengths are too different.
+            let d = if c.len.abs_diff(lookup_len) > dist {
+                None
+            } else {
+                edit_distance(lookup, c.name, dist)
+            };
+            if let Some(d) = d {
+                if d < dist || best.map_or(true, |best| c.name < best) {
+                    dist = d;
+                    best = Some(c.name);
+                }
+            }
+
+            if best.is_none() && c.sorted_words == lookup_sorted_by_words {
+                sorted_words_match = Some(c.name);
+            }
+        }
+
+        best.or(sorted_words_match)
+    }
+}
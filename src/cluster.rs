@@ -0,0 +1,104 @@
+use alloc::{vec, vec::Vec};
+use core::mem;
+
+use crate::{distance_matrix, DistanceMatrix};
+
+/// How [`cluster_with`] decides whether a string is close enough to a cluster to join it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Linkage {
+    /// The string is within the distance of the representative of the cluster, its first
+    /// member. Each string joins the first cluster it can, in order, and starts a new one if
+    /// there is none, so the representatives are the strings that weren't close to any before
+    /// them.
+    #[default]
+    Representative,
+    /// The string is within the distance of any member of the cluster, so clusters are the
+    /// groups of strings connected by chains of close strings. A chain can make the first and
+    /// the last string of a cluster much further apart than the distance.
+    Single,
+    /// The string is within the distance of every member of the cluster. Each string joins the
+    /// first cluster it can, in order, as for [`Linkage::Representative`].
+    Complete,
+}
+
+/// Groups `strings` into clusters of near-duplicates, e.g. of tags submitted by users, with
+/// every member within `max_dist` of the first member of its cluster by
+/// [`edit_distance`](crate::edit_distance).
+///
+/// This is [`cluster_with`] with [`Linkage::Representative`].
+///
+/// ```
+/// use rustc_edit_distance::cluster;
+///
+/// let tags = ["rust", "python", "rusty", "pyhton", "go", "Rust"];
+/// assert_eq!(cluster(&tags, 1), [vec![0, 2, 5], vec![1, 3], vec![4]]);
+/// ```
+pub fn cluster(strings: &[&str], max_dist: usize) -> Vec<Vec<usize>> {
+    cluster_with(strings, max_dist, Linkage::Representative)
+}
+
+/// Groups `strings` into clusters of strings within `max_dist` of each other by
+/// [`edit_distance`](crate::edit_distance), as `linkage` says.
+///
+/// Every string is in exactly one cluster. A cluster is the indices of its members in
+/// `strings`, in increasing order, and the clusters are in the order of their first members.
+///
+/// ```
+/// use rustc_edit_distance::{cluster_with, Linkage};
+///
+/// let strings = ["cat", "cot", "cog", "dog"];
+/// assert_eq!(cluster_with(&strings, 1, Linkage::Single), [vec![0, 1, 2, 3]]);
+/// assert_eq!(cluster_with(&strings, 1, Linkage::Complete), [vec![0, 1], vec![2, 3]]);
+/// ```
+pub fn cluster_with(strings: &[&str], max_dist: usize, linkage: Linkage) -> Vec<Vec<usize>> {
+    let matrix = distance_matrix(strings, max_dist);
+    let close = |i: usize, j: usize| matrix.get(i, j).is_some();
+    match linkage {
+        Linkage::Representative => greedy(&matrix, |cluster, i| close(cluster[0], i)),
+        Linkage::Complete => greedy(&matrix, |cluster, i| cluster.iter().all(|&j| close(j, i))),
+        Linkage::Single => connected(&matrix),
+    }
+}
+
+/// Puts each string in the first cluster `joins`, or in a new one.
+fn greedy(matrix: &DistanceMatrix, joins: impl Fn(&[usize], usize) -> bool) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for i in 0..matrix.len() {
+        match clusters.iter_mut().find(|cluster| joins(cluster, i)) {
+            Some(cluster) => cluster.push(i),
+            None => clusters.push(vec![i]),
+        }
+    }
+    clusters
+}
+
+/// The connected components of the strings within the limit of the matrix of each other.
+fn connected(matrix: &DistanceMatrix) -> Vec<Vec<usize>> {
+    // The cluster of each string so far, as an index into `clusters`.
+    let mut cluster_of = vec![0; matrix.len()];
+    let mut clusters: Vec<Vec<usize>> = Vec::new();
+    for i in 0..matrix.len() {
+        cluster_of[i] = clusters.len();
+        clusters.push(vec![i]);
+        for j in 0..i {
+            let (a, b) = (cluster_of[j], cluster_of[i]);
+            if a == b || matrix.get(i, j).is_none() {
+                continue;
+            }
+            // Merge the later cluster into the earlier one, which keeps the order of the
+            // clusters by their first members.
+            let (into, from) = if a < b { (a, b) } else { (b, a) };
+            let moved = mem::take(&mut clusters[from]);
+            for &k in &moved {
+                cluster_of[k] = into;
+            }
+            clusters[into].extend(moved);
+        }
+    }
+    clusters.retain(|cluster| !cluster.is_empty());
+    for cluster in &mut clusters {
+        cluster.sort_unstable();
+    }
+    clusters
+}
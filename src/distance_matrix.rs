@@ -0,0 +1,123 @@
+use alloc::vec::Vec;
+
+use crate::edit_distances;
+
+/// The [`edit_distance`](crate::edit_distance)s between every pair of a set of strings, as
+/// computed by [`distance_matrix`].
+///
+/// The distance is symmetric, so only the pairs above the diagonal are stored, and a row is
+/// the same as the column of the same index.
+///
+/// ```
+/// use rustc_edit_distance::distance_matrix;
+///
+/// let matrix = distance_matrix(&["color", "colour", "flavor", "flavour"], 2);
+/// assert_eq!(matrix.get(0, 1), Some(1));
+/// assert_eq!(matrix.get(1, 3), None);
+/// assert_eq!(matrix.nearest_neighbor(2), Some((3, 1)));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistanceMatrix {
+    len: usize,
+    /// The distances of each pair `(i, j)` with `i < j`, in order of `i` and then `j`.
+    upper: Vec<Option<usize>>,
+}
+
+impl DistanceMatrix {
+    /// Builds a matrix from the rows of its upper triangle, as returned by [`upper_row`].
+    pub(crate) fn from_upper(len: usize, upper: Vec<Option<usize>>) -> Self {
+        debug_assert_eq!(upper.len(), len * len.saturating_sub(1) / 2);
+        DistanceMatrix { len, upper }
+    }
+
+    /// Returns the number of strings, i.e. of rows and of columns.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns whether the matrix is of no strings at all.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the distance between the `i`th and the `j`th string, or `None` if it exceeds the
+    /// limit. A string is at distance 0 from itself.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<usize> {
+        assert!(
+            i < self.len && j < self.len,
+            "index ({i}, {j}) out of bounds for a matrix of {} strings",
+            self.len
+        );
+        let (i, j) = if i < j { (i, j) } else { (j, i) };
+        if i == j {
+            return Some(0);
+        }
+        self.upper[self.upper_index(i, j)]
+    }
+
+    /// Returns the distances from the `i`th string to each string, in order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn row(&self, i: usize) -> impl Iterator<Item = Option<usize>> + '_ {
+        assert!(
+            i < self.len,
+            "row {i} out of bounds for a matrix of {} strings",
+            self.len
+        );
+        (0..self.len).map(move |j| self.get(i, j))
+    }
+
+    /// Returns the distances from each string to the `j`th string, in order, which are those of
+    /// [`DistanceMatrix::row`] `j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `j` is out of bounds.
+    pub fn column(&self, j: usize) -> impl Iterator<Item = Option<usize>> + '_ {
+        self.row(j)
+    }
+
+    /// Returns the index of the string closest to the `i`th one, other than itself, and its
+    /// distance, or `None` if every other string is over the limit. Ties go to the lowest
+    /// index.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` is out of bounds.
+    pub fn nearest_neighbor(&self, i: usize) -> Option<(usize, usize)> {
+        self.row(i)
+            .enumerate()
+            .filter(|&(j, _)| j != i)
+            .filter_map(|(j, d)| Some((j, d?)))
+            .min_by_key(|&(j, d)| (d, j))
+    }
+
+    fn upper_index(&self, i: usize, j: usize) -> usize {
+        // The rows before `i` hold `len - 1`, `len - 2`, ..., `len - i` pairs.
+        i * (2 * self.len - i - 1) / 2 + (j - i - 1)
+    }
+}
+
+/// Finds the [`edit_distance`](crate::edit_distance) between every pair of `items`, or `None`
+/// for a pair whose distance exceeds the limit.
+///
+/// Only the pairs above the diagonal are computed, each row with
+/// [`edit_distances`](crate::edit_distances), so the memory of the table is reused within a
+/// row.
+pub fn distance_matrix(items: &[&str], limit: usize) -> DistanceMatrix {
+    let upper = (0..items.len())
+        .flat_map(|i| upper_row(items, i, limit))
+        .collect();
+    DistanceMatrix::from_upper(items.len(), upper)
+}
+
+/// The distances from `items[i]` to the items after it.
+pub(crate) fn upper_row(items: &[&str], i: usize, limit: usize) -> Vec<Option<usize>> {
+    edit_distances(items[i], &items[i + 1..], limit)
+}
@@ -0,0 +1,375 @@
+use alloc::{vec, vec::Vec};
+use core::ops::Range;
+
+use crate::{edit_distance, osa_matrix};
+
+/// An edit that is part of turning one string into another, as found by [`edit_ops`].
+///
+/// Indices are in chars, not bytes. `source` is an index into the string being edited, `a`,
+/// and `target` an index into the string it is turned into, `b`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EditOp {
+    /// Inserts the char at `target` in `b` before the char at `source` in `a`.
+    Insert { source: usize, target: usize },
+    /// Deletes the char at `source` in `a`, which would have come before the char at `target`
+    /// in `b`.
+    Delete { source: usize, target: usize },
+    /// Replaces the char at `source` in `a` with the char at `target` in `b`.
+    Substitute { source: usize, target: usize },
+    /// Swaps the chars at `source` and `source + 1` in `a`, which become the chars at `target`
+    /// and `target + 1` in `b`.
+    Transpose { source: usize, target: usize },
+}
+
+/// Finds the edits behind [`edit_distance`], which turn `a` into `b`.
+///
+/// There are as many edits as the distance, in the order of the chars they apply to. Chars
+/// that are kept as they are aren't listed. When there are several ways to turn `a` into `b`
+/// with the fewest edits, the one returned prefers keeping chars, then transpositions, then
+/// substitutions, then deletions, matching from the ends of the strings.
+///
+/// This takes memory proportional to the product of the lengths of the strings, for the full
+/// table of [`edit_distance_matrix`](crate::edit_distance_matrix).
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_ops(a: &str, b: &str, limit: usize) -> Option<Vec<EditOp>> {
+    // Checking the limit first is much cheaper than building the table.
+    let distance = edit_distance(a, b, limit)?;
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut ops = Vec::with_capacity(distance);
+    push_ops(&mut ops, &a, &b, 0, 0);
+    Some(ops)
+}
+
+/// Pushes the edits of [`edit_ops`] that turn `a` into `b` onto `ops`, in order, with
+/// `source_offset` and `target_offset` added to their indices.
+pub(crate) fn push_ops<T: PartialEq>(
+    ops: &mut Vec<EditOp>,
+    a: &[T],
+    b: &[T],
+    source_offset: usize,
+    target_offset: usize,
+) {
+    let d = osa_matrix(a, b);
+    let start = ops.len();
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 || j > 0 {
+        if i > 0 && j > 0 && a[i - 1] == b[j - 1] && d[i][j] == d[i - 1][j - 1] {
+            i -= 1;
+            j -= 1;
+        } else if i > 1
+            && j > 1
+            && a[i - 1] == b[j - 2]
+            && a[i - 2] == b[j - 1]
+            && d[i][j] == d[i - 2][j - 2] + 1
+        {
+            ops.push(EditOp::Transpose {
+                source: i - 2,
+                target: j - 2,
+            });
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && j > 0 && d[i][j] == d[i - 1][j - 1] + 1 {
+            ops.push(EditOp::Substitute {
+                source: i - 1,
+                target: j - 1,
+            });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[i][j] == d[i - 1][j] + 1 {
+            ops.push(EditOp::Delete {
+                source: i - 1,
+                target: j,
+            });
+            i -= 1;
+        } else {
+            ops.push(EditOp::Insert {
+                source: i,
+                target: j - 1,
+            });
+            j -= 1;
+        }
+    }
+    ops[start..].reverse();
+    for op in &mut ops[start..] {
+        let (EditOp::Insert { source, target }
+        | EditOp::Delete { source, target }
+        | EditOp::Substitute { source, target }
+        | EditOp::Transpose { source, target }) = op;
+        *source += source_offset;
+        *target += target_offset;
+    }
+}
+
+/// Like [`edit_ops`], but takes memory proportional to the length of the shorter string
+/// instead of the product of both lengths, e.g. for whole documents.
+///
+/// The edits are found by [Hirschberg's algorithm]: the distance of both halves of `a` to
+/// every split of `b` is found a row at a time, and the halves are edited around the best
+/// split, down to single chars. This takes about twice as long as [`edit_ops`], and when there
+/// are several ways of turning `a` into `b` with the fewest edits, it may return another one.
+///
+/// ```
+/// use rustc_edit_distance::{edit_ops, edit_ops_linear_space};
+///
+/// let ops = edit_ops_linear_space("kitten", "sitting", 3).unwrap();
+/// assert_eq!(ops, edit_ops("kitten", "sitting", 3).unwrap());
+/// assert_eq!(edit_ops_linear_space("kitten", "sitting", 2), None);
+/// ```
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [Hirschberg's algorithm]: https://en.wikipedia.org/wiki/Hirschberg%27s_algorithm
+pub fn edit_ops_linear_space(a: &str, b: &str, limit: usize) -> Option<Vec<EditOp>> {
+    let distance = edit_distance(a, b, limit)?;
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut ops = Vec::with_capacity(distance);
+    // The rows are as long as `b`, so it should be the shorter string. The same six rows are
+    // used all the way down the recursion.
+    let mut rows = vec![0; 6 * (a.len().min(b.len()) + 1)];
+    if a.len() < b.len() {
+        hirschberg(&mut ops, &mut rows, &b, &a, 0, 0);
+        for op in &mut ops {
+            *op = match *op {
+                EditOp::Insert { source, target } => EditOp::Delete {
+                    source: target,
+                    target: source,
+                },
+                EditOp::Delete { source, target } => EditOp::Insert {
+                    source: target,
+                    target: source,
+                },
+                EditOp::Substitute { source, target } => EditOp::Substitute {
+                    source: target,
+                    target: source,
+                },
+                EditOp::Transpose { source, target } => EditOp::Transpose {
+                    source: target,
+                    target: source,
+                },
+            };
+        }
+    } else {
+        hirschberg(&mut ops, &mut rows, &a, &b, 0, 0);
+    }
+    Some(ops)
+}
+
+/// Pushes the edits that turn `a` into `b` onto `ops`, like [`push_ops`], in memory
+/// proportional to the length of `b`: `rows` has to hold six rows as long as `b`.
+fn hirschberg(
+    ops: &mut Vec<EditOp>,
+    rows: &mut [usize],
+    a: &[char],
+    b: &[char],
+    source_offset: usize,
+    target_offset: usize,
+) {
+    if b.is_empty() {
+        ops.extend((0..a.len()).map(|i| EditOp::Delete {
+            source: source_offset + i,
+            target: target_offset,
+        }));
+        return;
+    }
+    if a.len() < 2 {
+        // The table has at most two rows.
+        push_ops(ops, a, b, source_offset, target_offset);
+        return;
+    }
+
+    // The distances of the first half of `a`, and of the second half backwards, to the
+    // prefixes of `b`, and to its suffixes backwards: `after[k]` is the distance of the second
+    // half to the last `k` chars of `b`. The rows before the last are for a transposition
+    // across the middle.
+    let mid = a.len() / 2;
+    let (before_rows, after_rows) = rows.split_at_mut(rows.len() / 2);
+    let (before_prev, before) = last_rows(&a[..mid], b, false, before_rows);
+    let (after_prev, after) = last_rows(&a[mid..], b, true, after_rows);
+
+    // Every way of editing `a` into `b` either splits it at the middle or transposes the chars
+    // on both sides of it.
+    let m = b.len();
+    let mut best = (before[0] + after[m], 0, false);
+    for j in 1..=m {
+        let split = before[j] + after[m - j];
+        if split < best.0 {
+            best = (split, j, false);
+        }
+        if j < m && a[mid - 1] == b[j] && a[mid] == b[j - 1] {
+            let transposed = before_prev[j - 1] + 1 + after_prev[m - j - 1];
+            if transposed < best.0 {
+                best = (transposed, j, true);
+            }
+        }
+    }
+
+    let (_, j, transposed) = best;
+    if transposed {
+        hirschberg(
+            ops,
+            rows,
+            &a[..mid - 1],
+            &b[..j - 1],
+            source_offset,
+            target_offset,
+        );
+        ops.push(EditOp::Transpose {
+            source: source_offset + mid - 1,
+            target: target_offset + j - 1,
+        });
+        hirschberg(
+            ops,
+            rows,
+            &a[mid + 1..],
+            &b[j + 1..],
+            source_offset + mid + 1,
+            target_offset + j + 1,
+        );
+    } else {
+        hirschberg(ops, rows, &a[..mid], &b[..j], source_offset, target_offset);
+        hirschberg(
+            ops,
+            rows,
+            &a[mid..],
+            &b[j..],
+            source_offset + mid,
+            target_offset + j,
+        );
+    }
+}
+
+/// Returns the last two rows of [`edit_distance_matrix`](crate::edit_distance_matrix) of `a`
+/// and `b`, or of both reversed if `reverse`, which are the same row if `a` is empty.
+///
+/// The rows are filled in `rows`, which has to hold three rows as long as `b`.
+fn last_rows<'r>(
+    a: &[char],
+    b: &[char],
+    reverse: bool,
+    rows: &'r mut [usize],
+) -> (&'r [usize], &'r [usize]) {
+    let len = b.len() + 1;
+    let at = |s: &[char], i: usize| if reverse { s[s.len() - 1 - i] } else { s[i] };
+    let (mut before_prev, rest) = rows.split_at_mut(len);
+    let (mut prev, rest) = rest.split_at_mut(len);
+    let mut row = &mut rest[..len];
+    for (j, cell) in prev.iter_mut().enumerate() {
+        *cell = j;
+    }
+    for i in 1..=a.len() {
+        row[0] = i;
+        for j in 1..=b.len() {
+            let x = at(a, i - 1);
+            let substitution = prev[j - 1] + usize::from(x != at(b, j - 1));
+            row[j] = substitution.min(prev[j] + 1).min(row[j - 1] + 1);
+            if i > 1 && j > 1 && x == at(b, j - 2) && at(a, i - 2) == at(b, j - 1) {
+                row[j] = row[j].min(before_prev[j - 2] + 1);
+            }
+        }
+        // Rotate the buffers, reusing the memory.
+        [before_prev, prev, row] = [prev, row, before_prev];
+    }
+    if a.is_empty() {
+        before_prev.copy_from_slice(prev);
+    }
+    (before_prev, prev)
+}
+
+/// What happened to a part of `a` in an [`AlignedSpan`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpanKind {
+    /// The part is the same in `a` and `b`.
+    Equal,
+    /// The part of `a` was substituted or transposed into the part of `b`.
+    Changed,
+    /// The part of `b` was inserted, and the part of `a` is empty.
+    Inserted,
+    /// The part of `a` was deleted, and the part of `b` is empty.
+    Deleted,
+}
+
+/// A part of `a` and the part of `b` it is aligned with, as found by [`alignment`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct AlignedSpan {
+    pub kind: SpanKind,
+    /// The byte range of the part in `a`.
+    pub a: Range<usize>,
+    /// The byte range of the part in `b`.
+    pub b: Range<usize>,
+}
+
+/// Splits `a` and `b` into aligned parts that are equal, changed, inserted or deleted, e.g. to
+/// highlight what differs in a "did you mean" suggestion.
+///
+/// The parts follow the edits of [`edit_ops`], with neighbouring parts of the same kind
+/// merged. They cover both strings, in order, and their ranges are in bytes, so they can be
+/// used to slice `a` and `b` directly.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn alignment(a: &str, b: &str, limit: usize) -> Option<Vec<AlignedSpan>> {
+    let ops = edit_ops(a, b, limit)?;
+
+    // The byte offset of every char, and of the end of the string.
+    let offsets = |s: &str| {
+        s.char_indices()
+            .map(|(offset, _)| offset)
+            .chain([s.len()])
+            .collect::<Vec<_>>()
+    };
+    let (a_offsets, b_offsets) = (offsets(a), offsets(b));
+
+    let mut spans: Vec<AlignedSpan> = Vec::new();
+    let mut push = |kind, a_chars: Range<usize>, b_chars: Range<usize>| {
+        let a = a_offsets[a_chars.start]..a_offsets[a_chars.end];
+        let b = b_offsets[b_chars.start]..b_offsets[b_chars.end];
+        if a.is_empty() && b.is_empty() {
+            return;
+        }
+        match spans.last_mut() {
+            Some(last) if last.kind == kind => {
+                last.a.end = a.end;
+                last.b.end = b.end;
+            }
+            _ => spans.push(AlignedSpan { kind, a, b }),
+        }
+    };
+
+    let (mut i, mut j) = (0, 0);
+    for op in ops {
+        let (source, target) = match op {
+            EditOp::Insert { source, target }
+            | EditOp::Delete { source, target }
+            | EditOp::Substitute { source, target }
+            | EditOp::Transpose { source, target } => (source, target),
+        };
+        // Everything between two edits is kept as it is.
+        push(SpanKind::Equal, i..source, j..target);
+        (i, j) = match op {
+            EditOp::Insert { .. } => {
+                push(SpanKind::Inserted, source..source, target..target + 1);
+                (source, target + 1)
+            }
+            EditOp::Delete { .. } => {
+                push(SpanKind::Deleted, source..source + 1, target..target);
+                (source + 1, target)
+            }
+            EditOp::Substitute { .. } => {
+                push(SpanKind::Changed, source..source + 1, target..target + 1);
+                (source + 1, target + 1)
+            }
+            EditOp::Transpose { .. } => {
+                push(SpanKind::Changed, source..source + 2, target..target + 2);
+                (source + 2, target + 2)
+            }
+        };
+    }
+    push(
+        SpanKind::Equal,
+        i..a_offsets.len() - 1,
+        j..b_offsets.len() - 1,
+    );
+    Some(spans)
+}
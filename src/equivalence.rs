@@ -0,0 +1,93 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use crate::weighted::weighted_dp;
+use crate::Chars;
+
+/// Sets of chars that are interchangeable, like the `l` and `1`, or the `O` and `0`, that OCR
+/// confuses, for [`edit_distance_with_equivalences`] and
+/// [`MatchOptions::equivalences`](crate::MatchOptions::equivalences).
+///
+/// ```
+/// use rustc_edit_distance::{edit_distance_with_equivalences, EquivalenceClasses};
+///
+/// let ocr = EquivalenceClasses::new().class(&['l', '1', 'I']).class(&['O', '0']);
+/// assert_eq!(edit_distance_with_equivalences("HE11O W0RLD", "HEllO WORLD", 0, &ocr), Some(0));
+/// ```
+///
+/// To make substituting equivalent chars cheaper, rather than free, use
+/// [`are_equivalent`](EquivalenceClasses::are_equivalent) in the costs given to
+/// [`edit_distance_with_costs`](crate::edit_distance_with_costs).
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct EquivalenceClasses {
+    /// The class of each char that is in one.
+    classes: BTreeMap<char, usize>,
+    next_class: usize,
+}
+
+impl EquivalenceClasses {
+    /// Returns an empty set of classes, in which each char is only equivalent to itself.
+    pub fn new() -> Self {
+        EquivalenceClasses::default()
+    }
+
+    /// Adds a class of chars that are equivalent to each other. If some of them are already in
+    /// classes, those are merged into this one, as equivalence is transitive.
+    pub fn class(mut self, chars: &[char]) -> Self {
+        let class = self.next_class;
+        self.next_class += 1;
+        let merged = chars
+            .iter()
+            .filter_map(|c| self.classes.get(c).copied())
+            .collect::<Vec<_>>();
+        for other in self.classes.values_mut() {
+            if merged.contains(other) {
+                *other = class;
+            }
+        }
+        for &c in chars {
+            self.classes.insert(c, class);
+        }
+        self
+    }
+
+    /// Whether `a` and `b` are equal, or in the same class.
+    pub fn are_equivalent(&self, a: char, b: char) -> bool {
+        a == b
+            || matches!(
+                (self.classes.get(&a), self.classes.get(&b)),
+                (Some(a), Some(b)) if a == b
+            )
+    }
+}
+
+/// Like [`edit_distance`](crate::edit_distance), but substituting a char with an equivalent
+/// one in `classes` is free.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_distance_with_equivalences(
+    a: &str,
+    b: &str,
+    limit: usize,
+    classes: &EquivalenceClasses,
+) -> Option<usize> {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    equivalence_dp(&a, &b, limit, classes)
+}
+
+pub(crate) fn equivalence_dp(
+    a: &[char],
+    b: &[char],
+    limit: usize,
+    classes: &EquivalenceClasses,
+) -> Option<usize> {
+    weighted_dp(
+        a,
+        b,
+        limit,
+        |_| 1,
+        |_| 1,
+        |&x, &y| usize::from(!classes.are_equivalent(x, y)),
+        1,
+    )
+}
@@ -0,0 +1,48 @@
+use crate::{edit_distance, find_best_match_for_name_with, similarity_ratio, MatchOptions};
+
+/// Method syntax for the most used functions of the crate, on `str`.
+///
+/// ```
+/// use rustc_edit_distance::EditDistanceExt;
+///
+/// assert_eq!("recieve".edit_distance_to("receive", 2), Some(1));
+/// assert_eq!("kitten".similarity_to("kitten"), 1.0);
+/// assert_eq!("lenght".best_match_in(&["width", "length"]), Some("length"));
+/// ```
+///
+/// The trait is only implemented for `str`, and can't be implemented outside of the crate, so
+/// that methods can be added to it.
+pub trait EditDistanceExt: private::Sealed {
+    /// The [`edit_distance`](crate::edit_distance) to `other`, or `None` if it exceeds
+    /// `limit`.
+    fn edit_distance_to(&self, other: &str, limit: usize) -> Option<usize>;
+
+    /// The [`similarity_ratio`](crate::similarity_ratio) of `self` and `other`.
+    fn similarity_to(&self, other: &str) -> f64;
+
+    /// The candidate that best matches `self` as a lookup, as found by
+    /// [`find_best_match_for_name`](crate::find_best_match_for_name) with the default
+    /// distance.
+    fn best_match_in<'a>(&self, candidates: &[&'a str]) -> Option<&'a str>;
+}
+
+impl EditDistanceExt for str {
+    fn edit_distance_to(&self, other: &str, limit: usize) -> Option<usize> {
+        edit_distance(self, other, limit)
+    }
+
+    fn similarity_to(&self, other: &str) -> f64 {
+        similarity_ratio(self, other)
+    }
+
+    fn best_match_in<'a>(&self, candidates: &[&'a str]) -> Option<&'a str> {
+        // Unlike `find_best_match_for_name`, this doesn't tie the lookup to the candidates.
+        find_best_match_for_name_with(candidates, self, &MatchOptions::new())
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for str {}
+}
@@ -0,0 +1,151 @@
+//! A C interface to the crate, declared in `include/rustc_edit_distance.h`, e.g. for C and
+//! Swift.
+//!
+//! Build it as a shared or static library with
+//! `cargo rustc --release --features ffi --crate-type cdylib` (or `staticlib`).
+//!
+//! Every string is a NUL-terminated UTF-8 string owned by the caller. It is only read during
+//! the call, and never kept or freed. Nothing is allocated for the caller either: results are
+//! written through the pointers it passes, and a best match is returned as an index into its
+//! array. Every function returns a status, one of the `RED_*` constants, and only writes a
+//! result for [`RED_OK`].
+
+use std::ffi::{c_char, CStr};
+use std::vec::Vec;
+
+use crate::{edit_distance, find_best_match_for_name_with, similarity_ratio, MatchOptions};
+
+/// The call succeeded, and its result was written.
+pub const RED_OK: i32 = 0;
+/// The distance exceeds the limit, or no candidate matches.
+pub const RED_NOT_FOUND: i32 = 1;
+/// A pointer that has to be non-null is null.
+pub const RED_NULL_POINTER: i32 = -1;
+/// A string isn't valid UTF-8.
+pub const RED_INVALID_UTF8: i32 = -2;
+
+/// Passed as the distance of [`red_find_best_match`] to use the default one.
+pub const RED_DEFAULT_DISTANCE: usize = usize::MAX;
+
+/// Finds the [`edit_distance`] between `a` and `b`, and writes it to `distance`.
+///
+/// Returns [`RED_NOT_FOUND`] if the distance exceeds `limit`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or point to NUL-terminated strings, and `distance` must be null or
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn red_edit_distance(
+    a: *const c_char,
+    b: *const c_char,
+    limit: usize,
+    distance: *mut usize,
+) -> i32 {
+    let (a, b) = match (str_arg(a), str_arg(b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    if distance.is_null() {
+        return RED_NULL_POINTER;
+    }
+    match edit_distance(a, b, limit) {
+        Some(d) => {
+            *distance = d;
+            RED_OK
+        }
+        None => RED_NOT_FOUND,
+    }
+}
+
+/// Finds the [`similarity_ratio`] of `a` and `b`, and writes it to `similarity`.
+///
+/// # Safety
+///
+/// `a` and `b` must be null or point to NUL-terminated strings, and `similarity` must be null
+/// or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn red_similarity(
+    a: *const c_char,
+    b: *const c_char,
+    similarity: *mut f64,
+) -> i32 {
+    let (a, b) = match (str_arg(a), str_arg(b)) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(status), _) | (_, Err(status)) => return status,
+    };
+    if similarity.is_null() {
+        return RED_NULL_POINTER;
+    }
+    *similarity = similarity_ratio(a, b);
+    RED_OK
+}
+
+/// Finds the candidate that best matches `lookup`, as
+/// [`find_best_match_for_name`](crate::find_best_match_for_name) does, and writes its index in
+/// `candidates` to `index`.
+///
+/// `dist` is the greatest edit distance of a match, or [`RED_DEFAULT_DISTANCE`]. Returns
+/// [`RED_NOT_FOUND`] if no candidate matches.
+///
+/// # Safety
+///
+/// `candidates` must be null or point to `len` pointers, each null or pointing to a
+/// NUL-terminated string. `candidates` may only be null if `len` is 0. `lookup` must be null
+/// or point to a NUL-terminated string, and `index` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn red_find_best_match(
+    candidates: *const *const c_char,
+    len: usize,
+    lookup: *const c_char,
+    dist: usize,
+    index: *mut usize,
+) -> i32 {
+    let lookup = match str_arg(lookup) {
+        Ok(lookup) => lookup,
+        Err(status) => return status,
+    };
+    if index.is_null() || (candidates.is_null() && len > 0) {
+        return RED_NULL_POINTER;
+    }
+    let pointers = if len == 0 {
+        &[]
+    } else {
+        std::slice::from_raw_parts(candidates, len)
+    };
+    let mut strings = Vec::with_capacity(len);
+    for &pointer in pointers {
+        match str_arg(pointer) {
+            Ok(s) => strings.push(s),
+            Err(status) => return status,
+        }
+    }
+
+    let mut options = MatchOptions::new();
+    if dist != RED_DEFAULT_DISTANCE {
+        options = options.max_distance(dist);
+    }
+    match find_best_match_for_name_with(&strings, lookup, &options) {
+        Some(best) => {
+            // The index of the candidate itself, not of an equal one before it.
+            *index = strings
+                .iter()
+                .position(|s| s.as_ptr() == best.as_ptr())
+                .unwrap();
+            RED_OK
+        }
+        None => RED_NOT_FOUND,
+    }
+}
+
+/// Reads a string argument, or returns the status for why it can't be.
+///
+/// # Safety
+///
+/// `s` must be null or point to a NUL-terminated string that outlives `'a`.
+unsafe fn str_arg<'a>(s: *const c_char) -> Result<&'a str, i32> {
+    if s.is_null() {
+        return Err(RED_NULL_POINTER);
+    }
+    CStr::from_ptr(s).to_str().map_err(|_| RED_INVALID_UTF8)
+}
@@ -0,0 +1,101 @@
+use alloc::string::String;
+
+use fst::{Automaton, IntoStreamer, Streamer};
+
+use crate::{default_match_distance, AutomatonState, LevenshteinAutomaton};
+
+/// The state of a [`LevenshteinAutomaton`] reading the UTF-8 bytes of the keys of an FST.
+#[derive(Clone, Debug)]
+pub struct FstState {
+    /// `None` once the bytes read aren't UTF-8.
+    state: Option<AutomatonState>,
+    /// The bytes of a char that is only partly read yet, of which there are `pending_len`.
+    pending: [u8; 4],
+    pending_len: usize,
+}
+
+/// Lets a [`LevenshteinAutomaton`] search an [`fst::Set`] or [`fst::Map`] for the keys within
+/// its `max_dist`, which are then found in lexicographic order, without decoding any of the
+/// others.
+impl Automaton for LevenshteinAutomaton {
+    type State = FstState;
+
+    fn start(&self) -> FstState {
+        FstState {
+            state: Some(LevenshteinAutomaton::start(self)),
+            pending: [0; 4],
+            pending_len: 0,
+        }
+    }
+
+    fn is_match(&self, state: &FstState) -> bool {
+        state.pending_len == 0
+            && state
+                .state
+                .as_ref()
+                .map_or(false, |s| LevenshteinAutomaton::is_match(self, s))
+    }
+
+    fn can_match(&self, state: &FstState) -> bool {
+        state
+            .state
+            .as_ref()
+            .map_or(false, |s| LevenshteinAutomaton::can_match(self, s))
+    }
+
+    fn accept(&self, state: &FstState, byte: u8) -> FstState {
+        let mut next = state.clone();
+        let s = match &state.state {
+            Some(s) if next.pending_len < 4 => s,
+            _ => {
+                next.state = None;
+                return next;
+            }
+        };
+        next.pending[next.pending_len] = byte;
+        next.pending_len += 1;
+        match core::str::from_utf8(&next.pending[..next.pending_len]) {
+            Ok(c) => {
+                let c = c.chars().next().unwrap();
+                next.state = Some(self.step(s, c));
+                next.pending_len = 0;
+            }
+            // The char isn't complete yet.
+            Err(e) if e.error_len().is_none() => {}
+            Err(_) => next.state = None,
+        }
+        next
+    }
+}
+
+/// Like [`find_best_match_for_name`](crate::find_best_match_for_name), but only of its edit
+/// distance phase, over the keys of an [`fst::Set`], which need not be in memory.
+///
+/// The set is searched with a [`LevenshteinAutomaton`], so only the keys within `dist` of
+/// `lookup` are read. `dist` defaults to the same threshold as
+/// [`find_best_match_for_name`](crate::find_best_match_for_name), and ties are broken in favor
+/// of the lexicographically smallest key.
+pub fn find_best_match_in_fst<D: AsRef<[u8]>>(
+    set: &fst::Set<D>,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<String> {
+    let automaton = LevenshteinAutomaton::new(
+        lookup,
+        dist.unwrap_or_else(|| default_match_distance(lookup)),
+    );
+    let mut stream = set.search_with_state(&automaton).into_stream();
+    let mut best: Option<(usize, String)> = None;
+    while let Some((key, state)) = stream.next() {
+        let d = match state.state.as_ref().and_then(|s| automaton.distance(s)) {
+            Some(d) => d,
+            None => continue,
+        };
+        // The keys come in lexicographic order, so the first of a distance is the smallest.
+        if best.as_ref().map_or(true, |(best, _)| d < *best) {
+            // Only keys that are UTF-8 are matched.
+            best = Some((d, String::from_utf8(key.to_vec()).unwrap()));
+        }
+    }
+    best.map(|(_, key)| key)
+}
@@ -0,0 +1,72 @@
+use core::fmt;
+
+/// Finds the [Hamming distance] between two strings of the same length, i.e. the number of
+/// positions at which their chars differ.
+///
+/// Only substitutions are counted, so the strings must have the same number of chars.
+/// Counting stops as soon as the limit is exceeded.
+///
+/// Returns `Ok(None)` if the distance exceeds the limit.
+///
+/// [Hamming distance]: https://en.wikipedia.org/wiki/Hamming_distance
+pub fn hamming_distance(a: &str, b: &str, limit: usize) -> Result<Option<usize>, LengthMismatch> {
+    let (a_len, b_len) = (a.chars().count(), b.chars().count());
+    if a_len != b_len {
+        return Err(LengthMismatch { a_len, b_len });
+    }
+    Ok(count_mismatches(a.chars().zip(b.chars()), limit))
+}
+
+/// Like [`hamming_distance`], but for byte strings, where every byte is a position.
+pub fn hamming_distance_bytes(
+    a: &[u8],
+    b: &[u8],
+    limit: usize,
+) -> Result<Option<usize>, LengthMismatch> {
+    if a.len() != b.len() {
+        return Err(LengthMismatch {
+            a_len: a.len(),
+            b_len: b.len(),
+        });
+    }
+    Ok(count_mismatches(a.iter().zip(b), limit))
+}
+
+fn count_mismatches<T: PartialEq>(
+    pairs: impl Iterator<Item = (T, T)>,
+    limit: usize,
+) -> Option<usize> {
+    let mut distance = 0;
+    for (x, y) in pairs {
+        if x != y {
+            distance += 1;
+            // If we know the limit will be exceeded, we can return early.
+            if distance > limit {
+                return None;
+            }
+        }
+    }
+    Some(distance)
+}
+
+/// An error returned by [`hamming_distance`] for strings of different lengths.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// The length of `a`.
+    pub a_len: usize,
+    /// The length of `b`.
+    pub b_len: usize,
+}
+
+impl fmt::Display for LengthMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hamming distance needs inputs of the same length, not {} and {}",
+            self.a_len, self.b_len
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
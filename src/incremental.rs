@@ -0,0 +1,120 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::fill_row;
+
+/// Keeps the [`edit_distance`](crate::edit_distance) from a lookup to each of a fixed set of
+/// candidates up to date while the lookup is typed one char at a time, as in an autocomplete
+/// box.
+///
+/// The table of each candidate is kept with one row per char of the lookup, so
+/// [`push_char`](IncrementalMatcher::push_char) only fills in a new row, in time proportional to
+/// the length of the candidate, and [`pop_char`](IncrementalMatcher::pop_char) just drops it.
+///
+/// ```
+/// use rustc_edit_distance::IncrementalMatcher;
+///
+/// let mut matcher = IncrementalMatcher::new(&["checkout", "cherry-pick", "clone"]);
+/// for c in "chek".chars() {
+///     matcher.push_char(c);
+/// }
+/// matcher.push_char('o');
+/// assert_eq!(matcher.best_match(3), Some(("checkout", 3)));
+/// matcher.pop_char();
+/// assert_eq!(matcher.lookup(), "chek");
+/// ```
+#[derive(Clone, Debug)]
+pub struct IncrementalMatcher<'a> {
+    lookup: String,
+    candidates: Vec<Candidate<'a>>,
+}
+
+#[derive(Clone, Debug)]
+struct Candidate<'a> {
+    candidate: &'a str,
+    chars: Vec<char>,
+    /// The rows of the table, one after the other, each `chars.len() + 1` long. There is one
+    /// more row than the lookup has chars.
+    rows: Vec<usize>,
+}
+
+impl<'a> IncrementalMatcher<'a> {
+    /// Returns a matcher of `candidates` against an empty lookup.
+    pub fn new(candidates: &[&'a str]) -> Self {
+        let candidates = candidates
+            .iter()
+            .map(|&candidate| {
+                let chars = candidate.chars().collect::<Vec<_>>();
+                // Every char of the candidate is inserted into the empty lookup.
+                let rows = (0..=chars.len()).collect();
+                Candidate {
+                    candidate,
+                    chars,
+                    rows,
+                }
+            })
+            .collect();
+        IncrementalMatcher {
+            lookup: String::new(),
+            candidates,
+        }
+    }
+
+    /// Returns the lookup typed so far.
+    pub fn lookup(&self) -> &str {
+        &self.lookup
+    }
+
+    /// Appends `c` to the lookup, filling in one more row of the table of each candidate.
+    pub fn push_char(&mut self, c: char) {
+        let row_before = self.lookup.chars().next_back();
+        self.lookup.push(c);
+        let i = self.lookup.chars().count();
+
+        for candidate in &mut self.candidates {
+            let width = candidate.chars.len() + 1;
+            candidate.rows.resize((i + 1) * width, 0);
+            let (above, current) = candidate.rows.split_at_mut(i * width);
+            let (above, prev) = above.split_at(above.len() - width);
+            // Without a char before `c`, there is nothing to transpose it with, and the row
+            // above the previous one isn't read.
+            let prev_prev = &above[above.len().saturating_sub(width)..];
+            current[0] = i;
+            fill_row(
+                &c,
+                row_before.as_ref(),
+                &candidate.chars,
+                prev_prev,
+                prev,
+                current,
+            );
+        }
+    }
+
+    /// Removes the last char of the lookup, and its row of each table, and returns it, or
+    /// `None` if the lookup is empty.
+    pub fn pop_char(&mut self) -> Option<char> {
+        let c = self.lookup.pop()?;
+        let i = self.lookup.chars().count();
+        for candidate in &mut self.candidates {
+            candidate
+                .rows
+                .truncate((i + 1) * (candidate.chars.len() + 1));
+        }
+        Some(c)
+    }
+
+    /// Returns each candidate with its distance from the lookup, in the order they were given.
+    pub fn distances(&self) -> impl Iterator<Item = (&'a str, usize)> + '_ {
+        self.candidates
+            .iter()
+            .map(|c| (c.candidate, *c.rows.last().unwrap()))
+    }
+
+    /// Returns the candidate closest to the lookup and its distance, or `None` if every
+    /// candidate is further than `limit`. Ties go to the candidate given first.
+    pub fn best_match(&self, limit: usize) -> Option<(&'a str, usize)> {
+        self.distances()
+            .filter(|&(_, d)| d <= limit)
+            .min_by_key(|&(_, d)| d)
+    }
+}
@@ -0,0 +1,85 @@
+use core::cmp;
+
+use crate::small_vec::{SmallVec, INLINE_LEN};
+use crate::Chars;
+
+/// Finds the [Jaro similarity] of two strings, from 0.0 for strings with no chars in common
+/// to 1.0 for equal ones.
+///
+/// Two chars match if they are equal and no further apart than half the length of the longer
+/// string, minus one. The similarity is the mean of the fractions of `a` and of `b` that
+/// matched, and of the fraction of matches that are in the same order in both strings.
+/// Like [`edit_distance`](crate::edit_distance), this compares `char`s.
+///
+/// [Jaro similarity]: https://en.wikipedia.org/wiki/Jaro%E2%80%93Winkler_distance#Jaro_similarity
+pub fn jaro(a: &str, b: &str) -> f64 {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    if a.is_empty() && b.is_empty() {
+        return 1.0;
+    }
+    if a.is_empty() || b.is_empty() {
+        return 0.0;
+    }
+
+    let window = (cmp::max(a.len(), b.len()) / 2).saturating_sub(1);
+    let mut b_matched = SmallVec::<bool, INLINE_LEN>::from_elem(false, b.len());
+    let a_matches = a
+        .iter()
+        .enumerate()
+        .filter_map(|(i, x)| {
+            let start = i.saturating_sub(window);
+            let end = cmp::min(i + window + 1, b.len());
+            let j = (start..end).find(|&j| !b_matched[j] && b[j] == *x)?;
+            b_matched[j] = true;
+            Some(*x)
+        })
+        .collect::<Chars>();
+    if a_matches.is_empty() {
+        return 0.0;
+    }
+
+    // Half the number of matched chars that differ when both lists of matches are in order.
+    let b_matches = b
+        .iter()
+        .zip(b_matched.iter())
+        .filter(|(_, &m)| m)
+        .map(|(y, _)| y);
+    let transpositions = a_matches
+        .iter()
+        .zip(b_matches)
+        .filter(|(x, y)| x != y)
+        .count()
+        / 2;
+
+    let m = a_matches.len() as f64;
+    (m / a.len() as f64 + m / b.len() as f64 + (m - transpositions as f64) / m) / 3.0
+}
+
+/// Finds the [Jaro-Winkler similarity] of two strings, from 0.0 for strings with no chars in
+/// common to 1.0 for equal ones.
+///
+/// This is the [`jaro`] similarity, boosted for strings that start the same: each of the
+/// first four chars that `a` and `b` share takes off a tenth of the remaining dissimilarity.
+/// That suits short names, which are rarely misspelled in their first few chars.
+///
+/// [Jaro-Winkler similarity]: https://en.wikipedia.org/wiki/Jaro%E2%80%93Winkler_distance
+pub fn jaro_winkler(a: &str, b: &str) -> f64 {
+    jaro_winkler_with_prefix_weight(a, b, 0.1)
+}
+
+/// Like [`jaro_winkler`], but each shared char at the start takes off `prefix_weight` of the
+/// remaining dissimilarity, instead of a tenth.
+///
+/// With a `prefix_weight` of 0 this is the [`jaro`] similarity. Above 0.25, four shared chars
+/// would take off more than all of the dissimilarity, so the similarity can exceed 1.0.
+pub fn jaro_winkler_with_prefix_weight(a: &str, b: &str, prefix_weight: f64) -> f64 {
+    let jaro = jaro(a, b);
+    let prefix = a
+        .chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x == y)
+        .take(4)
+        .count();
+    jaro + prefix as f64 * prefix_weight * (1.0 - jaro)
+}
@@ -0,0 +1,122 @@
+use alloc::collections::BTreeMap;
+
+use crate::weighted::weighted_dp;
+use crate::Chars;
+
+/// Where the keys are on a keyboard, for costing typos by how far apart the keys are.
+///
+/// ```
+/// use rustc_edit_distance::{keyboard_edit_distance, KeyboardLayout};
+///
+/// let qwerty = KeyboardLayout::qwerty();
+/// // `w` is next to `q`, but `p` is across the keyboard.
+/// assert_eq!(keyboard_edit_distance("wuit", "quit", 4, &qwerty), Some(1));
+/// assert_eq!(keyboard_edit_distance("puit", "quit", 4, &qwerty), Some(2));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyboardLayout {
+    /// The row and column of each key.
+    keys: BTreeMap<char, (usize, usize)>,
+}
+
+impl KeyboardLayout {
+    /// The US QWERTY layout, without the shifted symbols.
+    pub fn qwerty() -> Self {
+        KeyboardLayout::from_rows(&["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"])
+    }
+
+    /// The French AZERTY layout, without the shifted symbols.
+    pub fn azerty() -> Self {
+        KeyboardLayout::from_rows(&[
+            "&é\"'(-è_çà)=",
+            "azertyuiop^$",
+            "qsdfghjklmù*",
+            "wxcvbn,;:!",
+        ])
+    }
+
+    /// A layout with the given rows of keys, from the top, each one shifted half a key to the
+    /// right of the one above, as on most keyboards. Letters are on the same key as their
+    /// upper case form.
+    pub fn from_rows(rows: &[&str]) -> Self {
+        let mut keys = BTreeMap::new();
+        for (row, keys_in_row) in rows.iter().enumerate() {
+            for (column, key) in keys_in_row.chars().enumerate() {
+                for c in key.to_lowercase() {
+                    keys.entry(c).or_insert((row, column));
+                }
+            }
+        }
+        KeyboardLayout { keys }
+    }
+
+    /// Whether `a` and `b` are on the same key, or on keys that touch.
+    ///
+    /// Chars that aren't on the keyboard are only adjacent to themselves.
+    pub fn are_adjacent(&self, a: char, b: char) -> bool {
+        if a == b {
+            return true;
+        }
+        let (a, b) = match (self.key(a), self.key(b)) {
+            (Some(a), Some(b)) => (a, b),
+            _ => return false,
+        };
+        let ((row_a, column_a), (row_b, column_b)) = if a.0 <= b.0 { (a, b) } else { (b, a) };
+        match row_b - row_a {
+            0 => column_a.abs_diff(column_b) <= 1,
+            // The row below is shifted to the right, so a key touches the one right above it,
+            // and the one after that.
+            1 => column_b == column_a || column_b + 1 == column_a,
+            _ => false,
+        }
+    }
+
+    /// The cost of typing `b` instead of `a`: 0 for the same char, 1 for adjacent keys, and 2
+    /// otherwise, as in [`keyboard_edit_distance`]. This can also be passed to
+    /// [`edit_distance_with_costs`](crate::edit_distance_with_costs), along with insertions
+    /// and deletions that cost 2.
+    pub fn substitution_cost(&self, a: char, b: char) -> usize {
+        if a == b {
+            0
+        } else if self.are_adjacent(a, b) {
+            1
+        } else {
+            2
+        }
+    }
+
+    fn key(&self, c: char) -> Option<(usize, usize)> {
+        let mut lower = c.to_lowercase();
+        match (lower.next(), lower.next()) {
+            (Some(lower), None) => self.keys.get(&lower).copied(),
+            _ => self.keys.get(&c).copied(),
+        }
+    }
+}
+
+/// Like [`edit_distance`](crate::edit_distance), but counting half an edit for hitting a key
+/// next to the right one, so that more likely typos are closer.
+///
+/// The distance is in half edits: insertions, deletions, transpositions and substitutions of
+/// keys that aren't adjacent in `layout` cost 2, and substitutions of adjacent keys cost 1.
+/// `limit` is in the same unit.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn keyboard_edit_distance(
+    a: &str,
+    b: &str,
+    limit: usize,
+    layout: &KeyboardLayout,
+) -> Option<usize> {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    weighted_dp(
+        &a,
+        &b,
+        limit,
+        |_| 2,
+        |_| 2,
+        |&x, &y| layout.substitution_cost(x, y),
+        2,
+    )
+}
@@ -8,29 +8,1165 @@
 //!
 //! This project is likewise dual-licensed under Apache-2.0 OR MIT, at your option.
 
-use std::{cmp, mem};
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod abbreviation;
+mod align;
+mod approx;
+mod automaton;
+mod bit_parallel;
+mod bk_tree;
+mod buffer;
+mod cache;
+mod candidate_set;
+mod cluster;
+mod distance_matrix;
+mod edit_ops;
+mod equivalence;
+mod ext;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fst")]
+mod fst_set;
+mod hamming;
+mod incremental;
+mod jaro;
+mod keyboard;
+mod match_options;
+mod metric;
+mod multi_pattern;
+mod ngram;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "std")]
+mod path;
+mod persist;
+mod phonetic;
+mod ranked;
+#[cfg(feature = "simd")]
+mod simd;
+mod small_vec;
+mod spell;
+mod suggestion;
+mod symspell;
+mod tokens;
+mod trie;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+mod weighted;
+mod wildcard;
+mod words;
+
+pub use abbreviation::abbreviation_score;
+pub use align::{global_align, global_align_linear_space, local_align, Alignment, ScoringScheme};
+pub use approx::{find_approx, fuzzy_contains, ApproxMatch};
+pub use automaton::{AutomatonState, LevenshteinAutomaton};
+pub use bk_tree::BkTree;
+pub use buffer::{edit_distance_in, edit_distances, EditDistanceBuffer};
+pub use cache::{CacheStats, DistanceCache};
+pub use candidate_set::CandidateSet;
+pub use cluster::{cluster, cluster_with, Linkage};
+pub use distance_matrix::{distance_matrix, DistanceMatrix};
+pub use edit_ops::{alignment, edit_ops, edit_ops_linear_space, AlignedSpan, EditOp, SpanKind};
+pub use equivalence::{edit_distance_with_equivalences, EquivalenceClasses};
+pub use ext::EditDistanceExt;
+#[cfg(feature = "fst")]
+pub use fst_set::{find_best_match_in_fst, FstState};
+pub use hamming::{hamming_distance, hamming_distance_bytes, LengthMismatch};
+pub use incremental::IncrementalMatcher;
+pub use jaro::{jaro, jaro_winkler, jaro_winkler_with_prefix_weight};
+pub use keyboard::{keyboard_edit_distance, KeyboardLayout};
+#[cfg(feature = "unicode-normalization")]
+pub use match_options::Normalization;
+pub use match_options::{MatchOptions, Phonetic, Scorer, Threshold, TieBreak};
+pub use metric::Metric;
+pub use multi_pattern::{MultiMatch, MultiPattern};
+pub use ngram::dice_coefficient;
+#[cfg(feature = "std")]
+pub use ngram::{cosine_similarity, NGramProfile};
+#[cfg(feature = "rayon")]
+pub use parallel::{distance_matrix_parallel, find_best_match_for_name_parallel};
+#[cfg(feature = "std")]
+pub use path::{find_best_match_for_path, os_str_edit_distance, path_edit_distance, PathScoring};
+pub use persist::IndexDecodeError;
+pub use phonetic::{double_metaphone, metaphone, soundex};
+pub use ranked::{
+    find_all_matches_within, find_all_matches_within_with, find_best_match_indices,
+    find_best_matches, matches_iter, Match, MatchPhase,
+};
+pub use spell::{Correction, SpellChecker, WordListError};
+pub use suggestion::Suggestion;
+pub use symspell::SymSpellIndex;
+pub use tokens::{
+    line_edit_distance, line_edit_distance_hashed, phrase_distance, token_edit_distance,
+    token_edit_distance_with, word_edit_distance, word_edit_distance_with,
+};
+pub use trie::Trie;
+pub use weighted::{
+    edit_distance_with_costs, weighted_edit_distance, weighted_edit_distance_affine, EditCosts,
+};
+pub use wildcard::pattern_distance;
+pub use words::split_identifier;
+
+use alloc::{collections::BTreeMap, string::String, vec, vec::Vec};
+use core::{cmp, fmt, mem, ops::Range};
+
+use buffer::RowBuffer;
+use small_vec::{SmallVec, INLINE_LEN};
+
+/// The chars of a string, kept on the stack if there are few enough of them.
+type Chars = SmallVec<char, INLINE_LEN>;
 
 /// Finds the [edit distance] between two strings.
 ///
+/// This is the [optimal string alignment] distance: insertions, deletions, substitutions and
+/// transpositions of adjacent chars all cost 1, but no substring is edited more than once. So
+/// `"ca"` and `"abc"` are at distance 3, as the `"ca"` → `"ac"` transposition can't be followed
+/// by an insertion between the swapped chars. [`damerau_levenshtein`] allows that.
+///
 /// Returns `None` if the distance exceeds the limit.
 ///
 /// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+/// [optimal string alignment]: https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Optimal_string_alignment_distance
 pub fn edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
-    let mut a = &a.chars().collect::<Vec<_>>()[..];
-    let mut b = &b.chars().collect::<Vec<_>>()[..];
+    // An ASCII char is a single byte, so the bytes can be compared as they are, without
+    // decoding or collecting the chars.
+    if a.is_ascii() && b.is_ascii() {
+        return edit_distance_fast(a.as_bytes(), b.as_bytes(), limit);
+    }
+
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// Like [`edit_distance`], but returns `limit + 1` instead of `None` if the distance exceeds
+/// the limit.
+///
+/// The result is only the exact distance if it is at most `limit`; `limit + 1` just means
+/// "further than `limit`". That gives every candidate a number that can be compared, e.g. with
+/// `min`, without unwrapping.
+pub fn edit_distance_capped(a: &str, b: &str, limit: usize) -> usize {
+    // With a limit of `usize::MAX`, the distance is always within it.
+    edit_distance(a, b, limit).unwrap_or_else(|| limit + 1)
+}
+
+/// Another name for [`edit_distance`], which says which distance it is.
+pub fn osa_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    edit_distance(a, b, limit)
+}
+
+/// Finds the [Levenshtein distance] between two strings: like [`edit_distance`], but without
+/// transpositions, so `"ab"` and `"ba"` are at distance 2.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [Levenshtein distance]: https://en.wikipedia.org/wiki/Levenshtein_distance
+pub fn levenshtein_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    // Stripping the common prefix and suffix is safe without transpositions too.
+    let (a, b) = match trim(&a, &b, limit) {
+        Trim::Done(distance) => return distance,
+        Trim::Dp { a, b, .. } => (a, b),
+    };
+
+    if b.len() <= bit_parallel::MAX_LEN && a.iter().chain(b).all(|&x| u32::from(x) <= 0xff) {
+        bit_parallel::levenshtein(a, b, limit)
+    } else {
+        // A transposition costing 2 is never better than two substitutions, so none are made.
+        weighted::weighted_dp(a, b, limit, |_| 1, |_| 1, |x, y| usize::from(x != y), 2)
+    }
+}
+
+/// Which distance [`edit_distance_with_algorithm`] finds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DistanceAlgorithm {
+    /// [`levenshtein_distance`]: insertions, deletions and substitutions.
+    Levenshtein,
+    /// [`osa_distance`], the same as [`edit_distance`]: also transpositions of adjacent chars,
+    /// as long as no substring is edited more than once.
+    Osa,
+    /// [`damerau_levenshtein`]: also transpositions of adjacent chars, with no restriction.
+    DamerauLevenshtein,
+}
+
+/// Finds the distance between two strings according to `algorithm`, for callers that choose
+/// it at runtime, e.g. to match the scores of another system.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_distance_with_algorithm(
+    algorithm: DistanceAlgorithm,
+    a: &str,
+    b: &str,
+    limit: usize,
+) -> Option<usize> {
+    match algorithm {
+        DistanceAlgorithm::Levenshtein => levenshtein_distance(a, b, limit),
+        DistanceAlgorithm::Osa => osa_distance(a, b, limit),
+        DistanceAlgorithm::DamerauLevenshtein => damerau_levenshtein(a, b, limit),
+    }
+}
+
+/// Finds the [Damerau-Levenshtein distance] between two strings.
+///
+/// Unlike [`edit_distance`], chars may be inserted between two that are transposed, so
+/// `"ca"` and `"abc"` are at distance 2. In exchange, this is a metric: it satisfies the
+/// triangle inequality.
+///
+/// This takes time and memory proportional to the product of the lengths of the strings.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [Damerau-Levenshtein distance]: https://en.wikipedia.org/wiki/Damerau%E2%80%93Levenshtein_distance#Distance_with_adjacent_transpositions
+pub fn damerau_levenshtein(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // If we know the limit will be exceeded, we can return early.
+    if a.len().abs_diff(b.len()) > limit {
+        return None;
+    }
+
+    // `d[i + 1][j + 1]` is the distance between `a[..i]` and `b[..j]`. The extra row and column
+    // hold a distance larger than any real one, for transpositions with no earlier match.
+    let max = a.len() + b.len();
+    let mut d = vec![vec![max; b.len() + 2]; a.len() + 2];
+    for i in 0..=a.len() {
+        d[i + 1][1] = i;
+    }
+    for j in 0..=b.len() {
+        d[1][j + 1] = j;
+    }
+
+    // The last row of `a` each char was seen in.
+    let mut last_row = BTreeMap::new();
+    for i in 1..=a.len() {
+        // The last column of `b` that matched `a[i - 1]` in this row.
+        let mut last_match_column = 0;
+        for j in 1..=b.len() {
+            let k = last_row.get(&b[j - 1]).copied().unwrap_or(0);
+            let l = last_match_column;
+            let substitution_cost = if a[i - 1] == b[j - 1] {
+                last_match_column = j;
+                0
+            } else {
+                1
+            };
+            d[i + 1][j + 1] = cmp::min(
+                cmp::min(
+                    // substitution
+                    d[i][j] + substitution_cost,
+                    // insertion
+                    d[i + 1][j] + 1,
+                ),
+                cmp::min(
+                    // deletion
+                    d[i][j + 1] + 1,
+                    // transposition of `a[k - 1]` and `b[l - 1]`, with everything between them
+                    // deleted from `a` and inserted into `b`
+                    d[k][l] + (i - k - 1) + 1 + (j - l - 1),
+                ),
+            );
+        }
+        last_row.insert(a[i - 1], i);
+    }
+
+    let distance = d[a.len() + 1][b.len() + 1];
+    (distance <= limit).then_some(distance)
+}
+
+/// Finds the [edit distance] between two slices of anything that can be compared, e.g. the
+/// tokens of two lines of code, or hashes of the lines of two files.
+///
+/// Every element is a unit of edit, as a `char` is for [`edit_distance`].
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn edit_distance_slices<T: PartialEq>(a: &[T], b: &[T], limit: usize) -> Option<usize> {
+    edit_distance_with_impl(a, b, limit, unit_substitution_cost)
+}
+
+/// Finds the [edit distance] between two byte strings.
+///
+/// Unlike [`edit_distance`], every byte is a unit of edit, so the inputs don't have to be
+/// valid UTF-8 and nothing is decoded. For ASCII inputs the result is the same.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn edit_distance_bytes(a: &[u8], b: &[u8], limit: usize) -> Option<usize> {
+    edit_distance_fast(a, b, limit)
+}
+
+/// Finds the [edit distance] between two strings, ignoring case.
+///
+/// Both strings are case folded before they are compared, as by [`caseless_eq`], so the
+/// distance is measured between the folded forms. A single char can fold to several, e.g. `ß`
+/// folds to `ss`, which makes `"STRASSE"` and `"straße"` equal.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn edit_distance_ci(a: &str, b: &str, limit: usize) -> Option<usize> {
+    // ASCII folds to ASCII, one char for one.
+    if a.is_ascii() && b.is_ascii() {
+        return edit_distance_ascii_ci(a, b, limit);
+    }
+
+    let a = fold_chars(a).collect::<Chars>();
+    let b = fold_chars(b).collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// Finds the [edit distance] between two strings, ignoring the case of ASCII letters only.
+///
+/// This is cheaper than [`edit_distance_ci`], as neither string is folded: the chars are
+/// compared ignoring the case of ASCII letters, and other chars as they are, so `"Ä"` and
+/// `"ä"` still differ.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn edit_distance_ascii_ci(a: &str, b: &str, limit: usize) -> Option<usize> {
+    if a.is_ascii() && b.is_ascii() {
+        return edit_distance_fast(
+            AsciiCaseless::bytes(a.as_bytes()),
+            AsciiCaseless::bytes(b.as_bytes()),
+            limit,
+        );
+    }
+
+    let a = a
+        .chars()
+        .map(AsciiCaseless)
+        .collect::<SmallVec<_, INLINE_LEN>>();
+    let b = b
+        .chars()
+        .map(AsciiCaseless)
+        .collect::<SmallVec<_, INLINE_LEN>>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// A byte or char that equals the same ASCII letter in the other case, for
+/// [`edit_distance_ascii_ci`]. Its value as a `u32` is that of the lowercase letter, so the
+/// bit-parallel and anti-diagonal paths agree with its `PartialEq`.
+#[derive(Clone, Copy, Debug, Default)]
+#[repr(transparent)]
+struct AsciiCaseless<T>(T);
+
+impl AsciiCaseless<u8> {
+    /// Views `bytes` as bytes that ignore ASCII case, without copying them.
+    fn bytes(bytes: &[u8]) -> &[AsciiCaseless<u8>] {
+        // SAFETY: `AsciiCaseless` is `repr(transparent)`, so it has the same layout as `u8`.
+        unsafe { core::slice::from_raw_parts(bytes.as_ptr().cast(), bytes.len()) }
+    }
+}
+
+impl PartialEq for AsciiCaseless<u8> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl PartialEq for AsciiCaseless<char> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.eq_ignore_ascii_case(&other.0)
+    }
+}
+
+impl From<AsciiCaseless<u8>> for u32 {
+    fn from(x: AsciiCaseless<u8>) -> u32 {
+        u32::from(x.0.to_ascii_lowercase())
+    }
+}
+
+impl From<AsciiCaseless<char>> for u32 {
+    fn from(x: AsciiCaseless<char>) -> u32 {
+        u32::from(x.0.to_ascii_lowercase())
+    }
+}
+
+/// The most bytes the shorter string passed to [`const_edit_distance`] can have, as its rows
+/// can't be allocated.
+const CONST_MAX_LEN: usize = 256;
+
+/// Finds the [edit distance] between two ASCII strings, in a `const` context.
+///
+/// This is a separate, plain implementation of [`edit_distance`] without a limit, for
+/// computing distances between string literals at compile time:
+///
+/// ```
+/// use rustc_edit_distance::const_edit_distance;
+///
+/// const DISTANCE: usize = const_edit_distance("commit", "comit");
+/// assert_eq!(DISTANCE, 1);
+/// ```
+///
+/// # Panics
+///
+/// Panics, or fails to compile when evaluated at compile time, if either string isn't ASCII
+/// or the shorter one is longer than 256 bytes.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub const fn const_edit_distance(a: &str, b: &str) -> usize {
+    const fn min(x: usize, y: usize) -> usize {
+        if x < y {
+            x
+        } else {
+            y
+        }
+    }
+
+    // The distance is symmetric, so the rows can be as long as the shorter string.
+    let (a, b) = if a.len() < b.len() {
+        (b.as_bytes(), a.as_bytes())
+    } else {
+        (a.as_bytes(), b.as_bytes())
+    };
+    // `str::is_ascii` isn't `const` on the minimum supported Rust version.
+    let mut i = 0;
+    while i < a.len() {
+        assert!(
+            a[i].is_ascii() && (i >= b.len() || b[i].is_ascii()),
+            "const_edit_distance only takes ASCII strings"
+        );
+        i += 1;
+    }
+    assert!(
+        b.len() <= CONST_MAX_LEN,
+        "const_edit_distance only takes strings of up to 256 bytes"
+    );
+
+    let mut prev_prev = [usize::MAX; CONST_MAX_LEN + 1];
+    let mut prev = [0; CONST_MAX_LEN + 1];
+    let mut current = [0; CONST_MAX_LEN + 1];
+    let mut j = 0;
+    while j <= b.len() {
+        prev[j] = j;
+        j += 1;
+    }
+
+    let mut i = 1;
+    while i <= a.len() {
+        current[0] = i;
+        let mut j = 1;
+        while j <= b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            current[j] = min(
+                // deletion
+                prev[j] + 1,
+                min(
+                    // insertion
+                    current[j - 1] + 1,
+                    // substitution
+                    prev[j - 1] + substitution_cost,
+                ),
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                // transposition
+                current[j] = min(current[j], prev_prev[j - 2] + 1);
+            }
+            j += 1;
+        }
+        prev_prev = prev;
+        prev = current;
+        i += 1;
+    }
+    prev[b.len()]
+}
+
+/// Finds the [edit distance] between two strings, counting extended grapheme clusters.
+///
+/// Each user-perceived character is a single unit of edit, so a letter with combining marks
+/// or an emoji sequence is inserted, deleted or substituted as a whole. Two clusters are only
+/// equal if they consist of the same `char`s; canonically equivalent forms still differ.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+#[cfg(feature = "unicode-segmentation")]
+pub fn edit_distance_graphemes(a: &str, b: &str, limit: usize) -> Option<usize> {
+    use unicode_segmentation::UnicodeSegmentation;
+
+    let a = a.graphemes(true).collect::<Vec<_>>();
+    let b = b.graphemes(true).collect::<Vec<_>>();
+    edit_distance_slices(&a, &b, limit)
+}
+
+/// Computes the whole dynamic programming table behind [`edit_distance`], for inspection.
+///
+/// The table has `a.chars().count() + 1` rows and `b.chars().count() + 1` columns, and the
+/// cell at row `i` and column `j` holds the distance between the first `i` chars of `a` and
+/// the first `j` chars of `b`. The bottom-right cell is the distance between `a` and `b`.
+///
+/// None of the shortcuts of [`edit_distance`] are taken, so this uses memory proportional to
+/// the product of the lengths.
+pub fn edit_distance_matrix(a: &str, b: &str) -> Vec<Vec<usize>> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    osa_matrix(&a, &b)
+}
+
+/// Like [`edit_distance_matrix`], of slices.
+fn osa_matrix<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Vec<usize>> {
+    let mut matrix = vec![vec![0; b.len() + 1]; a.len() + 1];
+    matrix[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        matrix[i][0] = i;
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            matrix[i][j] = cmp::min(
+                // deletion
+                matrix[i - 1][j] + 1,
+                cmp::min(
+                    // insertion
+                    matrix[i][j - 1] + 1,
+                    // substitution
+                    matrix[i - 1][j - 1] + substitution_cost,
+                ),
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                // transposition
+                matrix[i][j] = cmp::min(matrix[i][j], matrix[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    matrix
+}
+
+/// Finds how closely `prefix` matches the start of `candidate`, e.g. for autocompletion.
+///
+/// This is the smallest [`edit_distance`] between `prefix` and any prefix of `candidate`, so
+/// whatever `candidate` has after the matched part costs nothing: `"recv"` is at distance 1
+/// from `"receive_message"`, through `"rec"`. Unlike [`edit_distance_with_substrings`], the
+/// match is anchored at the start of `candidate`.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn prefix_distance(prefix: &str, candidate: &str, limit: usize) -> Option<usize> {
+    let a = prefix.chars().collect::<Chars>();
+    let b = candidate.chars().collect::<Chars>();
+
+    // Strip common prefix. There is no common suffix to strip, as the end of `candidate` is
+    // not aligned with the end of `prefix`.
+    let common = a.iter().zip(b.iter()).take_while(|(a, b)| a == b).count();
+    let (a, b) = (&a[common..], &b[common..]);
+    if a.is_empty() {
+        return Some(0);
+    }
+
+    let mut rows = Rows::from_elem(0, 3 * (b.len() + 1));
+    let (mut prev_prev, mut prev, mut current) = three_rows(&mut rows, b.len() + 1);
+    let mut prev_min = 0;
+    for i in 1..=a.len() {
+        current[0] = i;
+        fill_row(
+            &a[i - 1],
+            i.checked_sub(2).map(|k| &a[k]),
+            b,
+            prev_prev,
+            prev,
+            current,
+        );
+
+        // No cell is less than the smaller of the minimum of the row above it and the minimum
+        // of the row above that plus one. So once two consecutive rows are over the limit, all the
+        // ones below are too.
+        let min = current.iter().copied().min().unwrap_or(usize::MAX);
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // `prev` because we already rotated the buffers.
+    let distance = prev.iter().copied().min().unwrap_or(usize::MAX);
+    (distance <= limit).then_some(distance)
+}
+
+/// Like [`edit_distance`], but reads `b` from an iterator, one char at a time.
+///
+/// Only `a` is kept in memory, along with three rows of the distance table as long as `a`, so
+/// `b` can be arbitrarily long. Once the distance is known to exceed the limit, the rest of
+/// `b` isn't read at all. This does less work up front than [`edit_distance`], which can swap
+/// the strings and strip their common suffix, so it is slower when both fit in memory.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_distance_chars<I>(a: &str, b: I, limit: usize) -> Option<usize>
+where
+    I: IntoIterator<Item = char>,
+{
+    let a = a.chars().collect::<Chars>();
+    let mut b = b.into_iter().peekable();
+
+    // Strip common prefix. There is no common suffix to strip, as the end of `b` is unknown.
+    let mut common = 0;
+    while common < a.len() && b.next_if_eq(&a[common]).is_some() {
+        common += 1;
+    }
+    let a = &a[common..];
+
+    // Each char of `b` is a row of the table, and each char of `a` a column.
+    let mut rows = Rows::from_elem(0, 3 * (a.len() + 1));
+    let (mut prev_prev, mut prev, mut current) = three_rows(&mut rows, a.len() + 1);
+    let mut prev_min = 0;
+    let mut row_before = None;
+    for (i, row) in (1..).zip(b) {
+        current[0] = i;
+        fill_row(&row, row_before.as_ref(), a, prev_prev, prev, current);
+
+        // As in `prefix_distance`, once two consecutive rows are over the limit, so are all
+        // the ones below, and so is the last cell of the last row.
+        let min = current.iter().copied().min().unwrap_or(usize::MAX);
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+        row_before = Some(row);
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // `prev` because we already rotated the buffers.
+    let distance = prev[a.len()];
+    (distance <= limit).then_some(distance)
+}
+
+/// The buffer that the three rows of a table live in, which stays on the stack for short
+/// inputs.
+pub(crate) type Rows<C = usize> = SmallVec<C, { 3 * (INLINE_LEN + 1) }>;
+
+/// A cell of the table that [`dp_in`] fills in.
+///
+/// All that a cell holds above the limit is that it is above it, so with a small enough
+/// limit, the cells can be smaller than `usize`, and more of the rows fit in the cache.
+pub(crate) trait Cell: Copy + Default {
+    /// The largest value a cell can hold.
+    const MAX: usize;
+
+    /// Returns a cell holding `value`, which is at most [`Cell::MAX`].
+    fn new(value: usize) -> Self;
+
+    fn get(self) -> usize;
+}
+
+impl Cell for u8 {
+    const MAX: usize = u8::MAX as usize;
+
+    fn new(value: usize) -> Self {
+        value as u8
+    }
+
+    fn get(self) -> usize {
+        usize::from(self)
+    }
+}
+
+impl Cell for usize {
+    const MAX: usize = usize::MAX;
+
+    fn new(value: usize) -> Self {
+        value
+    }
+
+    fn get(self) -> usize {
+        self
+    }
+}
+
+/// Whether [`dp_in`] can keep its rows in cells of type `C`, for `a` and `b` of these lengths.
+pub(crate) fn cells_fit<C: Cell>(a_len: usize, b_len: usize, limit: usize) -> bool {
+    // A cell holds at most one more than the limit, which is at most the sum of the lengths.
+    cmp::min(limit, a_len + b_len) < C::MAX
+}
+
+/// Splits `rows` into the rows two above the current one, the one above it, and the current
+/// one, each `len` cells long. The first is filled with `usize::MAX`, and the second with the
+/// first row of the table, `0..len`.
+pub(crate) fn three_rows(
+    rows: &mut [usize],
+    len: usize,
+) -> (&mut [usize], &mut [usize], &mut [usize]) {
+    let (prev_prev, rest) = rows.split_at_mut(len);
+    let (prev, rest) = rest.split_at_mut(len);
+    prev_prev.fill(usize::MAX);
+    for (j, cell) in prev.iter_mut().enumerate() {
+        *cell = j;
+    }
+    (prev_prev, prev, &mut rest[..len])
+}
+
+/// Fills in a row of the optimal string alignment table, given the two rows above it.
+///
+/// `row` is the element of the row, and `row_before` the element of the row above it, if there
+/// is one. `current[0]` has to be set already, and `prev_prev` is only read if `row_before` is
+/// `Some`.
+pub(crate) fn fill_row<T: PartialEq>(
+    row: &T,
+    row_before: Option<&T>,
+    columns: &[T],
+    prev_prev: &[usize],
+    prev: &[usize],
+    current: &mut [usize],
+) {
+    for j in 1..=columns.len() {
+        // There is no cost to substitute a character with itself.
+        let substitution_cost = if *row == columns[j - 1] { 0 } else { 1 };
+
+        current[j] = cmp::min(
+            // deletion
+            prev[j] + 1,
+            cmp::min(
+                // insertion
+                current[j - 1] + 1,
+                // substitution
+                prev[j - 1] + substitution_cost,
+            ),
+        );
+
+        if let Some(row_before) = row_before {
+            if j > 1 && *row == columns[j - 2] && *row_before == columns[j - 1] {
+                // transposition
+                current[j] = cmp::min(current[j], prev_prev[j - 2] + 1);
+            }
+        }
+    }
+}
+
+/// Like [`edit_distance`], but fails instead of computing a distance that is too expensive.
+///
+/// After the common prefix and suffix of the strings are removed, computing the distance
+/// takes time proportional to the product of the remaining lengths. If that product exceeds
+/// `max_cells`, this returns [`EditDistanceError::TooLarge`] before doing any of the work, so
+/// untrusted input can be rejected up front.
+pub fn try_edit_distance(
+    a: &str,
+    b: &str,
+    limit: usize,
+    max_cells: usize,
+) -> Result<Option<usize>, EditDistanceError> {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    try_edit_distance_impl(&a, &b, limit, max_cells, unit_substitution_cost)
+}
+
+/// An error returned by [`try_edit_distance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EditDistanceError {
+    /// Computing the distance would take more cells than allowed.
+    TooLarge {
+        /// The number of cells the computation would take.
+        cells: usize,
+        /// The maximum number of cells that was allowed.
+        max_cells: usize,
+    },
+}
+
+impl fmt::Display for EditDistanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditDistanceError::TooLarge { cells, max_cells } => write!(
+                f,
+                "edit distance would take {cells} cells, more than the maximum of {max_cells}"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EditDistanceError {}
+
+/// Like [`edit_distance`], but with a custom cost for substituting one char with another.
+///
+/// `substitution_cost(x, y)` is the cost of replacing `x`, a char of `a`, with `y`, a char of
+/// `b`. That lets, e.g., typos of keys that are next to each other on a keyboard cost less
+/// than other substitutions. Insertions, deletions and transpositions still cost 1.
+///
+/// `substitution_cost(x, x)` must be 0, which is what allows equal chars at the start and end
+/// of the strings to be skipped, and the distance to be at least the difference in length.
+/// A substitution can always be replaced with a deletion and an insertion, so costs above 2
+/// make no difference.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn edit_distance_with<F>(a: &str, b: &str, limit: usize, substitution_cost: F) -> Option<usize>
+where
+    F: Fn(char, char) -> usize,
+{
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    edit_distance_with_impl(&a, &b, limit, |x: &char, y: &char| {
+        substitution_cost(*x, *y)
+    })
+}
+
+fn unit_substitution_cost<T: PartialEq>(x: &T, y: &T) -> usize {
+    // There is no cost to substitute a character with itself.
+    if x == y {
+        0
+    } else {
+        1
+    }
+}
+
+/// Finds the [edit distance] between the [NFC] normalizations of two strings.
+///
+/// Canonically equivalent strings, like `"é"` as a single char and `"é"` as `e` followed by a
+/// combining acute accent, are at distance 0.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+/// [NFC]: https://unicode.org/reports/tr15/
+#[cfg(feature = "unicode-normalization")]
+pub fn edit_distance_normalized(a: &str, b: &str, limit: usize) -> Option<usize> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let a = a.nfc().collect::<Chars>();
+    let b = b.nfc().collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// Finds the [edit distance] between the [NFKC] normalizations of two strings.
+///
+/// On top of what [`edit_distance_normalized`] does, compatibility variants of chars are
+/// replaced with their plain forms, so e.g. the ligature `"ﬁ"` equals `"fi"`, and the
+/// full-width `"Ａ"` equals `"A"`.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+/// [NFKC]: https://unicode.org/reports/tr15/
+#[cfg(feature = "unicode-normalization")]
+pub fn edit_distance_nfkc(a: &str, b: &str, limit: usize) -> Option<usize> {
+    use unicode_normalization::UnicodeNormalization;
+
+    let a = a.nfkc().collect::<Chars>();
+    let b = b.nfkc().collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// Finds the [edit distance] between two strings, ignoring their diacritics, so that e.g.
+/// `"café"` equals `"cafe"`, and `"Ångström"` equals `"Angstrom"`.
+///
+/// The strings are decomposed as in NFD, and their combining marks are removed. What is left
+/// is composed again as in NFC, as in [`edit_distance_normalized`].
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+#[cfg(feature = "unicode-normalization")]
+pub fn edit_distance_ignoring_diacritics(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = strip_diacritics(a).collect::<Chars>();
+    let b = strip_diacritics(b).collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// The chars of `s`, in NFC, without combining marks.
+#[cfg(feature = "unicode-normalization")]
+pub(crate) fn strip_diacritics(s: &str) -> impl Iterator<Item = char> + '_ {
+    use unicode_normalization::{char::is_combining_mark, UnicodeNormalization};
+
+    s.nfd().filter(|&c| !is_combining_mark(c)).nfc()
+}
+
+/// Finds the [edit distance] between the confusable [skeletons] of two strings, so that
+/// strings that look alike are close, like `"paypal"` and `"раураl"`, whose `р`, `а` and `у`
+/// are Cyrillic.
+///
+/// The skeleton replaces each char with the one it is most easily mistaken for, as listed by
+/// Unicode, after NFD normalization. Strings with equal skeletons are at distance 0, which
+/// makes this suited to spotting names that imitate others.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+/// [skeletons]: https://www.unicode.org/reports/tr39/#Confusable_Detection
+#[cfg(feature = "unicode-security")]
+pub fn skeleton_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = unicode_security::skeleton(a).collect::<Chars>();
+    let b = unicode_security::skeleton(b).collect::<Chars>();
+    edit_distance_fast(&a, &b, limit)
+}
+
+/// Scores how similar two strings are, from 0.0 for entirely different strings to 1.0 for
+/// equal ones.
+///
+/// The ratio is one minus the [`edit_distance`] divided by the length, in chars, of the longer
+/// string. Two empty strings are equal, so their ratio is 1.0.
+pub fn similarity_ratio(a: &str, b: &str) -> f64 {
+    let len = cmp::max(a.chars().count(), b.chars().count());
+    if len == 0 {
+        return 1.0;
+    }
+    let distance = edit_distance(a, b, usize::MAX).unwrap();
+    1.0 - distance as f64 / len as f64
+}
+
+/// Like [`similarity_ratio`], but returns `None` if the ratio is below `min_ratio`.
+///
+/// The edit distance is only computed up to the most that still reaches `min_ratio`, which
+/// is faster for strings that are far apart.
+pub fn similarity_ratio_at_least(a: &str, b: &str, min_ratio: f64) -> Option<f64> {
+    let len = cmp::max(a.chars().count(), b.chars().count());
+    if len == 0 {
+        return (1.0 >= min_ratio).then_some(1.0);
+    }
+    // The largest distance at which the ratio could still reach `min_ratio`. It is rounded
+    // down and one added, since `ceil` needs `std`, and the ratio checked exactly below, so that
+    // rounding errors can't exclude a match.
+    let limit = (((1.0 - min_ratio) * len as f64).max(0.0) as usize).saturating_add(1);
+    let distance = edit_distance(a, b, limit)?;
+    let ratio = 1.0 - distance as f64 / len as f64;
+    (ratio >= min_ratio).then_some(ratio)
+}
+
+/// Finds a [longest common subsequence] of two strings, i.e. the longest string whose chars
+/// appear in both `a` and `b` in the same order, though not necessarily next to each other.
+///
+/// When there are several, the one that is found walking back from the ends of the strings and
+/// skipping chars of `a` before chars of `b` is returned, so the result only depends on the
+/// inputs.
+///
+/// [longest common subsequence]: https://en.wikipedia.org/wiki/Longest_common_subsequence
+pub fn longest_common_subsequence(a: &str, b: &str) -> String {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // `table[i][j]` is the length of a longest common subsequence of `a[..i]` and `b[..j]`.
+    let mut table = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            table[i][j] = if a[i - 1] == b[j - 1] {
+                table[i - 1][j - 1] + 1
+            } else {
+                cmp::max(table[i - 1][j], table[i][j - 1])
+            };
+        }
+    }
+
+    let mut lcs = Vec::with_capacity(table[a.len()][b.len()]);
+    let (mut i, mut j) = (a.len(), b.len());
+    while i > 0 && j > 0 {
+        if a[i - 1] == b[j - 1] {
+            lcs.push(a[i - 1]);
+            i -= 1;
+            j -= 1;
+        } else if table[i - 1][j] >= table[i][j - 1] {
+            i -= 1;
+        } else {
+            j -= 1;
+        }
+    }
+    lcs.iter().rev().collect()
+}
+
+/// Finds the length, in chars, of a [longest common subsequence] of two strings.
+///
+/// This is the length of [`longest_common_subsequence`], computed in memory proportional to the
+/// length of the shorter string.
+///
+/// [longest common subsequence]: https://en.wikipedia.org/wiki/Longest_common_subsequence
+pub fn lcs_length(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    let (a, b) = if a.len() < b.len() {
+        (&b, &a)
+    } else {
+        (&a, &b)
+    };
+
+    let mut prev = SmallVec::<usize, { INLINE_LEN + 1 }>::from_elem(0, b.len() + 1);
+    let mut current = SmallVec::<usize, { INLINE_LEN + 1 }>::from_elem(0, b.len() + 1);
+    for x in a.iter() {
+        for (j, y) in b.iter().enumerate() {
+            current[j + 1] = if x == y {
+                prev[j] + 1
+            } else {
+                cmp::max(prev[j + 1], current[j])
+            };
+        }
+        mem::swap(&mut prev, &mut current);
+    }
+    prev[b.len()]
+}
+
+/// Finds a [longest common substring] of two strings, e.g. a stem that two identifiers
+/// share, like `"config"` in `"parse_config"` and `"config_parser"`.
+///
+/// Returns the length of the substring in chars, and its byte ranges in `a` and in `b`, so
+/// that `&a[range_a] == &b[range_b]`. If there are several, the one that ends first in `a`,
+/// and then in `b`, is returned. If the strings have no char in common, the length is 0 and
+/// the ranges are empty.
+///
+/// [longest common substring]: https://en.wikipedia.org/wiki/Longest_common_substring
+pub fn longest_common_substring(a: &str, b: &str) -> (usize, Range<usize>, Range<usize>) {
+    let a = a.char_indices().collect::<Vec<_>>();
+    let b = b.char_indices().collect::<Vec<_>>();
+
+    // `current[j + 1]` is the length of the longest common suffix of `a[..=i]` and `b[..=j]`.
+    let mut prev = vec![0; b.len() + 1];
+    let mut current = vec![0; b.len() + 1];
+    // The length, and the indices of the last chars in `a` and `b`, of the best match so far.
+    let mut best = (0, 0, 0);
+    for (i, (_, x)) in a.iter().enumerate() {
+        for (j, (_, y)) in b.iter().enumerate() {
+            current[j + 1] = if x == y { prev[j] + 1 } else { 0 };
+            if current[j + 1] > best.0 {
+                best = (current[j + 1], i, j);
+            }
+        }
+        mem::swap(&mut prev, &mut current);
+    }
+
+    let (len, i, j) = best;
+    if len == 0 {
+        return (0, 0..0, 0..0);
+    }
+    let range = |s: &[(usize, char)], last: usize| {
+        let (end, c) = s[last];
+        s[last + 1 - len].0..end + c.len_utf8()
+    };
+    (len, range(&a, i), range(&b, j))
+}
+
+/// Finds the edit distance between two strings when only insertions and deletions are
+/// allowed, as in a line-based diff.
+///
+/// A substitution then takes a deletion and an insertion, and a transposition two of each, so
+/// this is never less than [`edit_distance`]. Every char not in a [longest common subsequence]
+/// has to be inserted or deleted, so this is the total length minus twice [`lcs_length`].
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// [longest common subsequence]: https://en.wikipedia.org/wiki/Longest_common_subsequence
+pub fn indel_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let (n, m) = (a.chars().count(), b.chars().count());
+    // If we know the limit will be exceeded, we can return early.
+    if n.abs_diff(m) > limit {
+        return None;
+    }
+    let distance = n + m - 2 * lcs_length(a, b);
+    (distance <= limit).then_some(distance)
+}
+
+fn edit_distance_with_impl<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    substitution_cost: impl Fn(&T, &T) -> usize,
+) -> Option<usize> {
+    match try_edit_distance_impl(a, b, limit, usize::MAX, substitution_cost) {
+        Ok(distance) => distance,
+        // No product of lengths exceeds `usize::MAX`, as it saturates.
+        Err(_) => unreachable!(),
+    }
+}
+
+fn try_edit_distance_impl<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    max_cells: usize,
+    substitution_cost: impl Fn(&T, &T) -> usize,
+) -> Result<Option<usize>, EditDistanceError> {
+    let (a, b, swapped) = match trim(a, b, limit) {
+        Trim::Done(distance) => return Ok(distance),
+        Trim::Dp { a, b, swapped } => (a, b, swapped),
+    };
+
+    let cells = a.len().saturating_mul(b.len());
+    if cells > max_cells {
+        return Err(EditDistanceError::TooLarge { cells, max_cells });
+    }
+
+    Ok(dp(a, b, limit, swapped, substitution_cost))
+}
+
+/// Finds the unit cost distance, letting short inputs of elements below 256 take the
+/// bit-parallel fast path.
+fn edit_distance_fast<T: Copy + Into<u32> + PartialEq>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    edit_distance_fast_in(None, a, b, limit)
+}
+
+/// Like [`edit_distance_fast`], but fills in the table in `rows` if there are any, so that
+/// [`edit_distance_in`] takes the same path to the distance as [`edit_distance`].
+fn edit_distance_fast_in<T: Copy + Into<u32> + PartialEq>(
+    rows: Option<&mut RowBuffer>,
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    let (a, b) = match trim(a, b, limit) {
+        Trim::Done(distance) => return distance,
+        Trim::Dp { a, b, .. } => (a, b),
+    };
+
+    if b.len() <= bit_parallel::MAX_LEN && a.iter().chain(b).all(|&x| x.into() <= 0xff) {
+        return bit_parallel::edit_distance(a, b, limit);
+    }
+    // With a small limit, the band `dp` computes is cheaper than whole anti-diagonals, and so
+    // are the rows of short inputs, for which setting up the anti-diagonals isn't worth it.
+    #[cfg(feature = "simd")]
+    if b.len() > bit_parallel::MAX_LEN
+        && banded_row_len(limit) > b.len()
+        && a.len() + b.len() < u32::MAX as usize
+    {
+        return match rows {
+            Some(rows) => rows.anti_diagonals(a, b, limit),
+            None => simd::edit_distance(a, b, limit),
+        };
+    }
+    match rows {
+        Some(rows) => rows.dp(a, b, limit),
+        None => dp(a, b, limit, false, unit_substitution_cost),
+    }
+}
+
+enum Trim<'t, T> {
+    /// The distance is known without filling in the table.
+    Done(Option<usize>),
+    /// The table has to be filled in for `a` and `b`, where `a` is the longer one. `swapped` is
+    /// whether they are in the opposite order of the caller's.
+    Dp {
+        a: &'t [T],
+        b: &'t [T],
+        swapped: bool,
+    },
+}
+
+/// Takes every shortcut there is to the distance between `a` and `b`, and if none of them
+/// gets there, returns what is left for `dp` to do.
+fn trim<'t, T: PartialEq>(a: &'t [T], b: &'t [T], limit: usize) -> Trim<'t, T> {
+    let (mut a, mut b) = (a, b);
 
     // Ensure that `b` is the shorter string, minimizing memory use.
-    if a.len() < b.len() {
+    let swapped = a.len() < b.len();
+    if swapped {
         mem::swap(&mut a, &mut b);
     }
 
     let min_dist = a.len() - b.len();
     // If we know the limit will be exceeded, we can return early.
     if min_dist > limit {
-        return None;
+        return Trim::Done(None);
     }
 
-    // Strip common prefix.
+    // Strip common prefix. This can't hide a cheaper transposition: one that straddles the
+    // boundary would swap the last stripped char `x` with the next char of `a`, and of `b`,
+    // which then both have to be `x` as well, making the transposition a plain match.
     while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_first().zip(a.split_first()) {
         if a_char != b_char {
             break;
@@ -38,7 +1174,7 @@ pub fn edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
         a = a_rest;
         b = b_rest;
     }
-    // Strip common suffix.
+    // Strip common suffix, which is safe for the same reason.
     while let Some(((b_char, b_rest), (a_char, a_rest))) = b.split_last().zip(a.split_last()) {
         if a_char != b_char {
             break;
@@ -50,70 +1186,553 @@ pub fn edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
     // If either string is empty, the distance is the length of the other.
     // We know that `b` is the shorter string, so we don't need to check `a`.
     if b.is_empty() {
-        return Some(min_dist);
+        return Trim::Done(Some(min_dist));
     }
 
-    let mut prev_prev = vec![usize::MAX; b.len() + 1];
-    let mut prev = (0..=b.len()).collect::<Vec<_>>();
-    let mut current = vec![0; b.len() + 1];
+    Trim::Dp { a, b, swapped }
+}
+
+/// Fills in the table for what [`trim`] left over.
+fn dp<T>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    swapped: bool,
+    substitution_cost: impl Fn(&T, &T) -> usize,
+) -> Option<usize>
+where
+    T: PartialEq,
+{
+    let len = dp_len(b, limit);
+    if cells_fit::<u8>(a.len(), b.len(), limit) {
+        let mut rows = Rows::<u8>::from_elem(0, len);
+        return dp_in(&mut rows, a, b, limit, swapped, substitution_cost);
+    }
+    let mut rows = Rows::<usize>::from_elem(0, len);
+    dp_in(&mut rows, a, b, limit, swapped, substitution_cost)
+}
+
+/// How many cells [`dp_in`] needs for the three rows it keeps.
+fn dp_len<T>(b: &[T], limit: usize) -> usize {
+    3 * cmp::min(b.len() + 1, banded_row_len(limit))
+}
+
+/// Like [`dp`], but keeps the rows in `rows`, which is at least [`dp_len`] long, in cells
+/// that [fit](cells_fit).
+fn dp_in<T, C: Cell>(
+    rows: &mut [C],
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    swapped: bool,
+    substitution_cost: impl Fn(&T, &T) -> usize,
+) -> Option<usize>
+where
+    T: PartialEq,
+{
+    // For a small limit, the band of each row that is computed is much shorter than the row.
+    if banded_row_len(limit) < b.len() + 1 {
+        return banded_dp(rows, a, b, limit, swapped, substitution_cost);
+    }
+
+    // A cell further than `limit` from the diagonal holds a distance greater than `limit`, so
+    // only the band `i - limit..=i + limit` of each row has to be computed (Ukkonen's cut-off).
+    // The cells just outside the band are set to `limit + 1`, which is all the band needs to
+    // know about them. Deleting all of `a` and inserting all of `b` bounds the distance, so
+    // clamping the limit to that keeps `limit + 1` from overflowing.
+    let limit = cmp::min(limit, a.len() + b.len());
+    let outside_band = limit + 1;
+    // Any cell above the limit is just as far over it as `outside_band`, which is what keeps
+    // them within the cell type.
+    let cell = |value: usize| C::new(cmp::min(value, outside_band));
+
+    let len = b.len() + 1;
+    let (mut prev_prev, rest) = rows.split_at_mut(len);
+    let (mut prev, rest) = rest.split_at_mut(len);
+    let mut current = &mut rest[..len];
+    prev_prev.fill(cell(outside_band));
+    for (j, value) in prev.iter_mut().enumerate() {
+        *value = cell(j);
+    }
+
+    // The smallest cell of the row above, for giving up early.
+    let mut prev_min = 0;
 
     // row by row
     for i in 1..=a.len() {
-        current[0] = i;
+        current[0] = cell(i);
         let a_idx = i - 1;
+        let start = cmp::max(1, i.saturating_sub(limit));
+        let end = cmp::min(b.len(), i + limit);
+        if start > 1 {
+            current[start - 1] = cell(outside_band);
+        }
+        let mut min = current[start - 1].get();
 
         // column by column
-        for j in 1..=b.len() {
+        for j in start..=end {
             let b_idx = j - 1;
 
-            // There is no cost to substitute a character with itself.
-            let substitution_cost = if a[a_idx] == b[b_idx] { 0 } else { 1 };
+            // Keep the arguments in the caller's order. Costs are capped at 2, the cost of a
+            // deletion and an insertion, which also keeps the sum below from overflowing.
+            let substitution_cost = cmp::min(
+                2,
+                if swapped {
+                    substitution_cost(&b[b_idx], &a[a_idx])
+                } else {
+                    substitution_cost(&a[a_idx], &b[b_idx])
+                },
+            );
 
-            current[j] = cmp::min(
+            let mut distance = cmp::min(
                 // deletion
-                prev[j] + 1,
+                prev[j].get() + 1,
                 cmp::min(
                     // insertion
-                    current[j - 1] + 1,
+                    current[j - 1].get() + 1,
                     // substitution
-                    prev[j - 1] + substitution_cost,
+                    prev[j - 1].get() + substitution_cost,
                 ),
             );
 
             if (i > 1) && (j > 1) && (a[a_idx] == b[b_idx - 1]) && (a[a_idx - 1] == b[b_idx]) {
                 // transposition
-                current[j] = cmp::min(current[j], prev_prev[j - 2] + 1);
+                distance = cmp::min(distance, prev_prev[j - 2].get() + 1);
             }
+            current[j] = cell(distance);
+            min = cmp::min(min, distance);
+        }
+        if end < b.len() {
+            current[end + 1] = cell(outside_band);
+        }
+
+        // Every cell comes from the row above, or from two rows up by a transposition, which
+        // costs 1. So once two rows in a row are over the limit, so are all the ones below.
+        if min > limit && prev_min > limit {
+            return None;
         }
+        prev_min = min;
 
         // Rotate the buffers, reusing the memory.
         [prev_prev, prev, current] = [prev, current, prev_prev];
     }
 
     // `prev` because we already rotated the buffers.
-    let distance = prev[b.len()];
+    let distance = prev[b.len()].get();
+    (distance <= limit).then_some(distance)
+}
+
+/// How many cells of each row [`banded_dp`] stores: the band, and a cell on either side of it.
+fn banded_row_len(limit: usize) -> usize {
+    limit.saturating_mul(2).saturating_add(3)
+}
+
+/// Like [`dp_in`], but only stores the band of each row that is computed, so it takes memory
+/// proportional to `limit` instead of to the length of `b`. `limit` must be below `b.len()`.
+fn banded_dp<T, C: Cell>(
+    rows: &mut [C],
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    swapped: bool,
+    substitution_cost: impl Fn(&T, &T) -> usize,
+) -> Option<usize>
+where
+    T: PartialEq,
+{
+    // Row `i` is stored from column `i - limit - 1` to `i + limit + 1`, so column `j` is at
+    // index `j + limit + 1 - i`. Going up a row shifts the band one column to the left, which
+    // is why the cell above `j` is at the next index, and the one two rows up at the same.
+    let len = banded_row_len(limit);
+    let outside_band = limit + 1;
+    // As in `dp_in`.
+    let cell = |value: usize| C::new(cmp::min(value, outside_band));
+    let (mut prev_prev, rest) = rows.split_at_mut(len);
+    let (mut prev, rest) = rest.split_at_mut(len);
+    let mut current = &mut rest[..len];
+    prev_prev.fill(cell(outside_band));
+    prev.fill(cell(outside_band));
+    for (j, value) in prev[limit + 1..].iter_mut().enumerate() {
+        *value = cell(j);
+    }
+
+    // As in `dp_in`.
+    let mut prev_min = 0;
+    for i in 1..=a.len() {
+        current.fill(cell(outside_band));
+        if i <= limit + 1 {
+            current[limit + 1 - i] = cell(i);
+        }
+        let mut min = cmp::min(i, outside_band);
+        let a_idx = i - 1;
+        let start = cmp::max(1, i.saturating_sub(limit));
+        let end = cmp::min(b.len(), i + limit);
+
+        for j in start..=end {
+            let b_idx = j - 1;
+            let k = j + limit + 1 - i;
+
+            // As in `dp`.
+            let substitution_cost = cmp::min(
+                2,
+                if swapped {
+                    substitution_cost(&b[b_idx], &a[a_idx])
+                } else {
+                    substitution_cost(&a[a_idx], &b[b_idx])
+                },
+            );
+
+            let mut distance = cmp::min(
+                // deletion
+                prev[k + 1].get() + 1,
+                cmp::min(
+                    // insertion
+                    current[k - 1].get() + 1,
+                    // substitution
+                    prev[k].get() + substitution_cost,
+                ),
+            );
+
+            if (i > 1) && (j > 1) && (a[a_idx] == b[b_idx - 1]) && (a[a_idx - 1] == b[b_idx]) {
+                // transposition
+                distance = cmp::min(distance, prev_prev[k].get() + 1);
+            }
+            current[k] = cell(distance);
+            min = cmp::min(min, distance);
+        }
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // `prev` because we already rotated the buffers. `trim` made sure that `b` is at most
+    // `limit` shorter than `a`, so the last column is inside the band.
+    let distance = prev[b.len() + limit + 1 - a.len()].get();
     (distance <= limit).then_some(distance)
 }
 
+/// Finds the candidate that best matches `lookup`, e.g. for a "did you mean" suggestion.
+///
+/// In order of priority, this returns:
+/// 1. the first candidate that equals `lookup`, contains it, or is contained in it, ignoring
+///    case;
+/// 2. the candidate with the smallest [`edit_distance`] to `lookup`, if it is within `dist`;
+/// 3. the last candidate made of the same `_`-separated words as `lookup`, in any order.
+///
+/// An empty candidate is never contained in `lookup` in that sense, and an empty `lookup` only
+/// matches an empty candidate.
+///
+/// If `dist` is `None`, [`default_match_distance`] is used. `Some(0)` is not the same: it
+/// turns off the second kind of match, as only a candidate equal to `lookup` is within 0 edits
+/// of it, and that is a match of the first kind already.
+///
+/// If several candidates share the smallest edit distance, the lexicographically smallest of
+/// them is returned, so the result doesn't depend on the order of `candidates`.
 pub fn find_best_match_for_name<'a>(
     candidates: &[&'a str],
     lookup: &'a str,
     dist: Option<usize>,
 ) -> Option<&'a str> {
-    find_best_match_for_name_impl(false, candidates, lookup, dist)
+    find_best_match_for_name_with(candidates, lookup, &options(dist))
+}
+
+/// Like [`find_best_match_for_name`], but also returns the [`edit_distance`] between `lookup`
+/// and the best match, e.g. to apply a threshold of one's own or to say how close it is.
+///
+/// The distance is returned whichever kind of match the candidate is, so it may be over `dist`
+/// for a match of the first or third kind.
+///
+/// ```
+/// use rustc_edit_distance::find_best_match_with_distance;
+///
+/// let candidates = ["length", "width", "Height"];
+/// assert_eq!(find_best_match_with_distance(&candidates, "lenght", None), Some(("length", 1)));
+/// assert_eq!(find_best_match_with_distance(&candidates, "height", None), Some(("Height", 1)));
+/// ```
+pub fn find_best_match_with_distance<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<(&'a str, usize)> {
+    let best = find_best_match_for_name_with(candidates, lookup, &options(dist))?;
+    Some((best, edit_distance(lookup, best, usize::MAX).unwrap()))
+}
+
+/// Like [`find_best_match_for_name`], but returns the index of the best match in
+/// `candidates`, e.g. to map it back to the item it is the name of.
+///
+/// This is [`find_best_match_for_name_owned`] for candidates that are `&str` already. See
+/// [`find_best_match_indices`] for the indices of the best few matches.
+pub fn find_best_match_index(
+    candidates: &[&str],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<usize> {
+    find_best_match_for_name_owned(candidates, lookup, dist)
+}
+
+/// Like [`find_best_match_for_name`], but takes candidates of any string type, e.g.
+/// `String` or `Cow<str>`, and returns the index of the best match.
+///
+/// Returning an index rather than a `&str` means the result doesn't borrow `candidates`.
+pub fn find_best_match_for_name_owned<S: AsRef<str>>(
+    candidates: &[S],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<usize> {
+    let best = find_best_match_for_name_iter_impl(
+        candidates.iter().map(AsRef::as_ref),
+        lookup,
+        &options(dist),
+    )?;
+    // `best` is one of the candidates, not a copy of it, so its address tells which one.
+    candidates
+        .iter()
+        .position(|c| core::ptr::eq(c.as_ref(), best))
+}
+
+/// Like [`find_best_match_for_name`], but takes the candidates from an iterator of any string
+/// type, e.g. the keys of a `HashMap<String, _>` or a `Vec<Rc<str>>`, and returns the best
+/// match itself.
+///
+/// The candidates are collected once, so unlike [`find_best_match_for_name_iter`], memory use
+/// grows with their number, but there's no `Vec<&str>` to build first.
+///
+/// ```
+/// use std::collections::HashMap;
+///
+/// use rustc_edit_distance::find_best_match_for_name_generic;
+///
+/// let symbols = HashMap::from([(String::from("push_back"), 1), (String::from("pop_front"), 2)]);
+/// let best = find_best_match_for_name_generic(symbols.keys(), "pop_frnt", None).unwrap();
+/// assert_eq!(symbols[best], 2);
+/// ```
+pub fn find_best_match_for_name_generic<I, C>(
+    candidates: I,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<C>
+where
+    I: IntoIterator<Item = C>,
+    C: AsRef<str>,
+{
+    let mut candidates = candidates.into_iter().collect::<Vec<_>>();
+    let best = find_best_match_for_name_owned(&candidates, lookup, dist)?;
+    Some(candidates.swap_remove(best))
+}
+
+/// Like [`find_best_match_for_name`], but each candidate comes with its own `dist`.
+///
+/// That lets some candidates, e.g. public names, require a closer match than others. Of the
+/// candidates within their own `dist` of `lookup`, the one with the smallest edit distance is
+/// returned, and the lexicographically smallest of them if there are several.
+pub fn find_best_match_for_name_with_limits<'a>(
+    candidates: &[(&'a str, usize)],
+    lookup: &str,
+) -> Option<&'a str> {
+    if lookup.is_empty() {
+        return candidates.iter().map(|&(c, _)| c).find(|c| c.is_empty());
+    }
+
+    let lookup_folded = case_fold(lookup);
+    let lookup_sorted_by_words = sort_by_words(lookup, &['_']);
+
+    // Every candidate has its own limit, so unlike `find_best_match_for_name_iter_impl`, the
+    // limit isn't narrowed as closer candidates are found.
+    let mut best = None;
+    let mut sorted_words_match = None;
+    for &(c, dist) in candidates {
+        if is_folded_match(&case_fold(c), &lookup_folded) {
+            return Some(c);
+        }
+
+        if let Some(d) = edit_distance(lookup, c, dist) {
+            if best.map_or(true, |best| (d, c) < best) {
+                best = Some((d, c));
+            }
+        }
+
+        if best.is_none() && sort_by_words(c, &['_']) == lookup_sorted_by_words {
+            sorted_words_match = Some(c);
+        }
+    }
+
+    best.map(|(_, c)| c).or(sorted_words_match)
+}
+
+/// Like [`find_best_match_for_name`], but each candidate comes with a weight, e.g. how often
+/// the word is used, and ties are broken in favor of the heaviest candidate.
+///
+/// This is what a spelling corrector wants: of the words at the smallest edit distance, the
+/// most common one is the likeliest to have been meant. The weight also picks between
+/// candidates of the first and third kind, instead of their position in `candidates`, and
+/// candidates of the same weight are broken lexicographically.
+///
+/// ```
+/// use rustc_edit_distance::find_best_match_for_name_weighted;
+///
+/// let words = [("than", 50), ("then", 900), ("thin", 120)];
+/// assert_eq!(find_best_match_for_name_weighted(&words, "thxn", None), Some("then"));
+/// ```
+pub fn find_best_match_for_name_weighted<'a>(
+    candidates: &[(&'a str, u64)],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<&'a str> {
+    // The best candidate has the smallest key.
+    let key = |(c, weight): (&'a str, u64)| (cmp::Reverse(weight), c);
+    if lookup.is_empty() {
+        return candidates
+            .iter()
+            .copied()
+            .filter(|(c, _)| c.is_empty())
+            .map(key)
+            .min()
+            .map(|(_, c)| c);
+    }
+
+    let dist = dist.unwrap_or_else(|| default_match_distance(lookup));
+    let lookup_folded = case_fold(lookup);
+    let lookup_sorted_by_words = sort_by_words(lookup, &['_']);
+
+    let mut folded_match = None;
+    let mut best = None;
+    let mut sorted_words_match = None;
+    for &(c, weight) in candidates {
+        let k = key((c, weight));
+        if is_folded_match(&case_fold(c), &lookup_folded) {
+            keep_min(&mut folded_match, k);
+        } else if let Some(d) = edit_distance(lookup, c, dist) {
+            keep_min(&mut best, (d, k));
+        } else if sort_by_words(c, &['_']) == lookup_sorted_by_words {
+            keep_min(&mut sorted_words_match, k);
+        }
+    }
+
+    fn keep_min<T: Ord>(min: &mut Option<T>, new: T) {
+        if min.as_ref().map_or(true, |min| new < *min) {
+            *min = Some(new);
+        }
+    }
+
+    folded_match
+        .or(best.map(|(_, k)| k))
+        .or(sorted_words_match)
+        .map(|(_, c)| c)
+}
+
+/// Like [`find_best_match_for_name`], but with the threshold given as a minimum
+/// [`similarity_ratio`] to `lookup` instead of as an edit distance.
+///
+/// That allows more edits for long candidates than for short ones. Of the candidates with a
+/// ratio of at least `min_ratio`, the one with the highest ratio is returned, and the
+/// lexicographically smallest of them if there are several. Candidates that match `lookup`
+/// ignoring case, or are made of the same words, are still preferred in the same way.
+pub fn find_best_match_for_name_by_ratio<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    min_ratio: f64,
+) -> Option<&'a str> {
+    find_best_match_by_similarity(
+        candidates.iter().copied(),
+        lookup,
+        &MatchOptions::new(),
+        |lookup, c| similarity_ratio_at_least(lookup, c, min_ratio),
+    )
+}
+
+/// Like [`find_best_match_for_name`], but ranks candidates by
+/// [`edit_distance_with_substrings`] rather than by [`edit_distance`].
+///
+/// This favours candidates that `lookup` is an abbreviation of, so e.g. `"cptr"` matches
+/// `"capture"`, which is too far from it by edit distance alone. When several candidates share
+/// the best score, the one with the smallest [`edit_distance`] to `lookup` is returned.
+pub fn find_best_match_for_name_with_substrings<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<&'a str> {
+    find_best_match_for_name_with(candidates, lookup, &options(dist).use_substrings(true))
+}
+
+/// Like [`find_best_match_for_name`], but with the distance of the candidates measured by
+/// `metric`, e.g. a domain-specific scorer, instead of [`edit_distance`].
+///
+/// Everything else is as in `find_best_match_for_name`: the candidates that match ignoring
+/// case come first, the candidates further than `dist` by `metric` are skipped, ties go to
+/// the lexicographically smallest candidate, and the sorted words are the fallback.
+pub fn find_best_match_for_name_with_metric<'a, M: Metric + ?Sized>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+    metric: &M,
+) -> Option<&'a str> {
+    options(dist).best_by(candidates, lookup, metric)
+}
+
+/// Like [`find_best_match_for_name`], but with the options set in `options`.
+pub fn find_best_match_for_name_with<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    options: &MatchOptions,
+) -> Option<&'a str> {
+    if candidates.is_empty() {
+        return None;
+    }
+    find_best_match_for_name_iter_impl(candidates.iter().copied(), lookup, options)
 }
 
+/// Scores how well `a` and `b` match when one of them may be a substring of the other.
+///
+/// The score starts as the [`edit_distance`] minus the difference in length, which is 0 when
+/// the shorter string only needs insertions to become the longer one, e.g. when it is a
+/// substring of it. If neither string is more than twice as long as the other, such a match
+/// scores 1, and any other score has half the difference in length, rounded up, added back.
+/// Otherwise, the whole difference in length is added back.
+///
+/// So the score jumps where one string becomes more than twice as long as the other: `"abc"`
+/// scores 1 against `"abcxyz"`, but 4 against `"abcxyzw"`. Past that point, a string that
+/// contains `a` can score worse than one that doesn't, but is closer in length: `"ab"` scores
+/// 3 against `"abxyz"`, but 2 against `"xbxy"`. This is how rustc scores substrings, and so
+/// tells apart a near match from what just happens to contain the lookup somewhere.
+///
+/// Returns `None` if the score exceeds the limit.
 pub fn edit_distance_with_substrings(a: &str, b: &str, limit: usize) -> Option<usize> {
-    let n = a.chars().count();
-    let m = b.chars().count();
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    substring_score(&a, &b, limit, edit_distance_fast)
+}
+
+/// Like [`edit_distance_with_substrings`], but for slices of anything that can be compared.
+pub fn edit_distance_with_substrings_slices<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    substring_score(a, b, limit, edit_distance_slices)
+}
+
+fn substring_score<T>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    edit_distance: impl Fn(&[T], &[T], usize) -> Option<usize>,
+) -> Option<usize> {
+    let n = a.len();
+    let m = b.len();
 
     // Check one isn't less than half the length of the other. If this is true then there is a
     // big difference in length.
     let big_len_diff = (n * 2) < m || (m * 2) < n;
     let len_diff = m.abs_diff(n);
-    let distance = edit_distance(a, b, limit + len_diff)?;
+    let distance = edit_distance(a, b, limit.saturating_add(len_diff))?;
 
-    // This is the crux, subtracting length difference means exact substring matches will now be 0
+    // This is the crux, subtracting length difference means exact substring matches will now be 0.
+    // Every edit changes the length by at most one, so the distance is never below `len_diff`.
     let score = distance - len_diff;
 
     // If the score is 0 but the words have different lengths then it's a substring match not a full
@@ -131,41 +1750,254 @@ pub fn edit_distance_with_substrings(a: &str, b: &str, limit: usize) -> Option<u
     (score <= limit).then_some(score)
 }
 
+/// Like [`edit_distance_with_substrings`], but also returns where `a` aligns within `b`.
+///
+/// The span is a half-open range of char indices into `b`: the window of `b` that `a` is
+/// closest to when the parts of `b` before and after the window are free to skip. If `a` is
+/// a substring of `b`, the span covers the first occurrence of it exactly.
+///
+/// Returns `None` if the score exceeds the limit.
+pub fn edit_distance_with_substrings_span(
+    a: &str,
+    b: &str,
+    limit: usize,
+) -> Option<(usize, (usize, usize))> {
+    let score = edit_distance_with_substrings(a, b, limit)?;
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+
+    // The table of distances between prefixes of `a` and substrings of `b`, alongside the
+    // column each of those substrings starts at. Starting anywhere in `b` is free, so the first
+    // row is all zeros.
+    let mut prev_prev = vec![(usize::MAX, 0); b.len() + 1];
+    let mut prev = (0..=b.len()).map(|j| (0, j)).collect::<Vec<_>>();
+    let mut current = vec![(0, 0); b.len() + 1];
+    for i in 1..=a.len() {
+        current[0] = (i, 0);
+        for j in 1..=b.len() {
+            let substitution_cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            // Comparing `(distance, start)` pairs makes ties prefer the window that starts
+            // first, which keeps matched chars at the start of the window inside it.
+            let (distance, start) = prev[j - 1];
+            let mut cell = (distance + substitution_cost, start);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                let (distance, start) = prev_prev[j - 2];
+                cell = cmp::min(cell, (distance + 1, start));
+            }
+            for (distance, start) in [prev[j], current[j - 1]] {
+                cell = cmp::min(cell, (distance + 1, start));
+            }
+            current[j] = cell;
+        }
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // Ending anywhere in `b` is free too, so the best window ends at the smallest distance in
+    // the last row. `min_by_key` keeps the first of equal cells, which is the leftmost window.
+    let (end, &(_, start)) = prev
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, (distance, _))| *distance)?;
+    Some((score, (start, end)))
+}
+
+#[doc(hidden)]
+#[deprecated(
+    since = "0.1.1",
+    note = "use `find_best_match_for_name` or `find_best_match_for_name_with_substrings`"
+)]
 pub fn find_best_match_for_name_impl<'a>(
     use_substring_score: bool,
     candidates: &[&'a str],
     lookup: &'a str,
     dist: Option<usize>,
 ) -> Option<&'a str> {
-    let lookup_uppercase = lookup.to_uppercase();
+    find_best_match_for_name_with(
+        candidates,
+        lookup,
+        &options(dist).use_substrings(use_substring_score),
+    )
+}
+
+/// Like [`find_best_match_for_name`], but splits names into words at any of `separators`
+/// when comparing them by their sorted words, instead of only at `'_'`.
+///
+/// For example with `'-'` as a separator, `"log-level"` matches `"level-log"`.
+pub fn find_best_match_for_name_with_separators<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+    separators: &[char],
+) -> Option<&'a str> {
+    find_best_match_for_name_with(candidates, lookup, &options(dist).separators(separators))
+}
 
-    // Priority of matches:
-    // 1. Exact case insensitive match or Substring insensitive match
-    // 2. Edit distance match
-    // 3. Sorted word match
-    if let Some(c) = candidates.iter().find(|c| {
-        c.to_uppercase() == lookup_uppercase
-            || c.to_uppercase().contains(&lookup_uppercase)
-            || lookup_uppercase.contains(&c.to_uppercase())
-    }) {
-        return Some(*c);
+/// Scores every candidate by its [`edit_distance`] to `lookup` and sorts them, closest first.
+///
+/// The sort is stable, so candidates at the same distance keep their relative order.
+pub fn rank_candidates<'a>(candidates: &[&'a str], lookup: &str) -> Vec<(&'a str, usize)> {
+    let mut ranked = candidates
+        .iter()
+        .map(|&c| (c, edit_distance(lookup, c, usize::MAX).unwrap()))
+        .collect::<Vec<_>>();
+    ranked.sort_by_key(|&(_, distance)| distance);
+    ranked
+}
+
+/// Like [`find_best_match_for_name`], but takes the candidates from an iterator.
+///
+/// The iterator is consumed exactly once and the candidates are never collected, so
+/// memory use does not grow with the number of candidates.
+pub fn find_best_match_for_name_iter<'a, I>(
+    candidates: I,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    find_best_match_for_name_iter_impl(candidates, lookup, &options(dist))
+}
+
+/// The default options, except for the distance.
+fn options(dist: Option<usize>) -> MatchOptions {
+    let options = MatchOptions::new();
+    match dist {
+        Some(dist) => options.max_distance(dist),
+        None => options,
+    }
+}
+
+fn find_best_match_for_name_iter_impl<'a, I>(
+    candidates: I,
+    lookup: &str,
+    options: &MatchOptions,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let metric = |a: &str, b: &str, limit| match_distance(a, b, limit, options);
+    find_best_match_by_metric(candidates, lookup, options, &metric)
+}
+
+/// Like `find_best_match_for_name_iter_impl`, but the distance of the second kind of match is
+/// that of `metric`, for [`Scorer::EditDistance`].
+fn find_best_match_by_metric<'a, I, M>(
+    candidates: I,
+    lookup: &str,
+    options: &MatchOptions,
+    metric: &M,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+    M: Metric + ?Sized,
+{
+    // An empty lookup says nothing about what was meant, so only an empty candidate matches it.
+    if lookup.is_empty() {
+        return candidates.into_iter().find(|c| c.is_empty());
     }
 
-    // `fn edit_distance()` use `chars()` to calculate edit distance, so we must
-    // also use `chars()` (and not `str::len()`) to calculate length here.
-    let lookup_len = lookup.chars().count();
+    let lookup_normalized = options.normalize(lookup);
+    let lookup = &*lookup_normalized;
+
+    match options.scorer {
+        Scorer::EditDistance => {}
+        Scorer::JaroWinkler { min_similarity } => {
+            return find_best_match_by_similarity(candidates, lookup, options, |a, b| {
+                Some(jaro_winkler(a, b)).filter(|&similarity| similarity >= min_similarity)
+            });
+        }
+        Scorer::Dice { min_similarity } => {
+            return find_best_match_by_similarity(candidates, lookup, options, |a, b| {
+                Some(dice_coefficient(a, b)).filter(|&similarity| similarity >= min_similarity)
+            });
+        }
+    }
+
+    let use_substring_score = options.use_substrings;
+    let lookup_folded = case_fold(lookup);
+    let lookup_sorted_by_words = options.sorted_words(lookup);
 
-    let mut dist = dist.unwrap_or_else(|| cmp::max(lookup_len, 3) / 3);
+    let mut dist = options.limit(lookup);
     let mut best = None;
     // store the candidates with the same distance, only for `use_substring_score` current.
     let mut next_candidates = vec![];
+    // The last candidate whose sorted words equal the lookup's, used as a fallback.
+    let mut sorted_words_match = None;
+    // The phonetic codes of the lookup, and the closest candidate with one of them.
+    let lookup_codes = options
+        .phonetic
+        .map(|phonetic| phonetic.encode(lookup))
+        .filter(|codes| !codes.is_empty());
+    let mut phonetic_match: Option<(usize, &str)> = None;
+    // The best score discounted for the prefix in common, with `MatchOptions::prefix_weight`.
+    let mut prefix_match: Option<(f64, &str)> = None;
+    // The first candidate that matches ignoring case.
+    let mut folded_match = None;
+    let folded_rank = options.rank(MatchPhase::CaseInsensitive);
+    let distance_rank = options.rank(MatchPhase::EditDistance);
+    let words_rank = options.rank(MatchPhase::SortedWords);
+    let abbreviation_rank = options.rank(MatchPhase::Abbreviation);
+    let before_distance = |rank: Option<usize>| rank.is_some() && rank < distance_rank;
+    let words_first = before_distance(words_rank);
+    // Whether an exact match by distance may still lose to a later candidate.
+    let exact_may_lose = words_first || before_distance(abbreviation_rank);
+    // The best abbreviation score, with `MatchPhase::Abbreviation`.
+    let mut abbreviation_match: Option<(usize, &str)> = None;
     for c in candidates {
-        match if use_substring_score {
-            edit_distance_with_substrings(lookup, c, dist)
+        // Priority of matches, by default:
+        // 1. Exact case insensitive match or Substring insensitive match
+        // 2. Phonetic match, if enabled
+        // 3. Edit distance match
+        // 4. Sorted word match
+        //
+        // The phonetic match is part of the edit distance phase. When the first kind of match
+        // is the first phase, it beats everything else, so the first such candidate can be
+        // returned right away, without looking at the rest of the candidates.
+        let c_normalized = options.normalize(c);
+        let c_folded = case_fold(&c_normalized);
+        if folded_rank.is_some() && options.is_folded_match(&c_folded, &lookup_folded) {
+            if folded_rank == Some(0) && options.tie_break.is_none() {
+                return Some(c);
+            }
+            if options.replaces(lookup, c, folded_match, false) {
+                folded_match = Some(c);
+            }
+        }
+        if abbreviation_rank.is_some() {
+            if let Some(score) = abbreviation_score(lookup, &c_normalized) {
+                if abbreviation_match
+                    .map_or(true, |best| options.is_better(lookup, (score, c), best))
+                {
+                    abbreviation_match = Some((score, c));
+                }
+            }
+        }
+        // Folding both strings first is what `edit_distance_ci` does too.
+        let (a, b) = if options.case_insensitive {
+            (&lookup_folded[..], &c_folded[..])
         } else {
-            edit_distance(lookup, c, dist)
-        } {
-            Some(0) => return Some(*c),
+            (lookup, &c_normalized[..])
+        };
+        if let (Some(phonetic), Some(lookup_codes)) = (options.phonetic, &lookup_codes) {
+            if distance_rank.is_some()
+                && phonetic
+                    .encode(&c_normalized)
+                    .iter()
+                    .any(|code| lookup_codes.contains(code))
+            {
+                let d = edit_distance(a, b, cmp::max(a.len(), b.len())).unwrap();
+                if phonetic_match.map_or(true, |best| options.is_better(lookup, (d, c), best)) {
+                    phonetic_match = Some((d, c));
+                }
+            }
+        }
+
+        let d = distance_rank.and_then(|_| metric.distance(a, b, dist));
+        match d {
+            Some(0) if !exact_may_lose && options.tie_break.is_none() => return Some(c),
             Some(d) => {
                 if use_substring_score {
                     if d < dist {
@@ -175,43 +2007,256 @@ pub fn find_best_match_for_name_impl<'a>(
                         // `d == dist` here, we need to store the candidates with the same distance
                         // so we won't decrease the distance in the next loop.
                     }
-                    next_candidates.push(*c);
-                } else {
-                    dist = d - 1;
+                    next_candidates.push(c);
+                    best = Some(c);
+                } else if let Some(weight) = options.prefix_weight {
+                    // Unlike a distance, a discounted score doesn't tell how close the next
+                    // candidate has to be to beat it, so `dist` stays as it is.
+                    let prefix = a
+                        .chars()
+                        .zip(b.chars())
+                        .take_while(|(x, y)| x == y)
+                        .take(4)
+                        .count();
+                    let score = d as f64 * (1.0 - prefix as f64 * weight);
+                    if prefix_match.map_or(true, |best| options.is_better(lookup, (score, c), best))
+                    {
+                        prefix_match = Some((score, c));
+                        best = Some(c);
+                    }
+                } else if d < dist || best.map_or(true, |best| options.breaks_tie(lookup, c, best))
+                {
+                    // Candidates at the current best distance are still considered, and by
+                    // default the lexicographically smallest one wins, so that the result
+                    // doesn't depend on the order of the candidates.
+                    dist = d;
+                    best = Some(c);
                 }
-                best = Some(*c);
             }
             None => {}
         }
+
+        // The sorted word match is only needed if there is no better match. The substring
+        // tie-break below may still discard `best`, so keep tracking it in that mode.
+        if words_rank.is_some()
+            && (best.is_none() || use_substring_score || words_first)
+            && options.sorted_words(&c_normalized) == lookup_sorted_by_words
+            && options.replaces(lookup, c, sorted_words_match, true)
+        {
+            sorted_words_match = Some(c);
+        }
     }
 
-    // We have a tie among several candidates, try to select the best among them ignoring substrings.
-    // For example, the candidates list `force_capture`, `capture`, and user inputted `forced_capture`,
-    // we select `force_capture` with a extra round of edit distance calculation.
-    if next_candidates.len() > 1 {
+    if let Some((_, c)) = phonetic_match {
+        best = Some(c);
+    } else if next_candidates.len() > 1 {
+        // We have a tie among several candidates, try to select the best among them ignoring
+        // substrings. For example, the candidates list `force_capture`, `capture`, and user
+        // inputted `forced_capture`, we select `force_capture` with a extra round of edit
+        // distance calculation.
         debug_assert!(use_substring_score);
-        best = find_best_match_for_name_impl(false, &next_candidates, lookup, Some(lookup.len()));
+        best = find_best_match_for_name_iter_impl(
+            next_candidates.iter().copied(),
+            lookup,
+            &options
+                .clone()
+                .use_substrings(false)
+                .max_distance(lookup.len())
+                .phases(&[MatchPhase::EditDistance, MatchPhase::SortedWords]),
+        );
     }
-    if best.is_some() {
-        return best;
+
+    options.phases.iter().find_map(|phase| match phase {
+        MatchPhase::CaseInsensitive => folded_match,
+        MatchPhase::EditDistance => best,
+        MatchPhase::SortedWords => sorted_words_match,
+        MatchPhase::Abbreviation => abbreviation_match.map(|(_, c)| c),
+    })
+}
+
+/// The distance of `b` from `a` that candidates are scored by, for [`Scorer::EditDistance`].
+fn match_distance(a: &str, b: &str, limit: usize, options: &MatchOptions) -> Option<usize> {
+    if options.phrases {
+        return tokens::phrase_distance_by(a, b, limit, |x, y| {
+            word_match_distance(x, y, usize::MAX, options)
+        });
     }
+    word_match_distance(a, b, limit, options)
+}
 
-    find_match_by_sorted_words(candidates, lookup)
+/// Like [`match_distance`], ignoring [`MatchOptions::phrases`].
+fn word_match_distance(a: &str, b: &str, limit: usize, options: &MatchOptions) -> Option<usize> {
+    match (&options.equivalences, options.use_substrings) {
+        (None, false) => edit_distance(a, b, limit),
+        (None, true) => edit_distance_with_substrings(a, b, limit),
+        (Some(classes), false) => edit_distance_with_equivalences(a, b, limit, classes),
+        (Some(classes), true) => {
+            let a = a.chars().collect::<Chars>();
+            let b = b.chars().collect::<Chars>();
+            substring_score(&a, &b, limit, |a, b, limit| {
+                equivalence::equivalence_dp(a, b, limit, classes)
+            })
+        }
+    }
 }
 
-fn find_match_by_sorted_words<'a>(iter_names: &[&'a str], lookup: &str) -> Option<&'a str> {
-    let lookup_sorted_by_words = sort_by_words(lookup);
-    iter_names.iter().fold(None, |result, candidate| {
-        if sort_by_words(candidate) == lookup_sorted_by_words {
-            Some(*candidate)
+/// Like `find_best_match_for_name_iter_impl`, but the second kind of match is the candidate
+/// with the highest `similarity` to `lookup`, which returns `None` for candidates that are too
+/// different. Ties go to the lexicographically smallest candidate.
+fn find_best_match_by_similarity<'a, I, F>(
+    candidates: I,
+    lookup: &str,
+    options: &MatchOptions,
+    similarity: F,
+) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+    F: Fn(&str, &str) -> Option<f64>,
+{
+    if lookup.is_empty() {
+        return candidates.into_iter().find(|c| c.is_empty());
+    }
+
+    let lookup_normalized = options.normalize(lookup);
+    let lookup = &*lookup_normalized;
+    let lookup_folded = case_fold(lookup);
+    let lookup_sorted_by_words = options.sorted_words(lookup);
+
+    let mut best: Option<(f64, &str)> = None;
+    let mut sorted_words_match = None;
+    let mut folded_match = None;
+    let folded_rank = options.rank(MatchPhase::CaseInsensitive);
+    let similarity_rank = options.rank(MatchPhase::EditDistance);
+    let words_rank = options.rank(MatchPhase::SortedWords);
+    let abbreviation_rank = options.rank(MatchPhase::Abbreviation);
+    let mut abbreviation_match: Option<(usize, &str)> = None;
+    for c in candidates {
+        let c_normalized = options.normalize(c);
+        let c_folded = case_fold(&c_normalized);
+        if folded_rank.is_some() && options.is_folded_match(&c_folded, &lookup_folded) {
+            if folded_rank == Some(0) && options.tie_break.is_none() {
+                return Some(c);
+            }
+            if options.replaces(lookup, c, folded_match, false) {
+                folded_match = Some(c);
+            }
+        }
+        if abbreviation_rank.is_some() {
+            if let Some(score) = abbreviation_score(lookup, &c_normalized) {
+                if abbreviation_match
+                    .map_or(true, |best| options.is_better(lookup, (score, c), best))
+                {
+                    abbreviation_match = Some((score, c));
+                }
+            }
+        }
+
+        let (a, b) = if options.case_insensitive {
+            (&lookup_folded[..], &c_folded[..])
         } else {
-            result
+            (lookup, &c_normalized[..])
+        };
+        if let Some(similarity) = similarity_rank.and_then(|_| similarity(a, b)) {
+            let better = best.map_or(true, |(best_similarity, best)| {
+                similarity > best_similarity
+                    || (similarity == best_similarity && options.breaks_tie(lookup, c, best))
+            });
+            if better {
+                best = Some((similarity, c));
+            }
+        }
+
+        if words_rank.is_some()
+            && (best.is_none() || words_rank < similarity_rank)
+            && options.sorted_words(&c_normalized) == lookup_sorted_by_words
+            && options.replaces(lookup, c, sorted_words_match, true)
+        {
+            sorted_words_match = Some(c);
         }
+    }
+
+    let best = best.map(|(_, c)| c);
+    options.phases.iter().find_map(|phase| match phase {
+        MatchPhase::CaseInsensitive => folded_match,
+        MatchPhase::EditDistance => best,
+        MatchPhase::SortedWords => sorted_words_match,
+        MatchPhase::Abbreviation => abbreviation_match.map(|(_, c)| c),
     })
 }
 
-fn sort_by_words(name: &str) -> Vec<&str> {
-    let mut split_words: Vec<&str> = name.split('_').collect();
+/// The `dist` used by [`find_best_match_for_name`] when none is given: a third of the length
+/// of `lookup`, in chars, but at least 1.
+///
+/// This is public so that a caller can derive its own `dist` from it, e.g. to be stricter
+/// with `default_match_distance(lookup) / 2`.
+pub fn default_match_distance(lookup: &str) -> usize {
+    // `fn edit_distance()` use `chars()` to calculate edit distance, so we must
+    // also use `chars()` (and not `str::len()`) to calculate length here.
+    let lookup_len = lookup.chars().count();
+    cmp::max(lookup_len, 3) / 3
+}
+
+/// Whether `a` and `b` are equal ignoring case, as compared by the matching functions and
+/// [`edit_distance_ci`].
+///
+/// With the `caseless` feature, both are compared by their full Unicode [default case
+/// folding]. Without it, mapping every char to uppercase and then to lowercase approximates
+/// that with nothing but the standard library. That gets most strings right, like `ß` and
+/// `SS`, but not a few: `ẞ` doesn't match `ß`, and the dotless `ı` matches `i`, through `I`.
+/// Neither folds the dotted and dotless `i` as Turkish does, as that depends on the language.
+///
+/// ```
+/// use rustc_edit_distance::caseless_eq;
+///
+/// assert!(caseless_eq("Straße", "STRASSE"));
+/// assert!(caseless_eq("ΣΊΣΥΦΟΣ", "σίσυφος"));
+/// assert!(!caseless_eq("file", "fille"));
+/// ```
+///
+/// [default case folding]: https://www.unicode.org/versions/latest/core-spec/chapter-3/#G33992
+pub fn caseless_eq(a: &str, b: &str) -> bool {
+    fold_chars(a).eq(fold_chars(b))
+}
+
+/// Folds the case of `s` for caseless comparison, as described on [`caseless_eq`]. `ß`
+/// becomes `ss`, and `ς`, `σ` and `Σ` all become `σ`. ASCII is simply lowercased.
+fn case_fold(s: &str) -> String {
+    fold_chars(s).collect()
+}
+
+/// Whether a candidate is a match of the first kind for a non-empty lookup, given both with
+/// their case folded: equal to it, containing it, or contained in it. An empty candidate is
+/// contained in every lookup, but isn't a match.
+fn is_folded_match(c_folded: &str, lookup_folded: &str) -> bool {
+    is_folded_match_of_len(c_folded, lookup_folded, 1)
+}
+
+/// Like `is_folded_match`, but whichever string is contained in the other has to have at least
+/// `min_len` chars, which is at least 1, unless they are equal.
+fn is_folded_match_of_len(c_folded: &str, lookup_folded: &str, min_len: usize) -> bool {
+    let long_enough = |s: &str| s.chars().nth(min_len - 1).is_some();
+    !c_folded.is_empty()
+        && (c_folded == lookup_folded
+            || (long_enough(lookup_folded) && c_folded.contains(lookup_folded))
+            || (long_enough(c_folded) && lookup_folded.contains(c_folded)))
+}
+
+#[cfg(not(feature = "caseless"))]
+fn fold_chars(s: &str) -> impl Iterator<Item = char> + '_ {
+    s.chars()
+        .flat_map(char::to_uppercase)
+        .flat_map(char::to_lowercase)
+}
+
+#[cfg(feature = "caseless")]
+fn fold_chars(s: &str) -> impl Iterator<Item = char> + '_ {
+    use caseless::Caseless;
+
+    s.chars().default_case_fold()
+}
+
+fn sort_by_words<'a>(name: &'a str, separators: &[char]) -> Vec<&'a str> {
+    let mut split_words: Vec<&str> = name.split(separators).collect();
     // We are sorting primitive &strs and can use unstable sort here.
     split_words.sort_unstable();
     split_words
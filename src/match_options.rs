@@ -0,0 +1,606 @@
+use alloc::{borrow::Cow, string::String, sync::Arc, vec, vec::Vec};
+use core::{cmp, fmt};
+
+use crate::{EquivalenceClasses, MatchPhase, Metric};
+
+/// Options for [`find_best_match_for_name_with`](crate::find_best_match_for_name_with).
+///
+/// The defaults match [`find_best_match_for_name`](crate::find_best_match_for_name), and each
+/// method changes one option:
+///
+/// ```
+/// use rustc_edit_distance::{find_best_match_for_name_with, MatchOptions};
+///
+/// let options = MatchOptions::new().case_insensitive(true).max_distance(1);
+/// let candidates = ["Vec", "VecDeque", "HashMap"];
+/// assert_eq!(find_best_match_for_name_with(&candidates, "hashmp", &options), Some("HashMap"));
+/// ```
+///
+/// Options compare equal when they differ at most in their
+/// [`splitter`](MatchOptions::splitter) or the function of a [`Threshold::Custom`], since
+/// functions can't be compared.
+#[derive(Clone, Debug)]
+pub struct MatchOptions {
+    pub(crate) scorer: Scorer,
+    pub(crate) case_insensitive: bool,
+    pub(crate) use_substrings: bool,
+    pub(crate) phrases: bool,
+    pub(crate) threshold: Option<Threshold>,
+    pub(crate) separators: Vec<char>,
+    pub(crate) phonetic: Option<Phonetic>,
+    pub(crate) equivalences: Option<EquivalenceClasses>,
+    pub(crate) phases: Vec<MatchPhase>,
+    pub(crate) min_substring_len: usize,
+    pub(crate) prefix_weight: Option<f64>,
+    pub(crate) split_case: bool,
+    pub(crate) splitter: Option<Splitter>,
+    pub(crate) tie_break: Option<TieBreak>,
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) normalization: Option<Normalization>,
+}
+
+impl MatchOptions {
+    /// Returns the default options.
+    pub fn new() -> Self {
+        MatchOptions {
+            scorer: Scorer::EditDistance,
+            case_insensitive: false,
+            use_substrings: false,
+            phrases: false,
+            threshold: None,
+            separators: vec!['_'],
+            phonetic: None,
+            equivalences: None,
+            phases: vec![
+                MatchPhase::CaseInsensitive,
+                MatchPhase::EditDistance,
+                MatchPhase::SortedWords,
+            ],
+            min_substring_len: 1,
+            prefix_weight: None,
+            split_case: false,
+            splitter: None,
+            tie_break: None,
+            #[cfg(feature = "unicode-normalization")]
+            normalization: None,
+        }
+    }
+
+    /// How candidates are scored against `lookup`. Defaults to [`Scorer::EditDistance`].
+    pub fn scorer(mut self, scorer: Scorer) -> Self {
+        self.scorer = scorer;
+        self
+    }
+
+    /// Whether to ignore case when scoring candidates by distance, as [`edit_distance_ci`] does.
+    ///
+    /// Candidates equal to `lookup`, or containing it or contained in it, are matched first,
+    /// ignoring case either way. Defaults to `false`.
+    ///
+    /// [`edit_distance_ci`]: crate::edit_distance_ci
+    pub fn case_insensitive(mut self, yes: bool) -> Self {
+        self.case_insensitive = yes;
+        self
+    }
+
+    /// Whether to score candidates by
+    /// [`edit_distance_with_substrings`](crate::edit_distance_with_substrings), as
+    /// [`find_best_match_for_name_with_substrings`](crate::find_best_match_for_name_with_substrings)
+    /// does. Only used by [`Scorer::EditDistance`]. Defaults to `false`.
+    pub fn use_substrings(mut self, yes: bool) -> Self {
+        self.use_substrings = yes;
+        self
+    }
+
+    /// Whether to score candidates by [`phrase_distance`](crate::phrase_distance), e.g. for
+    /// the commands of a command palette, with each pair of aligned words scored as the other
+    /// options would score a whole candidate. Only used by [`Scorer::EditDistance`]. Defaults
+    /// to `false`.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{find_all_matches_within_with, MatchOptions};
+    ///
+    /// let commands = ["Open File in Editor", "Open Folder", "Close Editor"];
+    /// let options = MatchOptions::new().phrases(true).case_insensitive(true);
+    /// assert_eq!(
+    ///     find_all_matches_within_with(&commands, "open fiel in editr", &options),
+    ///     [("Open File in Editor", 2)]
+    /// );
+    /// ```
+    pub fn phrases(mut self, yes: bool) -> Self {
+        self.phrases = yes;
+        self
+    }
+
+    /// The largest distance, or score, at which a candidate can still match. Defaults to
+    /// [`default_match_distance`](crate::default_match_distance) of `lookup`. Only used by
+    /// [`Scorer::EditDistance`].
+    pub fn max_distance(self, dist: usize) -> Self {
+        self.threshold(Threshold::Absolute(dist))
+    }
+
+    /// Like [`max_distance`](MatchOptions::max_distance), but the largest distance depends on
+    /// `lookup` as set by `threshold`, e.g. to allow more edits for longer lookups.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{MatchOptions, Threshold};
+    ///
+    /// let options = MatchOptions::new().threshold(Threshold::Proportional(0.3));
+    /// let candidates = ["is_alphanumeric", "is_numeric"];
+    /// assert_eq!(options.best(&candidates, "is_alhpanumerc"), Some("is_alphanumeric"));
+    /// assert_eq!(options.best(&candidates, "is_nmrc"), None);
+    /// ```
+    pub fn threshold(mut self, threshold: Threshold) -> Self {
+        self.threshold = Some(threshold);
+        self
+    }
+
+    /// The chars at which names are split into words, when comparing them by their sorted
+    /// words. Defaults to `'_'`.
+    pub fn separators(mut self, separators: &[char]) -> Self {
+        self.separators = separators.to_vec();
+        self
+    }
+
+    /// Splits names into words with `splitter` when comparing them by their sorted words,
+    /// instead of at the [`separators`](MatchOptions::separators), e.g. to drop a file
+    /// extension. Defaults to splitting at the separators.
+    ///
+    /// ```
+    /// use rustc_edit_distance::MatchOptions;
+    ///
+    /// let options = MatchOptions::new().splitter(|name| name.split(['-', '.', ' ']).collect());
+    /// assert_eq!(options.best(&["report-final.txt"], "final.report txt"), Some("report-final.txt"));
+    /// ```
+    pub fn splitter(
+        mut self,
+        splitter: impl Fn(&str) -> Vec<&str> + Send + Sync + 'static,
+    ) -> Self {
+        self.splitter = Some(Splitter(Arc::new(splitter)));
+        self
+    }
+
+    /// Whether to also split names into words where the case changes, or where a run of digits
+    /// starts or ends, as [`split_identifier`](crate::split_identifier) does, when comparing
+    /// them by their sorted words. The words are then compared ignoring case, so e.g.
+    /// `parseConfig` matches `config_parse`. Defaults to `false`.
+    pub fn split_case(mut self, yes: bool) -> Self {
+        self.split_case = yes;
+        self
+    }
+
+    /// Prefers candidates that sound like `lookup`, i.e. that have the same `code`, over those
+    /// that are merely close to it. Among those, the closest one wins, whatever its distance.
+    /// Only used by [`Scorer::EditDistance`]. Defaults to not comparing codes.
+    pub fn phonetic(mut self, code: Phonetic) -> Self {
+        self.phonetic = Some(code);
+        self
+    }
+
+    /// Substituting chars that are equivalent in `classes` is free when scoring candidates by
+    /// distance, as in [`edit_distance_with_equivalences`]. The chars are compared after case
+    /// folding if [`case_insensitive`](MatchOptions::case_insensitive) is set. Only used by
+    /// [`Scorer::EditDistance`]. Defaults to no equivalences.
+    ///
+    /// [`edit_distance_with_equivalences`]: crate::edit_distance_with_equivalences
+    pub fn equivalences(mut self, classes: EquivalenceClasses) -> Self {
+        self.equivalences = Some(classes);
+        self
+    }
+
+    /// The kinds of match to look for, best first. A kind that isn't in `phases` is never
+    /// matched, and the best match is one of the first kind that any candidate is.
+    ///
+    /// Defaults to all three, in the order of
+    /// [`find_best_match_for_name`](crate::find_best_match_for_name). E.g. without
+    /// [`MatchPhase::CaseInsensitive`], a short lookup no longer matches every candidate that
+    /// contains it, and `[MatchPhase::EditDistance]` alone only compares candidates by
+    /// distance.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{MatchOptions, MatchPhase};
+    ///
+    /// let candidates = ["a_config", "ab"];
+    /// let options = MatchOptions::new();
+    /// assert_eq!(options.best(&candidates, "a"), Some("a_config"));
+    /// let options = options.phases(&[MatchPhase::EditDistance, MatchPhase::CaseInsensitive]);
+    /// assert_eq!(options.best(&candidates, "a"), Some("ab"));
+    /// ```
+    pub fn phases(mut self, phases: &[MatchPhase]) -> Self {
+        self.phases = phases.to_vec();
+        self
+    }
+
+    /// The fewest chars `lookup` needs for a candidate that contains it, ignoring case, to be a
+    /// match of the first kind, and the fewest a candidate contained in `lookup` needs.
+    ///
+    /// A short lookup is contained in many candidates that have little to do with it, so e.g.
+    /// with `3`, a lookup of two chars is only compared by distance to a candidate that
+    /// contains it. A candidate equal to `lookup` ignoring case stays a match of the first
+    /// kind. Defaults to 1, and 0 is the same.
+    ///
+    /// ```
+    /// use rustc_edit_distance::MatchOptions;
+    ///
+    /// let candidates = ["index", "id"];
+    /// assert_eq!(MatchOptions::new().best(&candidates, "nd"), Some("index"));
+    /// let options = MatchOptions::new().min_substring_len(3);
+    /// assert_eq!(options.best(&candidates, "nd"), Some("id"));
+    /// ```
+    pub fn min_substring_len(mut self, len: usize) -> Self {
+        self.min_substring_len = len.max(1);
+        self
+    }
+
+    /// Discounts the distance of candidates that start like `lookup`, as [`jaro_winkler`] does
+    /// its similarity, since the first chars of a name are rarely the ones misspelled.
+    ///
+    /// Each of the first four chars that a candidate shares with `lookup` takes `weight` off of
+    /// its distance, in proportion, and the candidate with the smallest discounted distance
+    /// within [`max_distance`](MatchOptions::max_distance) is the best match. So with a
+    /// `weight` of 0.25, a candidate that shares four chars scores as an exact match. Only
+    /// used by [`Scorer::EditDistance`] without
+    /// [`use_substrings`](MatchOptions::use_substrings). Defaults to no discount.
+    ///
+    /// ```
+    /// use rustc_edit_distance::MatchOptions;
+    ///
+    /// let candidates = ["pant", "print"];
+    /// assert_eq!(MatchOptions::new().best(&candidates, "prnt"), Some("pant"));
+    /// let options = MatchOptions::new().prefix_weight(0.1);
+    /// assert_eq!(options.best(&candidates, "prnt"), Some("print"));
+    /// ```
+    ///
+    /// [`jaro_winkler`]: crate::jaro_winkler
+    pub fn prefix_weight(mut self, weight: f64) -> Self {
+        self.prefix_weight = Some(weight);
+        self
+    }
+
+    /// How to pick between candidates that match equally well, in any kind of match.
+    ///
+    /// By default, of the candidates that match `lookup` ignoring case, the first one wins, of
+    /// those made of the same words the last one, and of the others the lexicographically
+    /// smallest one. With a `tie_break`, it picks the winner of every kind of match instead,
+    /// and so every candidate is looked at, even after a match of the first kind. Ties that are
+    /// left are broken lexicographically.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{MatchOptions, TieBreak};
+    ///
+    /// let candidates = ["beat", "melt"];
+    /// assert_eq!(MatchOptions::new().best(&candidates, "meat"), Some("beat"));
+    /// let options = MatchOptions::new().tie_break(TieBreak::LongestCommonPrefix);
+    /// assert_eq!(options.best(&candidates, "meat"), Some("melt"));
+    /// ```
+    pub fn tie_break(mut self, tie_break: TieBreak) -> Self {
+        self.tie_break = Some(tie_break);
+        self
+    }
+
+    /// Finds the best match for `lookup` of `candidates` with these options, as
+    /// [`find_best_match_for_name_with`](crate::find_best_match_for_name_with) does.
+    ///
+    /// The options are built once and can be used for any number of lookups.
+    pub fn best<'a>(&self, candidates: &[&'a str], lookup: &str) -> Option<&'a str> {
+        crate::find_best_match_for_name_with(candidates, lookup, self)
+    }
+
+    /// Like [`MatchOptions::best`], but with the distance of the candidates measured by
+    /// `metric` instead of [`edit_distance`](crate::edit_distance), for
+    /// [`Scorer::EditDistance`].
+    ///
+    /// The metric takes the place of the options for how the distance is computed, i.e.
+    /// [`use_substrings`](MatchOptions::use_substrings),
+    /// [`equivalences`](MatchOptions::equivalences) and [`phrases`](MatchOptions::phrases),
+    /// and is given the lookup and the candidates as they are compared otherwise, e.g.
+    /// folded with [`case_insensitive`](MatchOptions::case_insensitive).
+    pub fn best_by<'a, M: Metric + ?Sized>(
+        &self,
+        candidates: &[&'a str],
+        lookup: &str,
+        metric: &M,
+    ) -> Option<&'a str> {
+        if candidates.is_empty() {
+            return None;
+        }
+        let options = if self.use_substrings {
+            Cow::Owned(self.clone().use_substrings(false))
+        } else {
+            Cow::Borrowed(self)
+        };
+        crate::find_best_match_by_metric(candidates.iter().copied(), lookup, &options, metric)
+    }
+
+    /// Normalizes `lookup` and the candidates to `form` before comparing them, so that e.g.
+    /// canonically equivalent names are equal. The candidates are returned as they were given.
+    /// Defaults to comparing them as they are.
+    #[cfg(feature = "unicode-normalization")]
+    pub fn normalization(mut self, form: Normalization) -> Self {
+        self.normalization = Some(form);
+        self
+    }
+
+    /// The largest distance at which a candidate can still match `lookup`.
+    pub(crate) fn limit(&self, lookup: &str) -> usize {
+        match &self.threshold {
+            Some(threshold) => threshold.limit(lookup),
+            None => crate::default_match_distance(lookup),
+        }
+    }
+
+    /// Whether a candidate is a match of the first kind, given it and the lookup with their case
+    /// folded.
+    pub(crate) fn is_folded_match(&self, c_folded: &str, lookup_folded: &str) -> bool {
+        crate::is_folded_match_of_len(c_folded, lookup_folded, self.min_substring_len)
+    }
+
+    /// The words of `name`, sorted, for comparing names by their sorted words.
+    pub(crate) fn sorted_words<'s>(&self, name: &'s str) -> Vec<Cow<'s, str>> {
+        let words = match &self.splitter {
+            Some(Splitter(splitter)) => splitter(name),
+            None => name.split(&self.separators[..]).collect(),
+        };
+        let mut words = if self.split_case {
+            words
+                .into_iter()
+                .flat_map(crate::words::split_case)
+                .map(|word| Cow::Owned(crate::case_fold(word)))
+                .collect::<Vec<_>>()
+        } else {
+            words.into_iter().map(Cow::Borrowed).collect()
+        };
+        words.sort_unstable();
+        words
+    }
+
+    /// Whether candidate `new` wins a tie with `old`, which came before it.
+    pub(crate) fn breaks_tie(&self, lookup: &str, new: &str, old: &str) -> bool {
+        let common_prefix = |c: &str| {
+            let prefix = lookup.chars().zip(c.chars()).take_while(|(a, b)| a == b);
+            cmp::Reverse(prefix.count())
+        };
+        match self.tie_break.unwrap_or(TieBreak::Lexicographic) {
+            TieBreak::FirstInOrder => false,
+            TieBreak::Shortest => (new.chars().count(), new) < (old.chars().count(), old),
+            TieBreak::LongestCommonPrefix => (common_prefix(new), new) < (common_prefix(old), old),
+            TieBreak::Lexicographic => new < old,
+        }
+    }
+
+    /// Whether `new` is a better match than `old`, given both with their scores, where the
+    /// smaller is better.
+    pub(crate) fn is_better<T: PartialOrd>(
+        &self,
+        lookup: &str,
+        (new_score, new): (T, &str),
+        (old_score, old): (T, &str),
+    ) -> bool {
+        new_score < old_score || (new_score == old_score && self.breaks_tie(lookup, new, old))
+    }
+
+    /// Whether `new` replaces `old` as the match of a kind of match without scores, where by
+    /// default the first match is kept, or the last one if `last_by_default`.
+    pub(crate) fn replaces(
+        &self,
+        lookup: &str,
+        new: &str,
+        old: Option<&str>,
+        last_by_default: bool,
+    ) -> bool {
+        match (old, self.tie_break) {
+            (None, _) => true,
+            (Some(old), Some(_)) => self.breaks_tie(lookup, new, old),
+            (Some(_), None) => last_by_default,
+        }
+    }
+
+    /// The position of `phase` in [`MatchOptions::phases`], if it is there.
+    pub(crate) fn rank(&self, phase: MatchPhase) -> Option<usize> {
+        self.phases.iter().position(|&p| p == phase)
+    }
+
+    #[cfg(feature = "unicode-normalization")]
+    pub(crate) fn normalize<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        use unicode_normalization::UnicodeNormalization;
+
+        match self.normalization {
+            Some(Normalization::Nfc) => Cow::Owned(s.nfc().collect()),
+            Some(Normalization::Nfkc) => Cow::Owned(s.nfkc().collect()),
+            Some(Normalization::StripDiacritics) => {
+                Cow::Owned(crate::strip_diacritics(s).collect())
+            }
+            None => Cow::Borrowed(s),
+        }
+    }
+
+    #[cfg(not(feature = "unicode-normalization"))]
+    pub(crate) fn normalize<'s>(&self, s: &'s str) -> Cow<'s, str> {
+        Cow::Borrowed(s)
+    }
+}
+
+impl Default for MatchOptions {
+    fn default() -> Self {
+        MatchOptions::new()
+    }
+}
+
+impl PartialEq for MatchOptions {
+    fn eq(&self, other: &Self) -> bool {
+        // Every field but `splitter`, and the function of a custom `threshold`.
+        let same_threshold = match (&self.threshold, &other.threshold) {
+            (Some(a), Some(b)) => a.same_as(b),
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        let equal = self.scorer == other.scorer
+            && self.case_insensitive == other.case_insensitive
+            && self.use_substrings == other.use_substrings
+            && self.phrases == other.phrases
+            && same_threshold
+            && self.separators == other.separators
+            && self.phonetic == other.phonetic
+            && self.equivalences == other.equivalences
+            && self.phases == other.phases
+            && self.min_substring_len == other.min_substring_len
+            && self.prefix_weight == other.prefix_weight
+            && self.split_case == other.split_case
+            && self.tie_break == other.tie_break;
+        #[cfg(feature = "unicode-normalization")]
+        let equal = equal && self.normalization == other.normalization;
+        equal
+    }
+}
+
+/// A function for [`MatchOptions::splitter`].
+#[derive(Clone)]
+pub(crate) struct Splitter(Arc<SplitFn>);
+
+type SplitFn = dyn Fn(&str) -> Vec<&str> + Send + Sync;
+
+impl fmt::Debug for Splitter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Splitter(..)")
+    }
+}
+
+/// How [`find_best_match_for_name_with`](crate::find_best_match_for_name_with) scores
+/// candidates that don't match `lookup` outright.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Scorer {
+    /// By [`edit_distance`](crate::edit_distance), or
+    /// [`edit_distance_with_substrings`](crate::edit_distance_with_substrings). The closest
+    /// candidate within [`MatchOptions::max_distance`] is the best match.
+    EditDistance,
+    /// By [`jaro_winkler`](crate::jaro_winkler) similarity. The most similar candidate with a
+    /// similarity of at least `min_similarity` is the best match.
+    JaroWinkler { min_similarity: f64 },
+    /// By [`dice_coefficient`](crate::dice_coefficient) of the char bigrams, which suits long
+    /// names made of the same words in another order. The most similar candidate with a
+    /// coefficient of at least `min_similarity` is the best match.
+    Dice { min_similarity: f64 },
+}
+
+/// How far a candidate can be from a lookup and still match it, for
+/// [`MatchOptions::threshold`], or to compute the `limit` of any of the distance functions with
+/// [`Threshold::limit`].
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum Threshold {
+    /// At most this many edits, whatever the lookup.
+    Absolute(usize),
+    /// At most this fraction of the length of the lookup in chars, rounded to the nearest
+    /// integer, e.g. `0.3` for 30%.
+    Proportional(f64),
+    /// At most as many edits as the function returns for the lookup, e.g.
+    /// [`default_match_distance`](crate::default_match_distance).
+    Custom(Arc<dyn Fn(&str) -> usize + Send + Sync>),
+}
+
+impl Threshold {
+    /// Returns the largest distance from `lookup` allowed by the threshold.
+    ///
+    /// ```
+    /// use std::sync::Arc;
+    ///
+    /// use rustc_edit_distance::Threshold;
+    ///
+    /// assert_eq!(Threshold::Absolute(2).limit("anything"), 2);
+    /// assert_eq!(Threshold::Proportional(0.3).limit("iterator"), 2);
+    /// assert_eq!(Threshold::Custom(Arc::new(|s| s.len() / 2)).limit("iterator"), 4);
+    /// ```
+    pub fn limit(&self, lookup: &str) -> usize {
+        match self {
+            Threshold::Absolute(limit) => *limit,
+            // Not `f64::round`, which needs `std`. The cast saturates, and turns NaN into 0.
+            Threshold::Proportional(fraction) => {
+                (lookup.chars().count() as f64 * fraction + 0.5) as usize
+            }
+            Threshold::Custom(limit) => limit(lookup),
+        }
+    }
+
+    /// Whether the thresholds are the same but for the functions of custom ones, which can't be
+    /// compared.
+    fn same_as(&self, other: &Threshold) -> bool {
+        match (self, other) {
+            (Threshold::Absolute(a), Threshold::Absolute(b)) => a == b,
+            (Threshold::Proportional(a), Threshold::Proportional(b)) => a == b,
+            (Threshold::Custom(_), Threshold::Custom(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Debug for Threshold {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Threshold::Absolute(limit) => f.debug_tuple("Absolute").field(limit).finish(),
+            Threshold::Proportional(fraction) => {
+                f.debug_tuple("Proportional").field(fraction).finish()
+            }
+            Threshold::Custom(_) => f.write_str("Custom(..)"),
+        }
+    }
+}
+
+/// How to break ties between candidates, for [`MatchOptions::tie_break`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum TieBreak {
+    /// The candidate that comes first in the candidates wins.
+    FirstInOrder,
+    /// The candidate with the fewest chars wins.
+    Shortest,
+    /// The candidate that starts with the most chars of the lookup wins.
+    LongestCommonPrefix,
+    /// The lexicographically smallest candidate wins.
+    Lexicographic,
+}
+
+/// A phonetic code for [`MatchOptions::phonetic`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Phonetic {
+    /// [`soundex`](crate::soundex).
+    Soundex,
+    /// [`metaphone`](crate::metaphone).
+    Metaphone,
+    /// [`double_metaphone`](crate::double_metaphone). Two strings sound alike if either code
+    /// of one is either code of the other.
+    DoubleMetaphone,
+}
+
+impl Phonetic {
+    /// The codes of `s`, none if it has no letters to code.
+    pub(crate) fn encode(self, s: &str) -> Vec<String> {
+        let mut codes = match self {
+            Phonetic::Soundex => vec![crate::soundex(s)],
+            Phonetic::Metaphone => vec![crate::metaphone(s)],
+            Phonetic::DoubleMetaphone => {
+                let (primary, alternate) = crate::double_metaphone(s);
+                vec![primary, alternate]
+            }
+        };
+        codes.retain(|code| !code.is_empty());
+        codes.dedup();
+        codes
+    }
+}
+
+/// A [Unicode normalization form] for [`MatchOptions::normalization`].
+///
+/// [Unicode normalization form]: https://unicode.org/reports/tr15/
+#[cfg(feature = "unicode-normalization")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Normalization {
+    /// Canonical composition, as in [`edit_distance_normalized`](crate::edit_distance_normalized).
+    Nfc,
+    /// Compatibility composition, as in [`edit_distance_nfkc`](crate::edit_distance_nfkc).
+    Nfkc,
+    /// Canonical composition without diacritics, as in
+    /// [`edit_distance_ignoring_diacritics`](crate::edit_distance_ignoring_diacritics).
+    StripDiacritics,
+}
@@ -0,0 +1,65 @@
+use crate::{
+    edit_distance_with_equivalences, keyboard_edit_distance, weighted_edit_distance, EditCosts,
+    EquivalenceClasses, KeyboardLayout,
+};
+
+/// A distance between strings that candidates can be matched by, with
+/// [`find_best_match_for_name_with_metric`](crate::find_best_match_for_name_with_metric) or
+/// [`MatchOptions::best_by`](crate::MatchOptions::best_by).
+///
+/// Every function called like [`edit_distance`](crate::edit_distance) is a metric, e.g.
+/// [`edit_distance`](crate::edit_distance) itself,
+/// [`damerau_levenshtein`](crate::damerau_levenshtein) or a closure. So are the types that
+/// parameterize a distance: [`EditCosts`] by [`weighted_edit_distance`], [`KeyboardLayout`] by
+/// [`keyboard_edit_distance`] and [`EquivalenceClasses`] by
+/// [`edit_distance_with_equivalences`].
+///
+/// ```
+/// use rustc_edit_distance::{edit_distance, find_best_match_for_name_with_metric, Metric};
+///
+/// /// Ignores underscores, which are easy to leave out.
+/// struct IgnoreUnderscores;
+///
+/// impl Metric for IgnoreUnderscores {
+///     fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+///         edit_distance(&a.replace('_', ""), &b.replace('_', ""), limit)
+///     }
+/// }
+///
+/// let candidates = ["foobarbaz", "foo_bar_bat"];
+/// let best = find_best_match_for_name_with_metric(&candidates, "foo_bar_baz", None, &IgnoreUnderscores);
+/// assert_eq!(best, Some("foobarbaz"));
+/// ```
+pub trait Metric {
+    /// Returns the distance between `a` and `b`, or `None` if it exceeds `limit`.
+    ///
+    /// The distance of a string to itself must be 0.
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize>;
+}
+
+impl<F> Metric for F
+where
+    F: Fn(&str, &str, usize) -> Option<usize>,
+{
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        self(a, b, limit)
+    }
+}
+
+impl Metric for EditCosts {
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        weighted_edit_distance(a, b, limit, self)
+    }
+}
+
+impl Metric for KeyboardLayout {
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        keyboard_edit_distance(a, b, limit, self)
+    }
+}
+
+impl Metric for EquivalenceClasses {
+    fn distance(&self, a: &str, b: &str, limit: usize) -> Option<usize> {
+        edit_distance_with_equivalences(a, b, limit, self)
+    }
+}
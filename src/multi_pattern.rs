@@ -0,0 +1,155 @@
+use alloc::{boxed::Box, collections::VecDeque, vec::Vec};
+use core::str::CharIndices;
+
+use crate::approx::Table;
+use crate::bit_parallel::{self, Searcher};
+
+/// A set of patterns to search a text for all at once, allowing a few edits, as with `agrep`.
+///
+/// Every pattern is searched for as by [`find_approx`](crate::find_approx), but the text is
+/// only read once. Patterns of up to 64 chars, all below 256, are searched for with the
+/// bit-parallel algorithm of [`fuzzy_contains`](crate::fuzzy_contains), which finds where
+/// matches end, but not where they start.
+///
+/// ```
+/// use rustc_edit_distance::{MultiMatch, MultiPattern};
+///
+/// let patterns = MultiPattern::new(&["unwrap", "expect"]);
+/// let text = "let x = y.unwarp(); z.expcet(\"z\");";
+/// let matches = patterns.find_iter(text, 2).collect::<Vec<_>>();
+/// assert_eq!(
+///     matches,
+///     [
+///         MultiMatch { pattern: 0, end: 16, dist: 1 },
+///         MultiMatch { pattern: 1, end: 28, dist: 1 },
+///     ]
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct MultiPattern {
+    /// The state of the search for each pattern before reading any text, or `None` for an
+    /// empty pattern, which matches nowhere.
+    scanners: Vec<Option<Scanner>>,
+}
+
+/// Where a [`MultiPattern`] found one of its patterns in a text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct MultiMatch {
+    /// The index of the pattern, in the order they were given.
+    pub pattern: usize,
+    /// The byte offset in the text where the match ends, exclusive.
+    pub end: usize,
+    /// The [`edit_distance`](crate::edit_distance) between the pattern and the match.
+    pub dist: usize,
+}
+
+#[derive(Clone, Debug)]
+enum Scanner {
+    /// Boxed, as its table of match masks is much larger than a [`Table`].
+    BitParallel(Box<Searcher>),
+    Table(Table),
+}
+
+impl Scanner {
+    /// Moves on to the next char of the text, `t`, which ends at the byte offset `end`, and
+    /// returns the distance of the closest match ending there.
+    fn advance(&mut self, t: char, end: usize) -> usize {
+        match self {
+            Scanner::BitParallel(searcher) => searcher.advance(t),
+            Scanner::Table(table) => table.advance(t, end).0,
+        }
+    }
+}
+
+impl MultiPattern {
+    /// Compiles `patterns` for searching.
+    pub fn new(patterns: &[&str]) -> Self {
+        let scanners = patterns
+            .iter()
+            .map(|pattern| {
+                let chars = pattern.chars().collect::<Vec<_>>();
+                if chars.is_empty() {
+                    None
+                } else if chars.len() <= bit_parallel::MAX_LEN
+                    && chars.iter().all(|&c| u32::from(c) <= 0xff)
+                {
+                    Some(Scanner::BitParallel(Box::new(Searcher::new(&chars))))
+                } else {
+                    Some(Scanner::Table(Table::new(chars)))
+                }
+            })
+            .collect();
+        MultiPattern { scanners }
+    }
+
+    /// Finds the places in `text` where any of the patterns occurs with at most `k` edits.
+    ///
+    /// Like [`find_approx`](crate::find_approx), only the closest match of each run of
+    /// consecutive ends of a pattern is returned, the first one on a tie. Matches are returned
+    /// in the order their runs end, and of runs that end at the same char, in the order of
+    /// their patterns.
+    pub fn find_iter<'t>(&self, text: &'t str, k: usize) -> impl Iterator<Item = MultiMatch> + 't {
+        MultiMatches {
+            scanners: self.scanners.clone(),
+            best: self.scanners.iter().map(|_| None).collect(),
+            k,
+            text: text.char_indices(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+}
+
+struct MultiMatches<'t> {
+    scanners: Vec<Option<Scanner>>,
+    /// The closest match of the current run of ends within `k` edits of each pattern.
+    best: Vec<Option<MultiMatch>>,
+    k: usize,
+    text: CharIndices<'t>,
+    /// The matches whose runs ended at the last char read, still to be returned.
+    pending: VecDeque<MultiMatch>,
+    done: bool,
+}
+
+impl Iterator for MultiMatches<'_> {
+    type Item = MultiMatch;
+
+    fn next(&mut self) -> Option<MultiMatch> {
+        loop {
+            if let Some(m) = self.pending.pop_front() {
+                return Some(m);
+            }
+            if self.done {
+                return None;
+            }
+
+            let (offset, t) = match self.text.next() {
+                Some(next) => next,
+                None => {
+                    self.done = true;
+                    self.pending
+                        .extend(self.best.iter_mut().filter_map(Option::take));
+                    continue;
+                }
+            };
+            let end = offset + t.len_utf8();
+
+            for (pattern, (scanner, best)) in
+                self.scanners.iter_mut().zip(&mut self.best).enumerate()
+            {
+                let scanner = match scanner {
+                    Some(scanner) => scanner,
+                    None => continue,
+                };
+                let dist = scanner.advance(t, end);
+                if dist <= self.k {
+                    if best.map_or(true, |best| dist < best.dist) {
+                        *best = Some(MultiMatch { pattern, end, dist });
+                    }
+                } else if let Some(best) = best.take() {
+                    self.pending.push_back(best);
+                }
+            }
+        }
+    }
+}
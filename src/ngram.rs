@@ -0,0 +1,138 @@
+#[cfg(feature = "std")]
+use alloc::{borrow::ToOwned, string::String};
+use alloc::{collections::BTreeMap, vec::Vec};
+
+/// Finds the [Sørensen–Dice coefficient] of the char bigrams of two strings, from 0.0 for
+/// strings with no bigrams in common to 1.0 for equal ones.
+///
+/// The coefficient is twice the number of bigrams the strings share, counted with
+/// multiplicity, over their total number of bigrams. Since the order of the bigrams doesn't
+/// matter, reordered words still score high:
+///
+/// ```
+/// use rustc_edit_distance::dice_coefficient;
+///
+/// assert!(dice_coefficient("night club", "club night") > 0.75);
+/// ```
+///
+/// Strings of fewer than two chars have no bigrams, so they only score 1.0 against an equal
+/// string, and 0.0 otherwise.
+///
+/// [Sørensen–Dice coefficient]: https://en.wikipedia.org/wiki/S%C3%B8rensen%E2%80%93Dice_coefficient
+pub fn dice_coefficient(a: &str, b: &str) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+
+    let a = bigrams(a);
+    let b = bigrams(b);
+    let total = a.len() + b.len();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut counts = BTreeMap::new();
+    for bigram in a {
+        *counts.entry(bigram).or_insert(0usize) += 1;
+    }
+    let mut shared = 0;
+    for bigram in b {
+        if let Some(count) = counts.get_mut(&bigram).filter(|count| **count > 0) {
+            *count -= 1;
+            shared += 1;
+        }
+    }
+    (2 * shared) as f64 / total as f64
+}
+
+fn bigrams(s: &str) -> Vec<(char, char)> {
+    s.chars().zip(s.chars().skip(1)).collect()
+}
+
+/// The char n-grams of a string and how often each occurs, for comparing strings by
+/// [`cosine_similarity`]. Like it, this needs the `std` feature.
+///
+/// Building a profile is the costly part of the comparison, so a profile can be built once
+/// per candidate and reused for every lookup:
+///
+/// ```
+/// use rustc_edit_distance::{cosine_similarity, NGramProfile};
+///
+/// let titles = ["USB-C charging cable 2m", "Wireless mouse, black"];
+/// let profiles = titles.map(|title| NGramProfile::new(title, 3));
+/// let lookup = NGramProfile::new("usb-c cable, 2m, charging", 3);
+/// assert!(cosine_similarity(&lookup, &profiles[0]) > cosine_similarity(&lookup, &profiles[1]));
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug)]
+pub struct NGramProfile {
+    counts: BTreeMap<String, usize>,
+    norm_squared: usize,
+}
+
+#[cfg(feature = "std")]
+impl NGramProfile {
+    /// Counts the n-grams of `s`, i.e. its substrings of `n` chars. A non-empty string of
+    /// fewer than `n` chars has itself as its only n-gram, so that it is still similar to an
+    /// equal string.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is 0.
+    pub fn new(s: &str, n: usize) -> Self {
+        assert!(n > 0, "n-grams must be at least one char long");
+
+        let starts = s.char_indices().map(|(i, _)| i).collect::<Vec<_>>();
+        let mut counts = BTreeMap::new();
+        if !s.is_empty() && starts.len() < n {
+            counts.insert(s.to_owned(), 1);
+        }
+        for (k, &start) in starts.iter().enumerate() {
+            let end = match starts.get(k + n) {
+                Some(&end) => end,
+                None if k + n == starts.len() => s.len(),
+                None => break,
+            };
+            *counts.entry(s[start..end].to_owned()).or_insert(0) += 1;
+        }
+        let norm_squared = counts.values().map(|&count| count * count).sum();
+        NGramProfile {
+            counts,
+            norm_squared,
+        }
+    }
+}
+
+/// Finds the [cosine similarity] of two [`NGramProfile`]s, from 0.0 for profiles with no
+/// n-grams in common to 1.0 for ones with the same n-grams in the same proportions.
+///
+/// Unlike edit distance, this doesn't grow with the lengths of the strings, so a short string
+/// can still be similar to a much longer one with the same words. Two empty profiles have a
+/// similarity of 1.0, and profiles built with different `n` have nothing in common.
+///
+/// Needs the `std` feature, for the square root.
+///
+/// [cosine similarity]: https://en.wikipedia.org/wiki/Cosine_similarity
+#[cfg(feature = "std")]
+pub fn cosine_similarity(a: &NGramProfile, b: &NGramProfile) -> f64 {
+    if a.counts.is_empty() && b.counts.is_empty() {
+        return 1.0;
+    }
+    if a.counts.is_empty() || b.counts.is_empty() {
+        return 0.0;
+    }
+
+    let (small, large) = if a.counts.len() <= b.counts.len() {
+        (a, b)
+    } else {
+        (b, a)
+    };
+    let dot = small
+        .counts
+        .iter()
+        .filter_map(|(gram, &count)| Some(count * large.counts.get(gram)?))
+        .sum::<usize>();
+    // Taking a single square root of the product keeps equal profiles at exactly 1.0.
+    let norms = ((a.norm_squared * b.norm_squared) as f64).sqrt();
+    (dot as f64 / norms).min(1.0)
+}
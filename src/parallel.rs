@@ -0,0 +1,67 @@
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use rayon::prelude::*;
+
+use crate::distance_matrix::upper_row;
+use crate::{
+    case_fold, default_match_distance, edit_distance, is_folded_match, sort_by_words,
+    DistanceMatrix,
+};
+
+/// Like [`find_best_match_for_name`], but computes the edit distances on the [`rayon`] thread
+/// pool.
+///
+/// Like the serial version, which narrows `dist` every time it finds a closer candidate, the
+/// threads share the smallest distance found so far, so that each can give up on a candidate
+/// as soon as it is further than that. A candidate at that distance is still measured, and the
+/// closest one is picked afterwards, so the result is always the same as that of
+/// [`find_best_match_for_name`], including how ties are broken.
+///
+/// [`find_best_match_for_name`]: crate::find_best_match_for_name
+pub fn find_best_match_for_name_parallel<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<&'a str> {
+    if lookup.is_empty() {
+        return candidates.par_iter().find_first(|c| c.is_empty()).copied();
+    }
+
+    let lookup_folded = case_fold(lookup);
+    if let Some(c) = candidates
+        .par_iter()
+        .find_first(|c| is_folded_match(&case_fold(c), &lookup_folded))
+    {
+        return Some(*c);
+    }
+
+    let dist = AtomicUsize::new(dist.unwrap_or_else(|| default_match_distance(lookup)));
+    let best = candidates
+        .par_iter()
+        .filter_map(|&c| {
+            let d = edit_distance(lookup, c, dist.load(Ordering::Relaxed))?;
+            dist.fetch_min(d, Ordering::Relaxed);
+            Some((d, c))
+        })
+        .min();
+    if let Some((_, c)) = best {
+        return Some(c);
+    }
+
+    let lookup_sorted_by_words = sort_by_words(lookup, &['_']);
+    candidates
+        .par_iter()
+        .find_last(|c| sort_by_words(c, &['_']) == lookup_sorted_by_words)
+        .copied()
+}
+
+/// Like [`distance_matrix`], but computes the rows of the matrix on the [`rayon`] thread pool.
+///
+/// [`distance_matrix`]: crate::distance_matrix
+pub fn distance_matrix_parallel(items: &[&str], limit: usize) -> DistanceMatrix {
+    let rows = (0..items.len())
+        .into_par_iter()
+        .map(|i| upper_row(items, i, limit))
+        .collect::<Vec<_>>();
+    DistanceMatrix::from_upper(items.len(), rows.concat())
+}
@@ -0,0 +1,157 @@
+use std::{ffi::OsStr, path::Path, vec::Vec};
+
+use crate::weighted::weighted_dp;
+use crate::{default_match_distance, edit_distance_slices};
+
+/// How [`path_edit_distance`] and [`find_best_match_for_path`] compare paths.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum PathScoring {
+    /// As whole strings, separators and all, like [`edit_distance`](crate::edit_distance).
+    Whole,
+    /// Component by component, aligning the components as
+    /// [`phrase_distance`](crate::phrase_distance) aligns words: each aligned pair costs its
+    /// edit distance, and every other component its length. So a typo in a file name is no
+    /// further than it is on its own, and a path with fewer components isn't made closer by
+    /// editing across the separators.
+    #[default]
+    Components,
+}
+
+/// Finds the [`edit_distance`](crate::edit_distance) between two OS strings.
+///
+/// The strings don't have to be valid Unicode. Their valid parts are compared char by char,
+/// and the rest unit by unit: a byte on Unix, an unpaired surrogate on Windows. So two
+/// different invalid strings aren't equal, as they would be after
+/// [`to_string_lossy`](OsStr::to_string_lossy). On other platforms they are compared lossily.
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn os_str_edit_distance(a: &OsStr, b: &OsStr, limit: usize) -> Option<usize> {
+    edit_distance_slices(&units(a), &units(b), limit)
+}
+
+/// Finds the distance between two paths, compared as `scoring` says.
+///
+/// ```
+/// use rustc_edit_distance::{path_edit_distance, PathScoring};
+/// use std::path::Path;
+///
+/// let (a, b) = (Path::new("src/amin.rs"), Path::new("src/main.rs"));
+/// assert_eq!(path_edit_distance(a, b, usize::MAX, PathScoring::Components), Some(1));
+/// // Leaving out a separator takes a whole component to fix.
+/// let c = Path::new("srcmain.rs");
+/// assert_eq!(path_edit_distance(b, c, usize::MAX, PathScoring::Whole), Some(1));
+/// assert_eq!(path_edit_distance(b, c, usize::MAX, PathScoring::Components), Some(6));
+/// ```
+///
+/// Returns `None` if the distance exceeds the limit.
+pub fn path_edit_distance(a: &Path, b: &Path, limit: usize, scoring: PathScoring) -> Option<usize> {
+    match scoring {
+        PathScoring::Whole => os_str_edit_distance(a.as_os_str(), b.as_os_str(), limit),
+        PathScoring::Components => {
+            let components = |path: &Path| {
+                path.components()
+                    .map(|component| units(component.as_os_str()))
+                    .collect::<Vec<_>>()
+            };
+            let len = |component: &Vec<u32>| component.len();
+            weighted_dp(
+                &components(a),
+                &components(b),
+                limit,
+                len,
+                len,
+                |x, y| edit_distance_slices(x, y, usize::MAX).unwrap(),
+                1,
+            )
+        }
+    }
+}
+
+/// Finds the candidate closest to `lookup` by [`path_edit_distance`], e.g. to suggest the path
+/// that was meant by a mistyped one.
+///
+/// `dist` defaults to the same threshold as
+/// [`find_best_match_for_name`](crate::find_best_match_for_name), of the path as a string.
+/// Ties go to the smallest candidate, as [`Path`]s are ordered. There are no other kinds of
+/// match, e.g. ignoring case.
+///
+/// ```
+/// use rustc_edit_distance::{find_best_match_for_path, PathScoring};
+/// use std::path::Path;
+///
+/// let candidates = [Path::new("src/main.rs"), Path::new("src/lib.rs"), Path::new("a.rs")];
+/// let best = find_best_match_for_path(&candidates, Path::new("src/amin.rs"), None, PathScoring::Components);
+/// assert_eq!(best, Some(Path::new("src/main.rs")));
+/// ```
+pub fn find_best_match_for_path<'a>(
+    candidates: &[&'a Path],
+    lookup: &Path,
+    dist: Option<usize>,
+    scoring: PathScoring,
+) -> Option<&'a Path> {
+    let mut dist = dist.unwrap_or_else(|| default_match_distance(&lookup.to_string_lossy()));
+    let mut best: Option<&'a Path> = None;
+    for &c in candidates {
+        if let Some(d) = path_edit_distance(lookup, c, dist, scoring) {
+            if d < dist || best.map_or(true, |best| c < best) {
+                dist = d;
+                best = Some(c);
+            }
+        }
+    }
+    best
+}
+
+/// The units of edit of `s`: its chars, and what isn't valid Unicode above them.
+fn units(s: &OsStr) -> Vec<u32> {
+    // Above every char.
+    const INVALID: u32 = char::MAX as u32 + 1;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        let mut bytes = s.as_bytes();
+        let mut units = Vec::with_capacity(bytes.len());
+        loop {
+            match std::str::from_utf8(bytes) {
+                Ok(valid) => {
+                    units.extend(valid.chars().map(u32::from));
+                    return units;
+                }
+                Err(error) => {
+                    let (valid, rest) = bytes.split_at(error.valid_up_to());
+                    // The bytes up to `valid_up_to` are valid UTF-8.
+                    let valid = std::str::from_utf8(valid).unwrap();
+                    units.extend(valid.chars().map(u32::from));
+                    let invalid = error.error_len().unwrap_or(rest.len());
+                    units.extend(
+                        rest[..invalid]
+                            .iter()
+                            .map(|&byte| INVALID + u32::from(byte)),
+                    );
+                    bytes = &rest[invalid..];
+                }
+            }
+        }
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::ffi::OsStrExt;
+
+        char::decode_utf16(s.encode_wide())
+            .map(|unit| match unit {
+                Ok(c) => u32::from(c),
+                Err(error) => INVALID + u32::from(error.unpaired_surrogate()),
+            })
+            .collect()
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = INVALID;
+        s.to_string_lossy().chars().map(u32::from).collect()
+    }
+}
@@ -0,0 +1,116 @@
+//! The compact binary format of the dictionary indexes, for building them offline and loading
+//! them at runtime, e.g. from `include_bytes!`.
+//!
+//! An index starts with a four byte tag of its type, followed by its fields: every number as
+//! eight little-endian bytes, and every string as its length followed by its UTF-8 bytes, which
+//! a loaded index borrows instead of copying.
+
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+/// An error returned when loading an index from bytes that aren't one.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IndexDecodeError {
+    /// The byte offset at which the bytes stopped making sense.
+    pub offset: usize,
+}
+
+impl fmt::Display for IndexDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid index bytes at offset {}", self.offset)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexDecodeError {}
+
+pub(crate) struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    pub(crate) fn new(tag: &[u8; 4]) -> Self {
+        Writer {
+            bytes: tag.to_vec(),
+        }
+    }
+
+    pub(crate) fn usize(&mut self, n: usize) {
+        self.bytes.extend_from_slice(&(n as u64).to_le_bytes());
+    }
+
+    pub(crate) fn str(&mut self, s: &str) {
+        self.usize(s.len());
+        self.bytes.extend_from_slice(s.as_bytes());
+    }
+
+    pub(crate) fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+pub(crate) struct Reader<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Starts reading `bytes`, which have to start with `tag`.
+    pub(crate) fn new(bytes: &'a [u8], tag: &[u8; 4]) -> Result<Self, IndexDecodeError> {
+        let mut reader = Reader { bytes, offset: 0 };
+        if reader.take(4)? != tag {
+            return Err(IndexDecodeError { offset: 0 });
+        }
+        Ok(reader)
+    }
+
+    pub(crate) fn usize(&mut self) -> Result<usize, IndexDecodeError> {
+        let offset = self.offset;
+        let bytes = self.take(8)?;
+        let n = u64::from_le_bytes(bytes.try_into().unwrap());
+        usize::try_from(n).map_err(|_| IndexDecodeError { offset })
+    }
+
+    /// The offset of the next byte to read, e.g. for the error of a value that was read.
+    pub(crate) fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Reads a number that has to be in `range`, e.g. an index.
+    pub(crate) fn index(&mut self, range: Range<usize>) -> Result<usize, IndexDecodeError> {
+        let offset = self.offset;
+        let n = self.usize()?;
+        if range.contains(&n) {
+            Ok(n)
+        } else {
+            Err(IndexDecodeError { offset })
+        }
+    }
+
+    pub(crate) fn str(&mut self) -> Result<&'a str, IndexDecodeError> {
+        let len = self.usize()?;
+        let offset = self.offset;
+        core::str::from_utf8(self.take(len)?).map_err(|_| IndexDecodeError { offset })
+    }
+
+    /// Checks that all of the bytes were read.
+    pub(crate) fn finish(self) -> Result<(), IndexDecodeError> {
+        if self.offset == self.bytes.len() {
+            Ok(())
+        } else {
+            Err(IndexDecodeError {
+                offset: self.offset,
+            })
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], IndexDecodeError> {
+        let error = IndexDecodeError {
+            offset: self.offset,
+        };
+        let end = self.offset.checked_add(len).ok_or(error)?;
+        let bytes = self.bytes.get(self.offset..end).ok_or(error)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+}
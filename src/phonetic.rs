@@ -0,0 +1,584 @@
+use alloc::{string::String, vec, vec::Vec};
+
+/// Finds the [Soundex] code of a string: its first letter, followed by three digits for the
+/// sounds of the consonants after it, so that names that sound alike, like `"Robert"` and
+/// `"Rupert"`, get the same code, here `"R163"`.
+///
+/// Only ASCII letters are coded, ignoring case, and everything else is skipped, so a string
+/// without any has an empty code.
+///
+/// [Soundex]: https://en.wikipedia.org/wiki/Soundex
+pub fn soundex(s: &str) -> String {
+    fn digit(letter: u8) -> Option<u8> {
+        match letter {
+            b'B' | b'F' | b'P' | b'V' => Some(b'1'),
+            b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => Some(b'2'),
+            b'D' | b'T' => Some(b'3'),
+            b'L' => Some(b'4'),
+            b'M' | b'N' => Some(b'5'),
+            b'R' => Some(b'6'),
+            _ => None,
+        }
+    }
+
+    let mut letters = letters(s).into_iter();
+    let first = match letters.next() {
+        Some(first) => first,
+        None => return String::new(),
+    };
+    let mut code = vec![first];
+    let mut last = digit(first);
+    for letter in letters {
+        if code.len() == 4 {
+            break;
+        }
+        match digit(letter) {
+            Some(d) if last != Some(d) => code.push(d),
+            Some(_) => {}
+            // `H` and `W` don't separate consonants with the same digit, but vowels do.
+            None if matches!(letter, b'H' | b'W') => continue,
+            None => {}
+        }
+        last = digit(letter);
+    }
+    code.resize(4, b'0');
+    String::from_utf8(code).unwrap()
+}
+
+/// Finds the [Metaphone] code of a string, which is more precise about English spelling than
+/// [`soundex`], e.g. it codes both `"phone"` and `"fone"` as `"FN"`.
+///
+/// The code keeps a leading vowel, and spells the sounds of the consonants with letters,
+/// and `0` for `th`. Like [`soundex`], this only codes ASCII letters, ignoring case.
+///
+/// [Metaphone]: https://en.wikipedia.org/wiki/Metaphone
+pub fn metaphone(s: &str) -> String {
+    let w = letters(s);
+    let at = |i: usize| w.get(i).copied().unwrap_or(0);
+    let is_vowel = |letter: u8| matches!(letter, b'A' | b'E' | b'I' | b'O' | b'U');
+
+    let mut code = String::new();
+    let mut i = 0;
+    // The index of the only vowel that is coded, after any silent first letter.
+    let mut start = 0;
+    match (at(0), at(1)) {
+        (b'A', b'E') | (b'G', b'N') | (b'K', b'N') | (b'P', b'N') | (b'W', b'R') => {
+            i = 1;
+            start = 1;
+        }
+        (b'X', _) => {
+            code.push('S');
+            i = 1;
+        }
+        (b'W', b'H') => {
+            code.push('W');
+            i = 2;
+        }
+        _ => {}
+    }
+
+    while i < w.len() {
+        let letter = w[i];
+        let prev = if i > 0 { w[i - 1] } else { 0 };
+        let next = at(i + 1);
+        let is_last = i + 1 == w.len();
+        i += 1;
+        if letter == prev && letter != b'C' {
+            continue;
+        }
+
+        let sound = match letter {
+            _ if is_vowel(letter) => Some(letter as char).filter(|_| i - 1 == start),
+            // Silent in a final `mb`.
+            b'B' => Some('B').filter(|_| !(prev == b'M' && is_last)),
+            b'C' if next == b'H' || (next == b'I' && at(i + 1) == b'A') => {
+                Some(if prev == b'S' { 'K' } else { 'X' })
+            }
+            b'C' if matches!(next, b'E' | b'I' | b'Y') => Some('S').filter(|_| prev != b'S'),
+            b'C' => Some('K'),
+            b'D' if next == b'G' && matches!(at(i + 1), b'E' | b'I' | b'Y') => {
+                // The `g` of `dge` is part of the same sound.
+                i += 1;
+                Some('J')
+            }
+            b'D' => Some('T'),
+            b'G' if next == b'H' && i + 1 < w.len() && !is_vowel(at(i + 1)) => None,
+            b'G' if next == b'N' && (i + 1 == w.len() || w[i + 1..] == *b"ED") => None,
+            b'G' if matches!(next, b'E' | b'I' | b'Y') && prev != b'G' => Some('J'),
+            b'G' => Some('K'),
+            b'H' if matches!(prev, b'C' | b'G' | b'P' | b'S' | b'T') => None,
+            b'H' => Some('H').filter(|_| is_vowel(next) || !is_vowel(prev)),
+            b'K' => Some('K').filter(|_| prev != b'C'),
+            b'P' => Some(if next == b'H' { 'F' } else { 'P' }),
+            b'Q' => Some('K'),
+            b'S' if next == b'H' || (next == b'I' && matches!(at(i + 1), b'A' | b'O')) => Some('X'),
+            b'T' if next == b'I' && matches!(at(i + 1), b'A' | b'O') => Some('X'),
+            b'T' if next == b'H' => Some('0'),
+            b'T' => Some('T').filter(|_| !(next == b'C' && at(i + 1) == b'H')),
+            b'V' => Some('F'),
+            b'W' | b'Y' => Some(letter as char).filter(|_| is_vowel(next)),
+            b'X' => {
+                code.push('K');
+                Some('S')
+            }
+            b'Z' => Some('S'),
+            _ => Some(letter as char),
+        };
+        code.extend(sound);
+    }
+    code
+}
+
+/// Finds the two [Double Metaphone] codes of a string: the primary one, for its most common
+/// pronunciation, and an alternate one, e.g. for a name of foreign origin. The two are equal
+/// if there is no alternate pronunciation.
+///
+/// Each code is at most 4 letters long, spelled like those of [`metaphone`], so
+/// `"Smith"` is coded as `("SM0", "XMT")` and `"Schmidt"` as `("XMT", "SMT")`: they sound
+/// alike, as one of the codes of each is the same. Like [`soundex`], this only codes ASCII
+/// letters, ignoring case.
+///
+/// [Double Metaphone]: https://en.wikipedia.org/wiki/Metaphone#Double_Metaphone
+pub fn double_metaphone(s: &str) -> (String, String) {
+    let mut w = letters(s);
+    let len = w.len() as isize;
+    let last = len - 1;
+    // Padded, so that the end of the word reads as a space, as the rules expect.
+    w.extend_from_slice(b"     ");
+    let at = |i: isize| if i < 0 { 0 } else { w[i as usize] };
+    // Whether one of `strs` starts at `i`.
+    let is = |i: isize, strs: &[&str]| {
+        i >= 0
+            && strs
+                .iter()
+                .any(|s| w.get(i as usize..i as usize + s.len()) == Some(s.as_bytes()))
+    };
+    let is_vowel = |i: isize| i < len && matches!(at(i), b'A' | b'E' | b'I' | b'O' | b'U' | b'Y');
+    let slavo_germanic = (0..len).any(|i| is(i, &["W", "K", "CZ"]));
+    let germanic = is(0, &["VAN ", "VON ", "SCH"]);
+
+    let mut primary = String::new();
+    let mut alternate = String::new();
+    let mut add = |main: &str, alt: &str| {
+        primary.push_str(main);
+        alternate.push_str(alt);
+    };
+
+    let mut i = 0;
+    // Silent first letters.
+    if is(0, &["GN", "KN", "PN", "WR", "PS"]) {
+        i = 1;
+    }
+    // As in `"Xavier"`.
+    if at(0) == b'X' {
+        add("S", "S");
+        i = 1;
+    }
+
+    while i < len {
+        let next = at(i + 1);
+        // How many letters the sound takes, if not one.
+        let mut skip = 1;
+        match at(i) {
+            b'A' | b'E' | b'I' | b'O' | b'U' | b'Y' => {
+                if i == 0 {
+                    add("A", "A");
+                }
+            }
+            b'B' => {
+                add("P", "P");
+                if next == b'B' {
+                    skip = 2;
+                }
+            }
+            b'C' => {
+                skip = 2;
+                if i > 1
+                    && !is_vowel(i - 2)
+                    && is(i - 1, &["ACH"])
+                    && at(i + 2) != b'I'
+                    && (at(i + 2) != b'E' || is(i - 2, &["BACHER", "MACHER"]))
+                {
+                    // Germanic.
+                    add("K", "K");
+                } else if i == 0 && is(i, &["CAESAR"]) {
+                    add("S", "S");
+                } else if is(i, &["CHIA"]) {
+                    // Italian, as in `"chianti"`.
+                    add("K", "K");
+                } else if is(i, &["CH"]) {
+                    if i > 0 && is(i, &["CHAE"]) {
+                        // As in `"Michael"`.
+                        add("K", "X");
+                    } else if i == 0
+                        && is(i + 1, &["HARAC", "HARIS", "HOR", "HYM", "HIA", "HEM"])
+                        && !is(0, &["CHORE"])
+                    {
+                        // Greek roots, as in `"chemistry"` and `"chorus"`.
+                        add("K", "K");
+                    } else if germanic
+                        || is(i - 2, &["ORCHES", "ARCHIT", "ORCHID"])
+                        || is(i + 2, &["T", "S"])
+                        || ((i == 0 || is(i - 1, &["A", "O", "U", "E"]))
+                            && is(i + 2, &["L", "R", "N", "M", "B", "H", "F", "V", "W", " "]))
+                    {
+                        add("K", "K");
+                    } else if i == 0 {
+                        add("X", "X");
+                    } else if is(0, &["MC"]) {
+                        add("K", "K");
+                    } else {
+                        add("X", "K");
+                    }
+                } else if is(i, &["CZ"]) && !is(i - 2, &["WICZ"]) {
+                    add("S", "X");
+                } else if is(i + 1, &["CIA"]) {
+                    // As in `"focaccia"`.
+                    add("X", "X");
+                    skip = 3;
+                } else if is(i, &["CC"]) && !(i == 1 && at(0) == b'M') {
+                    if is(i + 2, &["I", "E", "H"]) && !is(i + 2, &["HU"]) {
+                        if (i == 1 && at(0) == b'A') || is(i - 1, &["UCCEE", "UCCES"]) {
+                            // As in `"accident"` and `"succeed"`.
+                            add("KS", "KS");
+                        } else {
+                            // Italian, as in `"bacci"`.
+                            add("X", "X");
+                        }
+                        skip = 3;
+                    } else {
+                        add("K", "K");
+                    }
+                } else if is(i, &["CK", "CG", "CQ"]) {
+                    add("K", "K");
+                } else if is(i, &["CI", "CE", "CY"]) {
+                    if is(i, &["CIO", "CIE", "CIA"]) {
+                        add("S", "X");
+                    } else {
+                        add("S", "S");
+                    }
+                } else {
+                    add("K", "K");
+                    skip = if is(i + 1, &["C", "K", "Q"]) && !is(i + 1, &["CE", "CI"]) {
+                        2
+                    } else {
+                        1
+                    };
+                }
+            }
+            b'D' => {
+                if is(i, &["DG"]) {
+                    if is(i + 2, &["I", "E", "Y"]) {
+                        // As in `"edge"`.
+                        add("J", "J");
+                        skip = 3;
+                    } else {
+                        // As in `"Edgar"`.
+                        add("TK", "TK");
+                        skip = 2;
+                    }
+                } else {
+                    add("T", "T");
+                    if is(i, &["DT", "DD"]) {
+                        skip = 2;
+                    }
+                }
+            }
+            b'G' if next == b'H' => {
+                skip = 2;
+                if i > 0 && !is_vowel(i - 1) {
+                    add("K", "K");
+                } else if i == 0 {
+                    // As in `"Ghislane"`.
+                    if at(i + 2) == b'I' {
+                        add("J", "J");
+                    } else {
+                        add("K", "K");
+                    }
+                } else if (i > 1 && is(i - 2, &["B", "H", "D"]))
+                    || (i > 2 && is(i - 3, &["B", "H", "D"]))
+                    || (i > 3 && is(i - 4, &["B", "H"]))
+                {
+                    // Silent, as in `"hugh"` and `"bough"`.
+                } else if i > 2 && at(i - 1) == b'U' && is(i - 3, &["C", "G", "L", "R", "T"]) {
+                    // As in `"laugh"` and `"tough"`.
+                    add("F", "F");
+                } else if at(i - 1) != b'I' {
+                    add("K", "K");
+                }
+            }
+            b'G' if next == b'N' => {
+                skip = 2;
+                if i == 1 && is_vowel(0) && !slavo_germanic {
+                    add("KN", "N");
+                } else if !is(i + 2, &["EY"]) && !slavo_germanic {
+                    // Not as in `"Cagney"`.
+                    add("N", "KN");
+                } else {
+                    add("KN", "KN");
+                }
+            }
+            b'G' => {
+                skip = 2;
+                if is(i + 1, &["LI"]) && !slavo_germanic {
+                    // As in `"tagliaro"`.
+                    add("KL", "L");
+                } else if (i == 0
+                    && (next == b'Y'
+                        || is(
+                            i + 1,
+                            &[
+                                "ES", "EP", "EB", "EL", "EY", "IB", "IL", "IN", "IE", "EI", "ER",
+                            ],
+                        )))
+                    || ((is(i + 1, &["ER"]) || next == b'Y')
+                        && !is(0, &["DANGER", "RANGER", "MANGER"])
+                        && !is(i - 1, &["E", "I", "RGY", "OGY"]))
+                {
+                    add("K", "J");
+                } else if is(i + 1, &["E", "I", "Y"]) || is(i - 1, &["AGGI", "OGGI"]) {
+                    if germanic || is(i + 1, &["ET"]) {
+                        add("K", "K");
+                    } else if is(i + 1, &["IER "]) {
+                        // French.
+                        add("J", "J");
+                    } else {
+                        add("J", "K");
+                    }
+                } else {
+                    add("K", "K");
+                    if next != b'G' {
+                        skip = 1;
+                    }
+                }
+            }
+            b'H' => {
+                // Only between vowels, or after none.
+                if (i == 0 || is_vowel(i - 1)) && is_vowel(i + 1) {
+                    add("H", "H");
+                    skip = 2;
+                }
+            }
+            b'J' => {
+                if is(i, &["JOSE"]) || is(0, &["SAN "]) {
+                    // Spanish.
+                    if (i == 0 && at(i + 4) == b' ') || is(0, &["SAN "]) {
+                        add("H", "H");
+                    } else {
+                        add("J", "H");
+                    }
+                } else {
+                    if i == 0 {
+                        add("J", "A");
+                    } else if is_vowel(i - 1) && !slavo_germanic && matches!(next, b'A' | b'O') {
+                        // Spanish, as in `"bajador"`.
+                        add("J", "H");
+                    } else if i == last {
+                        add("J", "");
+                    } else if !is(i + 1, &["L", "T", "K", "S", "N", "M", "B", "Z"])
+                        && !is(i - 1, &["S", "K", "L"])
+                    {
+                        add("J", "J");
+                    }
+                    if next == b'J' {
+                        skip = 2;
+                    }
+                }
+            }
+            b'L' => {
+                if next == b'L' {
+                    skip = 2;
+                    if (i == len - 3 && is(i - 1, &["ILLO", "ILLA", "ALLE"]))
+                        || ((is(last - 1, &["AS", "OS"]) || is(last, &["A", "O"]))
+                            && is(i - 1, &["ALLE"]))
+                    {
+                        // Spanish, as in `"cabrillo"`.
+                        add("L", "");
+                    } else {
+                        add("L", "L");
+                    }
+                } else {
+                    add("L", "L");
+                }
+            }
+            b'M' => {
+                add("M", "M");
+                if (is(i - 1, &["UMB"]) && (i + 1 == last || is(i + 2, &["ER"]))) || next == b'M' {
+                    skip = 2;
+                }
+            }
+            b'P' => {
+                if next == b'H' {
+                    add("F", "F");
+                    skip = 2;
+                } else {
+                    add("P", "P");
+                    // As in `"Campbell"`.
+                    if matches!(next, b'P' | b'B') {
+                        skip = 2;
+                    }
+                }
+            }
+            b'R' => {
+                if i == last && !slavo_germanic && is(i - 2, &["IE"]) && !is(i - 4, &["ME", "MA"]) {
+                    // French, as in `"Rogier"`.
+                    add("", "R");
+                } else {
+                    add("R", "R");
+                }
+                if next == b'R' {
+                    skip = 2;
+                }
+            }
+            b'S' => {
+                if is(i - 1, &["ISL", "YSL"]) {
+                    // Silent, as in `"island"`.
+                } else if i == 0 && is(i, &["SUGAR"]) {
+                    add("X", "S");
+                } else if is(i, &["SH"]) {
+                    if is(i + 1, &["HEIM", "HOEK", "HOLM", "HOLZ"]) {
+                        add("S", "S");
+                    } else {
+                        add("X", "X");
+                    }
+                    skip = 2;
+                } else if is(i, &["SIO", "SIA"]) {
+                    if slavo_germanic {
+                        add("S", "S");
+                    } else {
+                        add("S", "X");
+                    }
+                    skip = 3;
+                } else if (i == 0 && is(i + 1, &["M", "N", "L", "W"])) || next == b'Z' {
+                    // So that `"Smith"` sounds like `"Schmidt"`.
+                    add("S", "X");
+                    if next == b'Z' {
+                        skip = 2;
+                    }
+                } else if is(i, &["SC"]) {
+                    skip = 3;
+                    if at(i + 2) == b'H' {
+                        if is(i + 3, &["ER", "EN"]) {
+                            add("X", "SK");
+                        } else if is(i + 3, &["OO", "UY", "ED", "EM"]) {
+                            // Dutch, as in `"school"`.
+                            add("SK", "SK");
+                        } else if i == 0 && !is_vowel(3) && at(3) != b'W' {
+                            add("X", "S");
+                        } else {
+                            add("X", "X");
+                        }
+                    } else if is(i + 2, &["I", "E", "Y"]) {
+                        add("S", "S");
+                    } else {
+                        add("SK", "SK");
+                    }
+                } else {
+                    if i == last && is(i - 2, &["AI", "OI"]) {
+                        // French, as in `"Artois"`.
+                        add("", "S");
+                    } else {
+                        add("S", "S");
+                    }
+                    if matches!(next, b'S' | b'Z') {
+                        skip = 2;
+                    }
+                }
+            }
+            b'T' => {
+                if is(i, &["TION", "TIA", "TCH"]) {
+                    add("X", "X");
+                    skip = 3;
+                } else if is(i, &["TH", "TTH"]) {
+                    if is(i + 2, &["OM", "AM"]) || germanic {
+                        // As in `"Thomas"`.
+                        add("T", "T");
+                    } else {
+                        add("0", "T");
+                    }
+                    skip = 2;
+                } else {
+                    add("T", "T");
+                    if matches!(next, b'T' | b'D') {
+                        skip = 2;
+                    }
+                }
+            }
+            b'W' => {
+                if is(i, &["WR"]) {
+                    add("R", "R");
+                    skip = 2;
+                } else {
+                    if i == 0 && is_vowel(i + 1) {
+                        add("A", "F");
+                    } else if i == 0 && next == b'H' {
+                        add("A", "A");
+                    }
+                    if (i == last && is_vowel(i - 1))
+                        || is(i - 1, &["EWSKI", "EWSKY", "OWSKI", "OWSKY"])
+                        || is(0, &["SCH"])
+                    {
+                        // As in `"Arnow"`, which sounds like `"Arnoff"`.
+                        add("", "F");
+                    } else if is(i, &["WICZ", "WITZ"]) {
+                        // Polish.
+                        add("TS", "FX");
+                        skip = 4;
+                    }
+                }
+            }
+            b'X' => {
+                // Not French, as in `"Breaux"`.
+                if !(i == last && (is(i - 3, &["IAU", "EAU"]) || is(i - 2, &["AU", "OU"]))) {
+                    add("KS", "KS");
+                }
+                if matches!(next, b'C' | b'X') {
+                    skip = 2;
+                }
+            }
+            b'Z' => {
+                if next == b'H' {
+                    // Pinyin, as in `"Zhao"`.
+                    add("J", "J");
+                    skip = 2;
+                } else {
+                    if is(i + 1, &["ZO", "ZI", "ZA"])
+                        || (slavo_germanic && i > 0 && at(i - 1) != b'T')
+                    {
+                        add("S", "TS");
+                    } else {
+                        add("S", "S");
+                    }
+                    if next == b'Z' {
+                        skip = 2;
+                    }
+                }
+            }
+            // `F`, `K`, `N`, `Q` and `V`, which sound the same doubled.
+            letter => {
+                let sound = match letter {
+                    b'F' | b'V' => "F",
+                    b'K' | b'Q' => "K",
+                    _ => "N",
+                };
+                add(sound, sound);
+                if next == letter {
+                    skip = 2;
+                }
+            }
+        }
+        i += skip;
+    }
+
+    primary.truncate(4);
+    alternate.truncate(4);
+    (primary, alternate)
+}
+
+/// The ASCII letters of `s`, in upper case.
+fn letters(s: &str) -> Vec<u8> {
+    s.bytes()
+        .filter(u8::is_ascii_alphabetic)
+        .map(|b| b.to_ascii_uppercase())
+        .collect()
+}
@@ -0,0 +1,257 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::{
+    case_fold, default_match_distance, edit_distance, edit_distance_in, is_folded_match,
+    match_distance, sort_by_words, EditDistanceBuffer, MatchOptions,
+};
+
+/// Which of the kinds of match of [`find_best_match_for_name`] a [`Match`] is, best first,
+/// and the phases to look for them in [`MatchOptions::phases`].
+///
+/// [`find_best_match_for_name`]: crate::find_best_match_for_name
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum MatchPhase {
+    /// The candidate equals the lookup, contains it, or is contained in it, ignoring case.
+    CaseInsensitive,
+    /// The candidate is within the limit of the lookup by [`edit_distance`], or by the
+    /// [`MatchOptions::scorer`].
+    EditDistance,
+    /// The candidate is made of the same `_`-separated words as the lookup, in any order.
+    SortedWords,
+    /// The lookup is an abbreviation of the candidate, e.g. `hm` of `HashMap`, with the best
+    /// [`abbreviation_score`](crate::abbreviation_score). This is never matched by
+    /// [`find_best_matches`], and only by [`MatchOptions`] that list it in their phases.
+    Abbreviation,
+}
+
+/// A candidate found by [`find_best_matches`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Match<'a> {
+    pub candidate: &'a str,
+    /// The [`edit_distance`] between the lookup and the candidate, whatever the phase.
+    pub score: usize,
+    pub phase: MatchPhase,
+}
+
+/// Like [`find_best_match_for_name`](crate::find_best_match_for_name), but returns up to `n`
+/// of the matching candidates, best first, e.g. for a "did you mean one of" suggestion.
+///
+/// Every candidate is matched in the best phase it can be, and the matches are sorted by
+/// phase, then by score, then lexicographically. So the first match is a close one of the
+/// best phase, which is not always the one `find_best_match_for_name` returns: that is the
+/// first or last candidate of its phase in `candidates` for the first and third phase.
+///
+/// ```
+/// use rustc_edit_distance::{find_best_matches, MatchPhase};
+///
+/// let candidates = ["fooo", "bar", "foob", "FOO"];
+/// let matches = find_best_matches(&candidates, "foox", Some(1), 3);
+/// let found = matches.iter().map(|m| (m.candidate, m.phase)).collect::<Vec<_>>();
+/// assert_eq!(
+///     found,
+///     [
+///         ("FOO", MatchPhase::CaseInsensitive),
+///         ("foob", MatchPhase::EditDistance),
+///         ("fooo", MatchPhase::EditDistance),
+///     ]
+/// );
+/// ```
+pub fn find_best_matches<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+    n: usize,
+) -> Vec<Match<'a>> {
+    ranked_matches(candidates, lookup, dist, n)
+        .into_iter()
+        .map(|(_, m)| m)
+        .collect()
+}
+
+/// Lazily matches a stream of candidates against `lookup` by [`edit_distance`], e.g. the
+/// millions of lines of a file, without collecting them.
+///
+/// Each match yielded is at least as close as every one before it: the limit starts at
+/// `threshold`, or the default of [`default_match_distance`] if it is `None`, and drops to the
+/// distance of every match, so closer candidates are found by ever cheaper comparisons. So the
+/// last match is the last of the closest candidates, and no match can beat one at distance 0.
+/// Callers can stop reading the candidates as soon as they have seen enough.
+///
+/// There are no other kinds of match, as in [`find_all_matches_within`]. Each match has the
+/// phase [`MatchPhase::EditDistance`].
+///
+/// ```
+/// use rustc_edit_distance::matches_iter;
+///
+/// let lines = "lenth\nwidth\nlength\nlenght\nheight";
+/// let found = matches_iter(lines.lines(), "lenght", None).map(|m| (m.candidate, m.score));
+/// assert_eq!(found.collect::<Vec<_>>(), [("lenth", 2), ("length", 1), ("lenght", 0)]);
+///
+/// // Stops reading the lines at the exact match.
+/// let exact = matches_iter(lines.lines(), "length", None).find(|m| m.score == 0);
+/// assert_eq!(exact.map(|m| m.candidate), Some("length"));
+/// ```
+pub fn matches_iter<'a, I>(
+    candidates: I,
+    lookup: &str,
+    threshold: Option<usize>,
+) -> impl Iterator<Item = Match<'a>>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    // Owned, so that the iterator only borrows from the candidates.
+    let lookup = String::from(lookup);
+    let mut limit = threshold.unwrap_or_else(|| default_match_distance(&lookup));
+    let mut buffer = EditDistanceBuffer::new();
+    candidates.into_iter().filter_map(move |candidate| {
+        let score = edit_distance_in(&mut buffer, &lookup, candidate, limit)?;
+        limit = score;
+        Some(Match {
+            candidate,
+            score,
+            phase: MatchPhase::EditDistance,
+        })
+    })
+}
+
+/// Like [`find_best_matches`], but returns the indices of the matches in `candidates`, e.g.
+/// to map them back to the items they are the names of. The candidates can be of any string
+/// type, e.g. `String` or `Rc<str>`.
+pub fn find_best_match_indices<S: AsRef<str>>(
+    candidates: &[S],
+    lookup: &str,
+    dist: Option<usize>,
+    n: usize,
+) -> Vec<usize> {
+    let candidates = candidates.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    ranked_matches(&candidates, lookup, dist, n)
+        .into_iter()
+        .map(|(i, _)| i)
+        .collect()
+}
+
+/// Finds every candidate within `max_dist` of `lookup` by [`edit_distance`], with its
+/// distance, e.g. to let the user pick one of them.
+///
+/// The matches are sorted by distance, and candidates at the same distance keep their order in
+/// `candidates`. Unlike [`find_best_matches`], there are no other kinds of match, so a
+/// candidate that merely contains `lookup` isn't one unless it is close enough.
+///
+/// ```
+/// use rustc_edit_distance::find_all_matches_within;
+///
+/// let candidates = ["foo", "fob", "bar", "fo", "food"];
+/// assert_eq!(
+///     find_all_matches_within(&candidates, "foo", 1),
+///     [("foo", 0), ("fob", 1), ("fo", 1), ("food", 1)]
+/// );
+/// ```
+pub fn find_all_matches_within<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    max_dist: usize,
+) -> Vec<(&'a str, usize)> {
+    find_all_matches_within_with(
+        candidates,
+        lookup,
+        &MatchOptions::new().max_distance(max_dist),
+    )
+}
+
+/// Like [`find_all_matches_within`], but with the distance, and how it is computed, set in
+/// `options`, e.g. to score candidates by
+/// [`edit_distance_with_substrings`](crate::edit_distance_with_substrings) with
+/// [`MatchOptions::use_substrings`].
+///
+/// Candidates are always scored as by [`Scorer::EditDistance`](crate::Scorer::EditDistance),
+/// whatever the [`MatchOptions::scorer`], and the options for the other kinds of match, like
+/// the separators, are not used.
+pub fn find_all_matches_within_with<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    options: &MatchOptions,
+) -> Vec<(&'a str, usize)> {
+    let lookup_normalized = options.normalize(lookup);
+    let lookup = &*lookup_normalized;
+    let lookup_folded = case_fold(lookup);
+    let dist = options.limit(lookup);
+
+    let mut matches = candidates
+        .iter()
+        .filter_map(|&c| {
+            let c_normalized = options.normalize(c);
+            let d = if options.case_insensitive {
+                match_distance(&lookup_folded, &case_fold(&c_normalized), dist, options)
+            } else {
+                match_distance(lookup, &c_normalized, dist, options)
+            }?;
+            Some((c, d))
+        })
+        .collect::<Vec<_>>();
+    // Stable, so ties keep their order.
+    matches.sort_by_key(|&(_, d)| d);
+    matches
+}
+
+/// The best `n` matches, with their indices in `candidates`.
+fn ranked_matches<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+    n: usize,
+) -> Vec<(usize, Match<'a>)> {
+    let mut matches = match_all(candidates, lookup, dist);
+    matches.sort_by_key(|(_, m)| (m.phase, m.score, m.candidate));
+    matches.truncate(n);
+    matches
+}
+
+/// Matches every candidate against `lookup` in the best phase it can be, in order.
+fn match_all<'a>(
+    candidates: &[&'a str],
+    lookup: &str,
+    dist: Option<usize>,
+) -> Vec<(usize, Match<'a>)> {
+    // An empty lookup only matches an empty candidate, as in `find_best_match_for_name`.
+    if lookup.is_empty() {
+        return candidates
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_empty())
+            .map(|(i, &c)| {
+                let m = Match {
+                    candidate: c,
+                    score: 0,
+                    phase: MatchPhase::CaseInsensitive,
+                };
+                (i, m)
+            })
+            .collect();
+    }
+
+    let dist = dist.unwrap_or_else(|| default_match_distance(lookup));
+    let lookup_folded = case_fold(lookup);
+    let lookup_sorted_by_words = sort_by_words(lookup, &['_']);
+    candidates
+        .iter()
+        .enumerate()
+        .filter_map(|(i, &c)| {
+            let (phase, score) = if is_folded_match(&case_fold(c), &lookup_folded) {
+                (MatchPhase::CaseInsensitive, None)
+            } else if let Some(d) = edit_distance(lookup, c, dist) {
+                (MatchPhase::EditDistance, Some(d))
+            } else if sort_by_words(c, &['_']) == lookup_sorted_by_words {
+                (MatchPhase::SortedWords, None)
+            } else {
+                return None;
+            };
+            let m = Match {
+                candidate: c,
+                score: score.unwrap_or_else(|| edit_distance(lookup, c, usize::MAX).unwrap()),
+                phase,
+            };
+            Some((i, m))
+        })
+        .collect()
+}
@@ -0,0 +1,129 @@
+//! The table of `dp` for unit costs, filled in one anti-diagonal at a time.
+//!
+//! Every cell of an anti-diagonal only depends on the cells of the four anti-diagonals before
+//! it, so each anti-diagonal is a loop without dependencies between its iterations, which the
+//! compiler vectorizes. On x86-64 the loop is also compiled for AVX2, which is used if the CPU
+//! turns out to have it, while the portable version is kept as the fallback.
+
+use core::cmp;
+
+use crate::small_vec::{SmallVec, INLINE_LEN};
+
+/// Computes the same distance as `dp` with unit costs, for what `trim` left over: a nonempty
+/// `b` no longer than `a`. The distances are kept as `u32`, so the lengths must add up to less
+/// than `u32::MAX`.
+pub(crate) fn edit_distance<T: Copy + Into<u32>>(a: &[T], b: &[T], limit: usize) -> Option<usize> {
+    let mut buffer = SmallVec::<u32, { 5 * (INLINE_LEN + 1) }>::from_elem(0, buffer_len(a));
+    edit_distance_in(&mut buffer, a, b, limit)
+}
+
+/// Like [`edit_distance`], but keeps the anti-diagonals in `buffer`, which is at least
+/// [`buffer_len`] long.
+pub(crate) fn edit_distance_in<T: Copy + Into<u32>>(
+    buffer: &mut [u32],
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    if is_x86_feature_detected!("avx2") {
+        // SAFETY: The CPU supports AVX2, which is all that `edit_distance_avx2` requires.
+        return unsafe { edit_distance_avx2(buffer, a, b, limit) };
+    }
+    diagonals(buffer, a, b, limit)
+}
+
+/// How many cells the anti-diagonals of [`edit_distance_in`] take.
+pub(crate) fn buffer_len<T>(a: &[T]) -> usize {
+    5 * (a.len() + 1)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn edit_distance_avx2<T: Copy + Into<u32>>(
+    buffer: &mut [u32],
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    diagonals(buffer, a, b, limit)
+}
+
+#[inline(always)]
+fn diagonals<T: Copy + Into<u32>>(
+    buffer: &mut [u32],
+    a: &[T],
+    b: &[T],
+    limit: usize,
+) -> Option<usize> {
+    let (n, m) = (a.len(), b.len());
+    debug_assert!(m > 0 && m <= n && n + m < u32::MAX as usize);
+
+    // Anti-diagonal `d` holds the cells `(i, d - i)`, indexed by `i`. Five of them are needed,
+    // from `d - 4`, for transpositions, to `d`, and they all live in one buffer. Every cell is
+    // written before it is read, so the buffer can hold anything.
+    let len = n + 1;
+    let (d0, rest) = buffer.split_at_mut(len);
+    let (d1, rest) = rest.split_at_mut(len);
+    let (d2, rest) = rest.split_at_mut(len);
+    let (d3, d4) = rest.split_at_mut(len);
+    let mut diagonals = [d0, d1, d2, d3, d4];
+
+    for d in 0..=n + m {
+        // `d` reuses the memory of `d - 5`, and is followed by `d - 1` to `d - 4`.
+        diagonals.rotate_right(1);
+        let [current, prev, prev_prev, _, prev4] = &mut diagonals;
+
+        if d <= m {
+            current[0] = d as u32;
+        }
+        if d <= n {
+            current[d] = d as u32;
+        }
+        // The cells that are neither in the first row nor in the first column.
+        let lo = cmp::max(1, d.saturating_sub(m));
+        let hi = cmp::min(n, d.saturating_sub(1));
+        if lo > hi {
+            continue;
+        }
+
+        // `b[j - 1]` for `j = d - i`, as `i` goes from `lo` to `hi`.
+        let b_rev = b[d - hi - 1..d - lo].iter().rev();
+        for (((((cell, &del), &ins), &sub), &x), &y) in current[lo..=hi]
+            .iter_mut()
+            .zip(&prev[lo - 1..hi])
+            .zip(&prev[lo..=hi])
+            .zip(&prev_prev[lo - 1..hi])
+            .zip(&a[lo - 1..hi])
+            .zip(b_rev)
+        {
+            let substitution = sub + u32::from(x.into() != y.into());
+            // deletion, insertion, substitution
+            *cell = cmp::min(cmp::min(del, ins) + 1, substitution);
+        }
+
+        // Transpositions, where both `i` and `j` are at least 2.
+        let lo = cmp::max(lo, 2);
+        let hi = cmp::min(hi, d.saturating_sub(2));
+        if lo > hi {
+            continue;
+        }
+        let b_rev = b[d - hi - 1..d - lo].iter().rev();
+        let b_rev_before = b[d - hi - 2..d - lo - 1].iter().rev();
+        for (((((cell, &tr), &x), &x_before), &y), &y_before) in current[lo..=hi]
+            .iter_mut()
+            .zip(&prev4[lo - 2..hi - 1])
+            .zip(&a[lo - 1..hi])
+            .zip(&a[lo - 2..hi - 1])
+            .zip(b_rev)
+            .zip(b_rev_before)
+        {
+            let swapped = x.into() == y_before.into() && x_before.into() == y.into();
+            *cell = cmp::min(*cell, if swapped { tr + 1 } else { u32::MAX });
+        }
+    }
+
+    // The last anti-diagonal was rotated to the front.
+    let distance = diagonals[0][n] as usize;
+    (distance <= limit).then_some(distance)
+}
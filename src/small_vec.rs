@@ -0,0 +1,67 @@
+use alloc::{vec, vec::Vec};
+
+use core::ops::{Deref, DerefMut};
+
+/// How many chars, or cells of a row, are kept on the stack before spilling to the heap.
+///
+/// Most identifiers are much shorter than this, so comparing them doesn't allocate at all.
+pub(crate) const INLINE_LEN: usize = 64;
+
+/// A vector that keeps up to `N` elements inline, and moves to the heap beyond that.
+pub(crate) enum SmallVec<T, const N: usize> {
+    Inline { buf: [T; N], len: usize },
+    Heap(Vec<T>),
+}
+
+impl<T: Copy + Default, const N: usize> SmallVec<T, N> {
+    pub(crate) fn from_elem(elem: T, len: usize) -> Self {
+        if len <= N {
+            SmallVec::Inline {
+                buf: [elem; N],
+                len,
+            }
+        } else {
+            SmallVec::Heap(vec![elem; len])
+        }
+    }
+}
+
+impl<T: Copy + Default, const N: usize> FromIterator<T> for SmallVec<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut iter = iter.into_iter();
+        let mut buf = [T::default(); N];
+        let mut len = 0;
+        for x in iter.by_ref() {
+            if len == N {
+                let mut vec = Vec::with_capacity(N * 2);
+                vec.extend_from_slice(&buf);
+                vec.push(x);
+                vec.extend(iter);
+                return SmallVec::Heap(vec);
+            }
+            buf[len] = x;
+            len += 1;
+        }
+        SmallVec::Inline { buf, len }
+    }
+}
+
+impl<T, const N: usize> Deref for SmallVec<T, N> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        match self {
+            SmallVec::Inline { buf, len } => &buf[..*len],
+            SmallVec::Heap(vec) => vec,
+        }
+    }
+}
+
+impl<T, const N: usize> DerefMut for SmallVec<T, N> {
+    fn deref_mut(&mut self) -> &mut [T] {
+        match self {
+            SmallVec::Inline { buf, len } => &mut buf[..*len],
+            SmallVec::Heap(vec) => vec,
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::{cmp::Reverse, fmt};
+
+use crate::SymSpellIndex;
+
+/// A spell checker of the words of a dictionary, each with how often it is used, that
+/// corrects misspelled words to the closest words, the most common first.
+///
+/// Lookups go through a [`SymSpellIndex`] of the words, so they hardly get slower with the
+/// size of the dictionary, for an index that takes much more memory than the words.
+///
+/// ```
+/// use rustc_edit_distance::SpellChecker;
+///
+/// let checker = SpellChecker::new(&[("the", 500), ("then", 40), ("they", 90), ("theme", 5)], 2);
+/// assert!(checker.check("they"));
+/// assert!(!checker.check("thy"));
+/// let corrections = checker.correct("thy", 3);
+/// let words = corrections.iter().map(|c| c.word).collect::<Vec<_>>();
+/// assert_eq!(words, ["the", "they", "then"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpellChecker<'a> {
+    frequencies: BTreeMap<&'a str, u64>,
+    index: SymSpellIndex<'a>,
+}
+
+/// A correction of a word by [`SpellChecker::correct`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Correction<'a> {
+    /// The word of the dictionary.
+    pub word: &'a str,
+    /// The [`edit_distance`](crate::edit_distance) between the misspelled word and `word`.
+    pub distance: usize,
+    /// How often `word` is used, as given to the spell checker.
+    pub frequency: u64,
+}
+
+impl<'a> SpellChecker<'a> {
+    /// Builds a spell checker of `words` and their frequencies, which corrects words within
+    /// `max_dist` of them. The frequencies of a word given more than once add up.
+    pub fn new(words: &[(&'a str, u64)], max_dist: usize) -> Self {
+        let mut frequencies = BTreeMap::new();
+        for &(word, frequency) in words {
+            let total: &mut u64 = frequencies.entry(word).or_default();
+            *total = total.saturating_add(frequency);
+        }
+        let index =
+            SymSpellIndex::build(&frequencies.keys().copied().collect::<Vec<_>>(), max_dist);
+        SpellChecker { frequencies, index }
+    }
+
+    /// Builds a spell checker of a word list with one word per line, optionally followed by
+    /// whitespace and its frequency, e.g. `"the 23135851162"`, which is 1 if it is left out.
+    /// Blank lines are skipped.
+    ///
+    /// ```
+    /// use rustc_edit_distance::SpellChecker;
+    ///
+    /// let checker = SpellChecker::from_word_list("the 500\nthey 90\nthem\n", 1).unwrap();
+    /// assert_eq!(checker.correct("thm", 1)[0].word, "the");
+    /// assert!(SpellChecker::from_word_list("the lots", 1).is_err());
+    /// ```
+    pub fn from_word_list(list: &'a str, max_dist: usize) -> Result<Self, WordListError> {
+        let mut words = Vec::new();
+        for (i, line) in list.lines().enumerate() {
+            let mut fields = line.split_whitespace();
+            let word = match fields.next() {
+                Some(word) => word,
+                None => continue,
+            };
+            let frequency = match (fields.next(), fields.next()) {
+                (None, _) => 1,
+                (Some(frequency), None) => frequency
+                    .parse()
+                    .map_err(|_| WordListError { line: i + 1 })?,
+                (Some(_), Some(_)) => return Err(WordListError { line: i + 1 }),
+            };
+            words.push((word, frequency));
+        }
+        Ok(SpellChecker::new(&words, max_dist))
+    }
+
+    /// Returns the number of distinct words in the dictionary.
+    pub fn len(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    /// Returns `true` if the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.frequencies.is_empty()
+    }
+
+    /// Returns `true` if `word` is in the dictionary, exactly as it is spelled there.
+    pub fn check(&self, word: &str) -> bool {
+        self.frequencies.contains_key(word)
+    }
+
+    /// Returns how often `word` is used, or `None` if it isn't in the dictionary.
+    pub fn frequency(&self, word: &str) -> Option<u64> {
+        self.frequencies.get(word).copied()
+    }
+
+    /// Finds up to `n` words of the dictionary within the `max_dist` the spell checker was
+    /// built for of `word`, the closest first, then the most common, then lexicographically.
+    ///
+    /// A word that is in the dictionary is its own first correction, at distance 0.
+    pub fn correct(&self, word: &str, n: usize) -> Vec<Correction<'a>> {
+        let mut corrections = self
+            .index
+            .lookup(word)
+            .into_iter()
+            .map(|(word, distance)| Correction {
+                word,
+                distance,
+                frequency: self.frequencies[word],
+            })
+            .collect::<Vec<_>>();
+        corrections.sort_unstable_by_key(|c| (c.distance, Reverse(c.frequency), c.word));
+        corrections.truncate(n);
+        corrections
+    }
+}
+
+/// An error returned by [`SpellChecker::from_word_list`] for a line that isn't a word
+/// optionally followed by its frequency.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WordListError {
+    /// The number of the line, starting at 1.
+    pub line: usize,
+}
+
+impl fmt::Display for WordListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid word list entry on line {}", self.line)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for WordListError {}
@@ -0,0 +1,161 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::{alignment, find_best_matches, AlignedSpan};
+
+/// A "did you mean" message for a name that wasn't found, listing the names that were meant
+/// most likely, as it is shown by [`Display`](fmt::Display).
+///
+/// Each method changes one part of the message:
+///
+/// ```
+/// use rustc_edit_distance::Suggestion;
+///
+/// let suggestion = Suggestion::new("lenght", &["length"]);
+/// assert_eq!(suggestion.to_string(), "did you mean `length`?");
+///
+/// let suggestion = Suggestion::new("fo", &["foo", "for", "fox", "of"]).max_listed(2);
+/// assert_eq!(suggestion.to_string(), "did you mean one of `foo`, `for` or 2 others?");
+///
+/// let suggestion = Suggestion::new("fo", &["foo", "for"])
+///     .prefixes("a similar name exists:", "similar names exist:")
+///     .quotes("'", "'")
+///     .suffix("");
+/// assert_eq!(suggestion.to_string(), "similar names exist: 'foo' or 'for'");
+/// ```
+///
+/// With no names to suggest, the message is empty.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Suggestion<'a> {
+    lookup: &'a str,
+    matches: Vec<&'a str>,
+    max_listed: usize,
+    single: &'a str,
+    multiple: &'a str,
+    open: &'a str,
+    close: &'a str,
+    suffix: &'a str,
+}
+
+impl<'a> Suggestion<'a> {
+    /// Suggests `matches`, best first, for `lookup`.
+    pub fn new(lookup: &'a str, matches: &[&'a str]) -> Self {
+        Suggestion {
+            lookup,
+            matches: matches.to_vec(),
+            max_listed: 5,
+            single: "did you mean",
+            multiple: "did you mean one of",
+            open: "`",
+            close: "`",
+            suffix: "?",
+        }
+    }
+
+    /// Suggests the candidates that best match `lookup`, as found by
+    /// [`find_best_matches`](crate::find_best_matches).
+    ///
+    /// ```
+    /// use rustc_edit_distance::Suggestion;
+    ///
+    /// let candidates = ["push", "pop", "insert", "remove"];
+    /// let suggestion = Suggestion::find(&candidates, "psuh", None);
+    /// assert_eq!(suggestion.to_string(), "did you mean `push`?");
+    /// ```
+    pub fn find(candidates: &[&'a str], lookup: &'a str, dist: Option<usize>) -> Self {
+        let matches = find_best_matches(candidates, lookup, dist, usize::MAX)
+            .into_iter()
+            .map(|m| m.candidate)
+            .collect::<Vec<_>>();
+        Suggestion::new(lookup, &matches)
+    }
+
+    /// The most names that are listed. The others are only counted, as in
+    /// `` `a`, `b` or 3 others``. Defaults to 5, and is at least 1.
+    pub fn max_listed(mut self, max_listed: usize) -> Self {
+        self.max_listed = max_listed.max(1);
+        self
+    }
+
+    /// What comes before a single name, and before a list of names. Defaults to
+    /// `"did you mean"` and `"did you mean one of"`.
+    pub fn prefixes(mut self, single: &'a str, multiple: &'a str) -> Self {
+        self.single = single;
+        self.multiple = multiple;
+        self
+    }
+
+    /// What comes before and after each name. Defaults to backticks, as in rustc.
+    pub fn quotes(mut self, open: &'a str, close: &'a str) -> Self {
+        self.open = open;
+        self.close = close;
+        self
+    }
+
+    /// What ends the message. Defaults to `"?"`.
+    pub fn suffix(mut self, suffix: &'a str) -> Self {
+        self.suffix = suffix;
+        self
+    }
+
+    /// Returns the names to suggest, best first, including those that aren't listed.
+    pub fn matches(&self) -> &[&'a str] {
+        &self.matches
+    }
+
+    /// Returns each listed name with its [`alignment`](crate::alignment) to the lookup, with
+    /// the lookup as `a` and the name as `b`, e.g. to highlight what the name changes.
+    ///
+    /// ```
+    /// use rustc_edit_distance::{SpanKind, Suggestion};
+    ///
+    /// let suggestion = Suggestion::new("lenght", &["length"]);
+    /// let (name, spans) = &suggestion.changes()[0];
+    /// let changed = spans.iter().filter(|span| span.kind == SpanKind::Changed);
+    /// assert_eq!(changed.map(|span| &name[span.b.clone()]).collect::<Vec<_>>(), ["th"]);
+    /// ```
+    pub fn changes(&self) -> Vec<(&'a str, Vec<AlignedSpan>)> {
+        self.listed()
+            .iter()
+            .map(|&name| {
+                // No limit, so there always is an alignment.
+                (name, alignment(self.lookup, name, usize::MAX).unwrap())
+            })
+            .collect()
+    }
+
+    fn listed(&self) -> &[&'a str] {
+        &self.matches[..self.matches.len().min(self.max_listed)]
+    }
+}
+
+impl fmt::Display for Suggestion<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let listed = self.listed();
+        let others = self.matches.len() - listed.len();
+        if listed.is_empty() {
+            return Ok(());
+        }
+        let prefix = if self.matches.len() == 1 {
+            self.single
+        } else {
+            self.multiple
+        };
+        if !prefix.is_empty() {
+            write!(f, "{prefix} ")?;
+        }
+        for (i, name) in listed.iter().enumerate() {
+            if i + 1 == listed.len() && others == 0 && i > 0 {
+                f.write_str(" or ")?;
+            } else if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}{name}{}", self.open, self.close)?;
+        }
+        if others > 0 {
+            let s = if others == 1 { "" } else { "s" };
+            write!(f, " or {others} other{s}")?;
+        }
+        f.write_str(self.suffix)
+    }
+}
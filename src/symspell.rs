@@ -0,0 +1,177 @@
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+
+use crate::persist::{IndexDecodeError, Reader, Writer};
+use crate::{edit_distance, Chars};
+
+/// The tag of the binary format of a [`SymSpellIndex`].
+const TAG: &[u8; 4] = b"SYM1";
+
+/// An index of the words of a dictionary by their [delete neighborhoods], for spelling
+/// correction in time that hardly grows with the size of the dictionary.
+///
+/// Two strings within `max_dist` edits of each other both become the same string when at most
+/// `max_dist` chars are deleted from each, as each edit takes at most one deletion on either
+/// side to undo. So building the index stores every word under each of its deletes, and a
+/// lookup only has to compute the [`edit_distance`] to the words stored under the deletes of
+/// the term. That is much faster than a scan of all words, for an index that takes much more
+/// memory than the dictionary, especially for larger `max_dist`.
+///
+/// ```
+/// use rustc_edit_distance::SymSpellIndex;
+///
+/// let index = SymSpellIndex::build(&["receive", "recipe", "deceive", "relieve"], 1);
+/// assert_eq!(index.lookup("recieve"), [("receive", 1), ("relieve", 1)]);
+/// ```
+///
+/// [delete neighborhoods]: https://github.com/wolfgarbe/SymSpell
+#[derive(Clone, Debug)]
+pub struct SymSpellIndex<'a> {
+    words: Vec<&'a str>,
+    /// The indices into `words` of the words each delete is one of. They are only borrowed in
+    /// an index loaded from bytes.
+    deletes: BTreeMap<Cow<'a, str>, Vec<usize>>,
+    max_dist: usize,
+}
+
+impl<'a> SymSpellIndex<'a> {
+    /// Builds an index of `words` for lookups of terms within `max_dist` of them. Duplicate
+    /// words are only stored once.
+    pub fn build(words: &[&'a str], max_dist: usize) -> Self {
+        let mut unique = words.to_vec();
+        unique.sort_unstable();
+        unique.dedup();
+
+        let mut deletes = BTreeMap::<Cow<'a, str>, Vec<usize>>::new();
+        for (i, word) in unique.iter().enumerate() {
+            for delete in delete_neighborhood(word, max_dist) {
+                deletes.entry(Cow::Owned(delete)).or_default().push(i);
+            }
+        }
+        SymSpellIndex {
+            words: unique,
+            deletes,
+            max_dist,
+        }
+    }
+
+    /// Returns the number of distinct words in the index.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if the index has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    /// Finds every word within the `max_dist` the index was built for of `term`, with its
+    /// distance, sorted by distance and then lexicographically, as a scan of all words with
+    /// [`edit_distance`] would.
+    pub fn lookup(&self, term: &str) -> Vec<(&'a str, usize)> {
+        let mut candidates = delete_neighborhood(term, self.max_dist)
+            .iter()
+            .filter_map(|delete| self.deletes.get(delete.as_str()))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+        candidates.sort_unstable();
+        candidates.dedup();
+
+        let mut matches = candidates
+            .into_iter()
+            .filter_map(|i| {
+                let word = self.words[i];
+                Some((word, edit_distance(term, word, self.max_dist)?))
+            })
+            .collect::<Vec<_>>();
+        matches.sort_unstable_by_key(|&(word, d)| (d, word));
+        matches
+    }
+
+    /// Encodes the index in the crate's compact binary format, to load it again with
+    /// [`SymSpellIndex::from_bytes`] instead of building it.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut writer = Writer::new(TAG);
+        writer.usize(self.max_dist);
+        writer.usize(self.words.len());
+        for word in &self.words {
+            writer.str(word);
+        }
+        writer.usize(self.deletes.len());
+        for (delete, words) in &self.deletes {
+            writer.str(delete);
+            writer.usize(words.len());
+            for &i in words {
+                writer.usize(i);
+            }
+        }
+        writer.finish()
+    }
+
+    /// Loads an index from bytes returned by [`SymSpellIndex::to_bytes`], borrowing its words
+    /// and deletes from `bytes`, so the bytes can be embedded with `include_bytes!`.
+    ///
+    /// ```
+    /// use rustc_edit_distance::SymSpellIndex;
+    ///
+    /// let bytes = SymSpellIndex::build(&["receive", "relieve"], 1).to_bytes();
+    /// let index = SymSpellIndex::from_bytes(&bytes).unwrap();
+    /// assert_eq!(index.lookup("recieve"), [("receive", 1), ("relieve", 1)]);
+    /// ```
+    pub fn from_bytes(bytes: &'a [u8]) -> Result<Self, IndexDecodeError> {
+        let mut reader = Reader::new(bytes, TAG)?;
+        let max_dist = reader.usize()?;
+        let mut words = Vec::new();
+        for _ in 0..reader.usize()? {
+            words.push(reader.str()?);
+        }
+        let mut deletes = BTreeMap::new();
+        for _ in 0..reader.usize()? {
+            let delete = reader.str()?;
+            let mut indices = Vec::new();
+            for _ in 0..reader.usize()? {
+                indices.push(reader.index(0..words.len())?);
+            }
+            deletes.insert(Cow::Borrowed(delete), indices);
+        }
+        reader.finish()?;
+        Ok(SymSpellIndex {
+            words,
+            deletes,
+            max_dist,
+        })
+    }
+}
+
+/// Every string that is left of `s` after deleting at most `max_dist` of its chars, including
+/// `s` itself.
+fn delete_neighborhood(s: &str, max_dist: usize) -> BTreeSet<String> {
+    let mut neighborhood = BTreeSet::new();
+    neighborhood.insert(String::from(s));
+    let mut last = neighborhood.clone();
+    for _ in 0..max_dist {
+        let mut next = BTreeSet::new();
+        for s in &last {
+            let chars = s.chars().collect::<Chars>();
+            for skip in 0..chars.len() {
+                let delete = chars
+                    .iter()
+                    .enumerate()
+                    .filter(|&(i, _)| i != skip)
+                    .map(|(_, &c)| c)
+                    .collect::<String>();
+                if !neighborhood.contains(&delete) {
+                    next.insert(delete);
+                }
+            }
+        }
+        neighborhood.extend(next.iter().cloned());
+        last = next;
+    }
+    neighborhood
+}
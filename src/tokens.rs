@@ -0,0 +1,178 @@
+use alloc::vec::Vec;
+
+use crate::weighted::weighted_dp;
+use crate::{edit_distance, edit_distance_slices, edit_distance_with_impl};
+
+/// Finds the [edit distance] between two sequences of tokens, e.g. the words of two sentences,
+/// where inserting, deleting, substituting or transposing a whole token is one edit.
+///
+/// Tokens are equal if their strings are, so a typo in a word makes it a different word. To
+/// count that as less than a whole substitution, use [`token_edit_distance_with`].
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// ```
+/// use rustc_edit_distance::token_edit_distance;
+///
+/// let a = ["open", "the", "file"];
+/// assert_eq!(token_edit_distance(&a, &["open", "file"], usize::MAX), Some(1));
+/// assert_eq!(token_edit_distance(&a, &["open", "a", "file"], usize::MAX), Some(1));
+/// assert_eq!(token_edit_distance(&a, &["file", "open"], 1), None);
+/// ```
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn token_edit_distance<S: AsRef<str>>(a: &[S], b: &[S], limit: usize) -> Option<usize> {
+    token_edit_distance_with(a, b, limit, |x, y| usize::from(x != y))
+}
+
+/// Like [`token_edit_distance`], but with a custom cost for substituting one token with
+/// another, e.g. less for tokens that are only a typo apart.
+///
+/// As for [`edit_distance_with`](crate::edit_distance_with), `substitution_cost(x, x)` must be
+/// 0, and costs above 2 make no difference, as a whole token can always be deleted and another
+/// inserted instead. Insertions, deletions and transpositions still cost 1.
+///
+/// ```
+/// use rustc_edit_distance::{edit_distance, token_edit_distance_with};
+///
+/// // A word with a typo costs 1 to fix, any other word 2.
+/// let cost = |x: &str, y: &str| edit_distance(x, y, 1).map_or(2, |_| 1);
+/// let a = ["open", "fiel"];
+/// assert_eq!(token_edit_distance_with(&a, &["open", "file"], usize::MAX, cost), Some(1));
+/// assert_eq!(token_edit_distance_with(&a, &["open", "dir"], usize::MAX, cost), Some(2));
+/// ```
+pub fn token_edit_distance_with<S, F>(
+    a: &[S],
+    b: &[S],
+    limit: usize,
+    substitution_cost: F,
+) -> Option<usize>
+where
+    S: AsRef<str>,
+    F: Fn(&str, &str) -> usize,
+{
+    let a = a.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    let b = b.iter().map(AsRef::as_ref).collect::<Vec<_>>();
+    edit_distance_with_impl(&a, &b, limit, |x: &&str, y: &&str| substitution_cost(x, y))
+}
+
+/// Like [`token_edit_distance`], with the tokens of `a` and `b` being their words, split at
+/// whitespace.
+///
+/// ```
+/// use rustc_edit_distance::word_edit_distance;
+///
+/// assert_eq!(word_edit_distance("open  the file", "open file", usize::MAX), Some(1));
+/// ```
+pub fn word_edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    word_edit_distance_with(a, b, limit, |x, y| usize::from(x != y))
+}
+
+/// Like [`token_edit_distance_with`], with the tokens of `a` and `b` being their words, split
+/// at whitespace.
+pub fn word_edit_distance_with<F>(
+    a: &str,
+    b: &str,
+    limit: usize,
+    substitution_cost: F,
+) -> Option<usize>
+where
+    F: Fn(&str, &str) -> usize,
+{
+    let a = a.split_whitespace().collect::<Vec<_>>();
+    let b = b.split_whitespace().collect::<Vec<_>>();
+    token_edit_distance_with(&a, &b, limit, substitution_cost)
+}
+
+/// Finds the [edit distance] between two texts, where inserting, deleting, substituting or
+/// transposing a whole line is one edit, e.g. to find near-duplicate files or snippets.
+///
+/// Lines end at `\n` or `\r\n`, as for [`str::lines`], so a final line ending makes no
+/// difference.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// ```
+/// use rustc_edit_distance::line_edit_distance;
+///
+/// let a = "fn main() {\n    let x = 1;\n    println!(\"{x}\");\n}\n";
+/// let b = "fn main() {\n    let x = 2;\n    println!(\"{x}\");\n}";
+/// assert_eq!(line_edit_distance(a, b, usize::MAX), Some(1));
+/// ```
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn line_edit_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = a.lines().collect::<Vec<_>>();
+    let b = b.lines().collect::<Vec<_>>();
+    token_edit_distance(&a, &b, limit)
+}
+
+/// Like [`line_edit_distance`], but compares 64-bit hashes of the lines instead of the lines
+/// themselves, which is faster for texts with many long lines, especially ones that start
+/// alike.
+///
+/// Two different lines only count as equal if their hashes collide, which is so unlikely
+/// that the result is the same as that of `line_edit_distance` for anything but adversarial
+/// input.
+pub fn line_edit_distance_hashed(a: &str, b: &str, limit: usize) -> Option<usize> {
+    let a = a.lines().map(hash_line).collect::<Vec<_>>();
+    let b = b.lines().map(hash_line).collect::<Vec<_>>();
+    edit_distance_slices(&a, &b, limit)
+}
+
+/// The 64-bit [FNV-1a] hash of `line`, which needs no `std` and is fast for short input.
+///
+/// [FNV-1a]: https://en.wikipedia.org/wiki/Fowler%E2%80%93Noll%E2%80%93Vo_hash_function
+fn hash_line(line: &str) -> u64 {
+    line.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Finds how far apart two phrases are, e.g. a command typed into a command palette and the
+/// name of a command, by aligning their words, split at whitespace, and adding up the
+/// [`edit_distance`] of each aligned pair of words.
+///
+/// A word that isn't aligned with one of the other phrase costs its length in chars, as if
+/// it were typed or deleted char by char, and swapping two adjacent words costs 1. So a typo
+/// costs the same as in [`edit_distance`], but the whitespace between words costs nothing,
+/// and neither does how many there are of it. Words run together, on the other hand, are
+/// words of their own: `"openfile"` is 8 from `"open file"`.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// ```
+/// use rustc_edit_distance::phrase_distance;
+///
+/// assert_eq!(phrase_distance("open fiel in editr", "open file in editor", usize::MAX), Some(2));
+/// assert_eq!(phrase_distance("open file", "open  file in editor", usize::MAX), Some(8));
+/// assert_eq!(phrase_distance("file open", "open file", usize::MAX), Some(1));
+/// ```
+///
+/// To rank candidates by it, e.g. with
+/// [`find_all_matches_within_with`](crate::find_all_matches_within_with), see
+/// [`MatchOptions::phrases`](crate::MatchOptions::phrases).
+pub fn phrase_distance(a: &str, b: &str, limit: usize) -> Option<usize> {
+    phrase_distance_by(a, b, limit, |x, y| edit_distance(x, y, usize::MAX))
+}
+
+/// Like [`phrase_distance`], with aligned words `x` and `y` costing `word_distance(x, y)`.
+pub(crate) fn phrase_distance_by(
+    a: &str,
+    b: &str,
+    limit: usize,
+    word_distance: impl Fn(&str, &str) -> Option<usize>,
+) -> Option<usize> {
+    let a = a.split_whitespace().collect::<Vec<_>>();
+    let b = b.split_whitespace().collect::<Vec<_>>();
+    let len = |word: &&str| word.chars().count();
+    weighted_dp(
+        &a,
+        &b,
+        limit,
+        len,
+        len,
+        |x, y| word_distance(x, y).unwrap_or(usize::MAX),
+        1,
+    )
+}
@@ -0,0 +1,101 @@
+use alloc::{vec, vec::Vec};
+
+use crate::LevenshteinAutomaton;
+
+/// A [trie] of candidates, for finding the closest one to a lookup without computing the
+/// distance to each.
+///
+/// The candidates that share a prefix share the rows of the table of
+/// [`edit_distance`](crate::edit_distance) for it, which are computed once, by a
+/// [`LevenshteinAutomaton`] walking down the trie. A subtree is skipped as soon as no
+/// candidate in it can be within the limit, which for symbol tables with many common
+/// prefixes, like a compiler's, is most of the trie.
+///
+/// ```
+/// use rustc_edit_distance::Trie;
+///
+/// let trie = Trie::from_candidates(&["to_string", "to_owned", "to_vec", "into_iter"]);
+/// assert_eq!(trie.nearest("to_strign", 2), Some(("to_string", 1)));
+/// assert_eq!(trie.nearest("as_str", 2), None);
+/// ```
+///
+/// [trie]: https://en.wikipedia.org/wiki/Trie
+#[derive(Clone, Debug)]
+pub struct Trie<'a> {
+    nodes: Vec<Node<'a>>,
+    len: usize,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node<'a> {
+    /// The candidate that ends at this node, if any.
+    candidate: Option<&'a str>,
+    /// Pairs of (next char, index into `Trie::nodes`), sorted by char.
+    children: Vec<(char, usize)>,
+}
+
+impl<'a> Trie<'a> {
+    /// Builds a trie of `candidates`. Duplicate candidates are only stored once.
+    pub fn from_candidates(candidates: &[&'a str]) -> Self {
+        let mut nodes = vec![Node::default()];
+        let mut len = 0;
+        for &candidate in candidates {
+            let mut current = 0;
+            for c in candidate.chars() {
+                current = match nodes[current]
+                    .children
+                    .binary_search_by_key(&c, |&(c, _)| c)
+                {
+                    Ok(i) => nodes[current].children[i].1,
+                    Err(i) => {
+                        let child = nodes.len();
+                        nodes.push(Node::default());
+                        nodes[current].children.insert(i, (c, child));
+                        child
+                    }
+                };
+            }
+            if nodes[current].candidate.is_none() {
+                nodes[current].candidate = Some(candidate);
+                len += 1;
+            }
+        }
+        Trie { nodes, len }
+    }
+
+    /// Returns the number of distinct candidates in the trie.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the trie has no candidates.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Finds the candidate with the smallest distance to `lookup`, and the distance, if it is
+    /// within `max_dist`. Ties are broken in favor of the lexicographically smallest candidate.
+    pub fn nearest(&self, lookup: &str, max_dist: usize) -> Option<(&'a str, usize)> {
+        let automaton = LevenshteinAutomaton::new(lookup, max_dist);
+        let mut best: Option<(&'a str, usize)> = None;
+        // Depth first, with the children of a node in order, visits the candidates in
+        // lexicographic order, so the first of several at the same distance is the smallest.
+        let mut stack = vec![(0, automaton.start())];
+        while let Some((current, state)) = stack.pop() {
+            let node = &self.nodes[current];
+            if let (Some(candidate), Some(d)) = (node.candidate, automaton.distance(&state)) {
+                if best.map_or(true, |(_, best)| d < best) {
+                    best = Some((candidate, d));
+                }
+            }
+            stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .map(|&(c, child)| (child, automaton.step(&state, c)))
+                    .filter(|(_, state)| automaton.can_match(state)),
+            );
+        }
+        best
+    }
+}
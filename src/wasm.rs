@@ -0,0 +1,55 @@
+//! [`wasm-bindgen`](https://docs.rs/wasm-bindgen) exports of the distances and matching, so
+//! that JavaScript, e.g. an autocomplete frontend, scores exactly as the crate does in Rust.
+//!
+//! Build them with `wasm-pack build --features wasm`, or
+//! `cargo build --target wasm32-unknown-unknown --features wasm` and `wasm-bindgen`. The
+//! functions are exported in camel case: `editDistance`, `similarity`, `findBestMatch` and
+//! `findBestMatches`. A limit or distance left `undefined` is the default, and a distance over
+//! the limit, or no match, is `undefined`.
+
+use std::{string::String, vec::Vec};
+
+use wasm_bindgen::prelude::wasm_bindgen;
+
+use crate::{find_best_match_for_name_with, find_best_matches as ranked, similarity_ratio};
+
+/// The [`edit_distance`](crate::edit_distance) between `a` and `b`, with no limit if `limit`
+/// is `None`.
+#[wasm_bindgen(js_name = editDistance)]
+pub fn edit_distance(a: &str, b: &str, limit: Option<usize>) -> Option<usize> {
+    crate::edit_distance(a, b, limit.unwrap_or(usize::MAX))
+}
+
+/// The [`similarity_ratio`] of `a` and `b`.
+#[wasm_bindgen]
+pub fn similarity(a: &str, b: &str) -> f64 {
+    similarity_ratio(a, b)
+}
+
+/// The candidate that best matches `lookup`, as found by
+/// [`find_best_match_for_name`](crate::find_best_match_for_name).
+#[wasm_bindgen(js_name = findBestMatch)]
+pub fn find_best_match(
+    candidates: Vec<String>,
+    lookup: &str,
+    dist: Option<usize>,
+) -> Option<String> {
+    let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+    find_best_match_for_name_with(&candidates, lookup, &crate::options(dist)).map(String::from)
+}
+
+/// Up to `n` of the candidates that match `lookup`, best first, as found by
+/// [`find_best_matches`](crate::find_best_matches).
+#[wasm_bindgen(js_name = findBestMatches)]
+pub fn find_best_matches(
+    candidates: Vec<String>,
+    lookup: &str,
+    dist: Option<usize>,
+    n: usize,
+) -> Vec<String> {
+    let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+    ranked(&candidates, lookup, dist, n)
+        .into_iter()
+        .map(|m| String::from(m.candidate))
+        .collect()
+}
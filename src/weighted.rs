@@ -0,0 +1,254 @@
+use alloc::vec;
+use core::cmp;
+
+use crate::{three_rows, Chars, Rows};
+
+/// The cost of each kind of edit, for [`weighted_edit_distance`].
+///
+/// The default costs are all 1, which makes [`weighted_edit_distance`] the same as
+/// [`edit_distance`](crate::edit_distance).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct EditCosts {
+    /// The cost of inserting a char of `b`.
+    pub insert: usize,
+    /// The cost of deleting a char of `a`.
+    pub delete: usize,
+    /// The cost of replacing a char of `a` with a different char of `b`.
+    pub substitute: usize,
+    /// The cost of swapping two adjacent chars of `a`.
+    pub transpose: usize,
+}
+
+impl Default for EditCosts {
+    fn default() -> Self {
+        EditCosts {
+            insert: 1,
+            delete: 1,
+            substitute: 1,
+            transpose: 1,
+        }
+    }
+}
+
+/// Finds the cheapest way to turn `a` into `b`, with the cost of each kind of edit given by
+/// `costs`.
+///
+/// As with [`edit_distance`](crate::edit_distance), no substring is edited more than once.
+/// Insertions and deletions may cost differently, so `weighted_edit_distance(a, b, ..)` need
+/// not equal `weighted_edit_distance(b, a, ..)`.
+///
+/// Returns `None` if the cost exceeds the limit.
+pub fn weighted_edit_distance(a: &str, b: &str, limit: usize, costs: &EditCosts) -> Option<usize> {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    weighted_dp(
+        &a,
+        &b,
+        limit,
+        |_| costs.insert,
+        |_| costs.delete,
+        |x, y| if x == y { 0 } else { costs.substitute },
+        costs.transpose,
+    )
+}
+
+/// Like [`weighted_edit_distance`], but the cost of each edit depends on the chars involved.
+///
+/// `insert(y)` is the cost of inserting `y`, a char of `b`, `delete(x)` the cost of deleting
+/// `x`, a char of `a`, and `substitute(x, y)` the cost of replacing `x` with `y`, e.g. less
+/// for a digit with another digit than with a letter. Keeping a char as it is costs nothing,
+/// so `substitute` is only called with different chars. Transpositions cost 1.
+///
+/// Returns `None` if the cost exceeds the limit.
+pub fn edit_distance_with_costs<I, D, S>(
+    a: &str,
+    b: &str,
+    limit: usize,
+    insert: I,
+    delete: D,
+    substitute: S,
+) -> Option<usize>
+where
+    I: Fn(char) -> usize,
+    D: Fn(char) -> usize,
+    S: Fn(char, char) -> usize,
+{
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    weighted_dp(
+        &a,
+        &b,
+        limit,
+        |&y| insert(y),
+        |&x| delete(x),
+        |&x, &y| if x == y { 0 } else { substitute(x, y) },
+        1,
+    )
+}
+
+/// The optimal string alignment table, with costs given per edit.
+///
+/// None of the shortcuts of `dp` that rely on every edit costing at least 1 are taken, and `a`
+/// and `b` are never swapped, as insertions and deletions may cost differently.
+pub(crate) fn weighted_dp<T: PartialEq>(
+    a: &[T],
+    b: &[T],
+    limit: usize,
+    insert: impl Fn(&T) -> usize,
+    delete: impl Fn(&T) -> usize,
+    substitute: impl Fn(&T, &T) -> usize,
+    transpose: usize,
+) -> Option<usize> {
+    // Equal chars at the start and the end cost nothing to keep, and a transposition across
+    // the boundary would swap equal chars, so they can be stripped.
+    let common_prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let (a, b) = (&a[common_prefix..], &b[common_prefix..]);
+    let common_suffix = a
+        .iter()
+        .rev()
+        .zip(b.iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a, b) = (&a[..a.len() - common_suffix], &b[..b.len() - common_suffix]);
+
+    let mut rows = Rows::from_elem(0, 3 * (b.len() + 1));
+    let (mut prev_prev, mut prev, mut current) = three_rows(&mut rows, b.len() + 1);
+    for j in 1..=b.len() {
+        prev[j] = prev[j - 1].saturating_add(insert(&b[j - 1]));
+    }
+    let mut prev_min = prev.iter().copied().min().unwrap_or(0);
+
+    for i in 1..=a.len() {
+        current[0] = prev[0].saturating_add(delete(&a[i - 1]));
+        for j in 1..=b.len() {
+            current[j] = cmp::min(
+                // deletion
+                prev[j].saturating_add(delete(&a[i - 1])),
+                cmp::min(
+                    // insertion
+                    current[j - 1].saturating_add(insert(&b[j - 1])),
+                    // substitution
+                    prev[j - 1].saturating_add(substitute(&a[i - 1], &b[j - 1])),
+                ),
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                // transposition
+                current[j] = cmp::min(current[j], prev_prev[j - 2].saturating_add(transpose));
+            }
+        }
+
+        // A transposition reaches back at most two rows, so once two consecutive rows are over
+        // the limit, all the ones below are too.
+        let min = current.iter().copied().min().unwrap_or(usize::MAX);
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // `prev` because we already rotated the buffers.
+    let distance = prev[b.len()];
+    (distance <= limit).then_some(distance)
+}
+
+/// Like [`weighted_edit_distance`], but with [affine gaps]: starting a run of insertions or
+/// deletions costs `gap_open` on top of the costs in `costs`, and continuing it doesn't, so a
+/// run of missing chars costs less than as many edits scattered about.
+///
+/// A run of `k` insertions costs `gap_open + k * costs.insert`, and one of deletions likewise.
+/// With a `gap_open` of 0 this is the same as `weighted_edit_distance`.
+///
+/// ```
+/// use rustc_edit_distance::{weighted_edit_distance_affine, EditCosts};
+///
+/// let costs = EditCosts::default();
+/// // One run of three deletions, whose cost is opened once.
+/// assert_eq!(weighted_edit_distance_affine("abcdef", "abf", 10, &costs, 2), Some(5));
+/// assert_eq!(weighted_edit_distance_affine("abcdef", "abf", 10, &costs, 0), Some(3));
+/// ```
+///
+/// Returns `None` if the cost exceeds the limit.
+///
+/// [affine gaps]: https://en.wikipedia.org/wiki/Gap_penalty#Affine
+pub fn weighted_edit_distance_affine(
+    a: &str,
+    b: &str,
+    limit: usize,
+    costs: &EditCosts,
+    gap_open: usize,
+) -> Option<usize> {
+    let a = a.chars().collect::<Chars>();
+    let b = b.chars().collect::<Chars>();
+    affine_dp(&a, &b, limit, costs, gap_open)
+}
+
+/// Gotoh's algorithm, with transpositions as in [`weighted_dp`].
+fn affine_dp(
+    a: &[char],
+    b: &[char],
+    limit: usize,
+    costs: &EditCosts,
+    gap_open: usize,
+) -> Option<usize> {
+    let len = b.len() + 1;
+    // The cheapest cost of turning the first `i` chars of `a` into the first `j` of `b` that
+    // ends in any edit, in `best`, or in a deletion, in `deleting`. One that ends in an
+    // insertion only depends on the cell to its left, so it is kept as the row is filled.
+    let mut best = vec![usize::MAX; 3 * len];
+    let (mut prev_prev, rest) = best.split_at_mut(len);
+    let (mut prev, mut current) = rest.split_at_mut(len);
+    let mut deleting = vec![usize::MAX; 2 * len];
+    let (mut prev_deleting, mut current_deleting) = deleting.split_at_mut(len);
+
+    for (j, cell) in prev.iter_mut().enumerate() {
+        *cell = match j {
+            0 => 0,
+            j => gap_open.saturating_add(costs.insert.saturating_mul(j)),
+        };
+    }
+    let mut prev_min = 0;
+
+    for i in 1..=a.len() {
+        current_deleting[0] = cmp::min(prev[0].saturating_add(gap_open), prev_deleting[0])
+            .saturating_add(costs.delete);
+        current[0] = current_deleting[0];
+        let mut inserting = usize::MAX;
+        for j in 1..len {
+            current_deleting[j] = cmp::min(prev[j].saturating_add(gap_open), prev_deleting[j])
+                .saturating_add(costs.delete);
+            inserting = cmp::min(current[j - 1].saturating_add(gap_open), inserting)
+                .saturating_add(costs.insert);
+            let substitute = if a[i - 1] == b[j - 1] {
+                0
+            } else {
+                costs.substitute
+            };
+            current[j] = cmp::min(
+                prev[j - 1].saturating_add(substitute),
+                cmp::min(current_deleting[j], inserting),
+            );
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                // transposition
+                current[j] = cmp::min(current[j], prev_prev[j - 2].saturating_add(costs.transpose));
+            }
+        }
+
+        // As in `weighted_dp`, two consecutive rows over the limit mean all the ones below are.
+        let min = current.iter().copied().min().unwrap_or(usize::MAX);
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+        [prev_deleting, current_deleting] = [current_deleting, prev_deleting];
+    }
+
+    // `prev` because we already rotated the buffers.
+    let distance = prev[b.len()];
+    (distance <= limit).then_some(distance)
+}
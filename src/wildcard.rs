@@ -0,0 +1,82 @@
+use core::cmp;
+
+use crate::{three_rows, Chars, Rows};
+
+/// Finds the [edit distance] between a glob-like `pattern` and a string, where `?` in the
+/// pattern stands for any single char and `*` for any run of chars, including none, both at no
+/// cost.
+///
+/// Every other char of the pattern is edited as by [`edit_distance`](crate::edit_distance),
+/// so a glob with a typo in it still matches the strings it was meant to. `?` and `*` are
+/// always wildcards, and a wildcard is never transposed.
+///
+/// Returns `None` if the distance exceeds the limit.
+///
+/// ```
+/// use rustc_edit_distance::pattern_distance;
+///
+/// assert_eq!(pattern_distance("test_*", "test_parser", 0), Some(0));
+/// assert_eq!(pattern_distance("tset_*", "test_parser", 2), Some(1));
+/// assert_eq!(pattern_distance("test_??", "test_abc", 2), Some(1));
+/// ```
+///
+/// [edit distance]: https://en.wikipedia.org/wiki/Edit_distance
+pub fn pattern_distance(pattern: &str, s: &str, limit: usize) -> Option<usize> {
+    let pattern = pattern.chars().collect::<Chars>();
+    let s = s.chars().collect::<Chars>();
+    let is_literal = |c: char| c != '?' && c != '*';
+
+    let mut rows = Rows::from_elem(0, 3 * (s.len() + 1));
+    let (mut prev_prev, mut prev, mut current) = three_rows(&mut rows, s.len() + 1);
+    let mut prev_min = 0;
+    for i in 1..=pattern.len() {
+        let c = pattern[i - 1];
+        if c == '*' {
+            // The star matches nothing, or the chars of a match with one char less of `s`.
+            current[0] = prev[0];
+            for j in 1..=s.len() {
+                current[j] = cmp::min(prev[j], current[j - 1]);
+            }
+        } else {
+            current[0] = prev[0] + 1;
+            for j in 1..=s.len() {
+                let substitution_cost = if c == '?' || c == s[j - 1] { 0 } else { 1 };
+                current[j] = cmp::min(
+                    // deletion
+                    prev[j] + 1,
+                    cmp::min(
+                        // insertion
+                        current[j - 1] + 1,
+                        // substitution
+                        prev[j - 1] + substitution_cost,
+                    ),
+                );
+                if i > 1
+                    && j > 1
+                    && is_literal(c)
+                    && is_literal(pattern[i - 2])
+                    && c == s[j - 2]
+                    && pattern[i - 2] == s[j - 1]
+                {
+                    // transposition
+                    current[j] = cmp::min(current[j], prev_prev[j - 2] + 1);
+                }
+            }
+        }
+
+        // As for `edit_distance`, no cell is less than the minimum of the two rows above it,
+        // so once two consecutive rows are over the limit, all the ones below are too.
+        let min = current.iter().copied().min().unwrap_or(usize::MAX);
+        if min > limit && prev_min > limit {
+            return None;
+        }
+        prev_min = min;
+
+        // Rotate the buffers, reusing the memory.
+        [prev_prev, prev, current] = [prev, current, prev_prev];
+    }
+
+    // `prev` because we already rotated the buffers.
+    let distance = prev[s.len()];
+    (distance <= limit).then_some(distance)
+}
@@ -0,0 +1,59 @@
+use alloc::vec::Vec;
+
+/// Whether the char at `i` starts a word of an identifier: the first letter or digit after
+/// anything else, an uppercase letter after a lowercase one or before one, as in `camelCase`
+/// and `HTTPServer`, or the first of a run of digits or the first letter after one.
+pub(crate) fn is_word_start(chars: &[char], i: usize) -> bool {
+    let c = chars[i];
+    if !c.is_alphanumeric() {
+        return false;
+    }
+    let prev = match i.checked_sub(1) {
+        Some(prev) => chars[prev],
+        None => return true,
+    };
+    let next_is_lowercase = chars.get(i + 1).map_or(false, |next| next.is_lowercase());
+    !prev.is_alphanumeric()
+        || (c.is_uppercase() && (!prev.is_uppercase() || next_is_lowercase))
+        || c.is_numeric() != prev.is_numeric()
+}
+
+/// Splits an identifier into its words, in any of `snake_case`, `kebab-case`, `camelCase` and
+/// `PascalCase`, or a mix of them.
+///
+/// Words end at every char that is neither a letter nor a digit, which is dropped, at the
+/// start of every word in camel or Pascal case, where an uppercase letter follows a lowercase
+/// one or starts a lowercase word after an acronym, and where a run of digits starts or ends.
+///
+/// ```
+/// use rustc_edit_distance::split_identifier;
+///
+/// assert_eq!(split_identifier("parseConfig"), ["parse", "Config"]);
+/// assert_eq!(split_identifier("config_parse"), ["config", "parse"]);
+/// assert_eq!(split_identifier("HTTPServer2-log"), ["HTTP", "Server", "2", "log"]);
+/// ```
+pub fn split_identifier(s: &str) -> Vec<&str> {
+    s.split(|c: char| !c.is_alphanumeric())
+        .flat_map(split_case)
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Splits `s` where a word of an identifier starts after a letter or a digit, i.e. at changes
+/// of case and at runs of digits, but not at any other char.
+pub(crate) fn split_case(s: &str) -> impl Iterator<Item = &str> {
+    let chars = s.chars().collect::<Vec<_>>();
+    let mut starts = s
+        .char_indices()
+        .enumerate()
+        .filter(|&(i, _)| i > 0 && chars[i - 1].is_alphanumeric() && is_word_start(&chars, i))
+        .map(|(_, (offset, _))| offset)
+        .collect::<Vec<_>>();
+    starts.push(s.len());
+    let mut start = 0;
+    starts.into_iter().map(move |end| {
+        let word = &s[start..end];
+        start = end;
+        word
+    })
+}
@@ -0,0 +1,176 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use rustc_edit_distance::{
+    edit_distance, edit_distance_ascii_ci, edit_distance_ci, edit_distance_in, edit_distances,
+    EditDistanceBuffer,
+};
+
+struct CountingAlloc;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAlloc = CountingAlloc;
+
+fn allocations<R>(f: impl FnOnce() -> R) -> (R, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let r = f();
+    (r, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn short_strings_do_not_allocate() {
+    assert_eq!(
+        allocations(|| edit_distance("kitten", "sitting", usize::MAX)),
+        (Some(3), 0)
+    );
+    assert_eq!(
+        allocations(|| edit_distance("кошка", "кошик", usize::MAX)),
+        (Some(2), 0)
+    );
+    assert_eq!(
+        allocations(|| edit_distance_ci("Error", "eror", usize::MAX)),
+        (Some(1), 0)
+    );
+}
+
+#[test]
+fn other_short_string_distances_do_not_allocate() {
+    use rustc_edit_distance::{
+        edit_distance_chars, jaro_winkler, levenshtein_distance, prefix_distance,
+        weighted_edit_distance, EditCosts,
+    };
+
+    let (a, b) = ("кошка_мяу", "кошик_мяу");
+    let costs = EditCosts::default();
+    assert_eq!(
+        allocations(|| weighted_edit_distance(a, b, 9, &costs)),
+        (Some(2), 0)
+    );
+    assert_eq!(allocations(|| levenshtein_distance(a, b, 9)), (Some(2), 0));
+    assert_eq!(allocations(|| prefix_distance("кош", b, 9)), (Some(0), 0));
+    assert_eq!(
+        allocations(|| edit_distance_chars(a, b.chars(), 9)),
+        (Some(2), 0)
+    );
+    assert_eq!(allocations(|| jaro_winkler(a, a)), (1.0, 0));
+}
+
+#[test]
+fn long_strings_spill_to_the_heap() {
+    let a = "ab".repeat(100);
+    let b = "ba".repeat(100);
+    let (d, n) = allocations(|| edit_distance(&a, &b, usize::MAX));
+    assert_eq!(d, Some(2));
+    assert!(n > 0);
+}
+
+#[test]
+fn ascii_strings_are_not_decoded() {
+    // Only the rows of the table are allocated, not copies of the chars.
+    let a = "ab".repeat(100);
+    let b = "ba".repeat(100);
+    assert_eq!(
+        allocations(|| edit_distance(&a, &b, usize::MAX)),
+        (Some(2), 1)
+    );
+
+    let a = "аб".repeat(100);
+    let b = "ба".repeat(100);
+    let (d, n) = allocations(|| edit_distance(&a, &b, usize::MAX));
+    assert_eq!(d, Some(2));
+    assert!(n > 1);
+}
+
+#[test]
+fn small_limits_keep_long_rows_on_the_stack() {
+    // Only the band of each row that is within the limit is stored.
+    let a = "ab".repeat(100);
+    let b = "ba".repeat(100);
+    assert_eq!(allocations(|| edit_distance(&a, &b, 2)), (Some(2), 0));
+    assert_eq!(allocations(|| edit_distance(&a, &b, 1)), (None, 0));
+}
+
+#[test]
+fn ascii_case_is_ignored_without_copying() {
+    // Long enough that copies of the strings would spill to the heap.
+    let a = "Ab".repeat(100);
+    let b = "aB".repeat(100);
+    assert_eq!(
+        allocations(|| edit_distance_ascii_ci(&a, &b, 2)),
+        (Some(0), 0)
+    );
+    let b = "bA".repeat(100);
+    assert_eq!(
+        allocations(|| edit_distance_ascii_ci(&a, &b, 2)),
+        (Some(2), 0)
+    );
+}
+
+#[test]
+fn buffers_are_reused() {
+    let a = "аб".repeat(100);
+    let b = "ба".repeat(100);
+    let mut buffer = EditDistanceBuffer::new();
+    let (d, n) = allocations(|| edit_distance_in(&mut buffer, &a, &b, usize::MAX));
+    assert_eq!(d, Some(2));
+    assert!(n > 0);
+    // Shorter strings, and ASCII ones, fit in the memory that is already there.
+    let (c, d) = ("аб".repeat(80), "ab".repeat(90));
+    assert_eq!(
+        allocations(|| edit_distance_in(&mut buffer, &a, &b, usize::MAX)),
+        (Some(2), 0)
+    );
+    assert_eq!(
+        allocations(|| edit_distance_in(&mut buffer, &c, &b, usize::MAX)),
+        (Some(40), 0)
+    );
+    assert_eq!(
+        allocations(|| edit_distance_in(&mut buffer, &d, &d, usize::MAX)),
+        (Some(0), 0)
+    );
+}
+
+#[test]
+fn batches_share_buffers() {
+    let lookup = "аб".repeat(100);
+    let candidates = (0..50).map(|i| "ба".repeat(80 + i)).collect::<Vec<_>>();
+    let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+    let (distances, n) = allocations(|| edit_distances(&lookup, &candidates, 30));
+    assert_eq!(distances[20], Some(2));
+    assert_eq!(distances[0], None);
+    // The results, the chars of the lookup and of the candidates, and the rows, each growing a
+    // few times at most, rather than for every candidate.
+    assert!(n < 16, "{n} allocations");
+}
+
+#[test]
+fn candidate_sets_fold_once() {
+    use rustc_edit_distance::CandidateSet;
+
+    let names = (0..100).map(|i| format!("symbol_{i}")).collect::<Vec<_>>();
+    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+    let set = CandidateSet::new(&names);
+    // Only the lookup is folded and split into words, not every candidate.
+    let (best, n) = allocations(|| set.best("smybol_42", None));
+    assert_eq!(best, Some("symbol_42"));
+    assert!(n <= 4, "{n} allocations");
+    let (best, n) =
+        allocations(|| rustc_edit_distance::find_best_match_for_name(&names, "smybol_42", None));
+    assert_eq!(best, Some("symbol_42"));
+    assert!(n >= names.len(), "{n} allocations");
+}
@@ -0,0 +1,352 @@
+use rustc_edit_distance::{damerau_levenshtein, edit_distance, BkTree};
+
+const WORDS: &[&str] = &[
+    "apple", "apply", "ample", "maple", "applet", "grape", "grapes", "drape", "cape", "tape",
+    "banana", "bandana", "cabana", "orange", "arrange", "range", "ranger", "danger", "manger",
+];
+
+fn linear_best<'a>(candidates: &[&'a str], lookup: &str, dist: usize) -> Option<&'a str> {
+    let mut best: Option<(usize, &'a str)> = None;
+    for &c in candidates {
+        let d = edit_distance(lookup, c, usize::MAX).unwrap();
+        if d <= dist && best.map_or(true, |best| (d, c) < best) {
+            best = Some((d, c));
+        }
+    }
+    best.map(|(_, c)| c)
+}
+
+#[test]
+fn agrees_with_linear_scan() {
+    let tree = BkTree::from_candidates(WORDS);
+    for lookup in [
+        "aple",
+        "grap",
+        "bananna",
+        "rnage",
+        "xyz",
+        "cabbage",
+        "dangerous",
+        "tap",
+    ] {
+        for dist in 0..4 {
+            assert_eq!(
+                tree.find_best_match(lookup, Some(dist)),
+                linear_best(WORDS, lookup, dist),
+                "lookup = {lookup:?}, dist = {dist}",
+            );
+        }
+    }
+}
+
+#[test]
+fn ties_prefer_smaller_candidates() {
+    let tree = BkTree::from_candidates(&["cat", "bat", "hat"]);
+    assert_eq!(tree.find_best_match("mat", Some(1)), Some("bat"));
+    let tree = BkTree::from_candidates(&["hat", "cat", "bat"]);
+    assert_eq!(tree.find_best_match("mat", Some(1)), Some("bat"));
+    // Even where the `edit_distance`s break the triangle inequality, as in
+    // `query_agrees_with_linear_scan`.
+    let tree = BkTree::from_candidates(&["ca", "abc"]);
+    assert_eq!(tree.find_best_match("ac", Some(1)), Some("abc"));
+}
+
+#[test]
+fn duplicates_and_empty() {
+    let tree = BkTree::from_candidates(&["foo", "foo", "bar"]);
+    assert_eq!(tree.len(), 2);
+    assert_eq!(tree.find_best_match("fo", None), Some("foo"));
+
+    let tree = BkTree::from_candidates(&[]);
+    assert!(tree.is_empty());
+    assert_eq!(tree.find_best_match("foo", None), None);
+}
+
+#[test]
+fn damerau_levenshtein_metric() {
+    // `edit_distance("bca", "ab")` is 3, but both are 1 from "ba", so a tree with edges by
+    // that metric would prune "ab", even though it ties with "bca" and is smaller.
+    let candidates = ["bca", "ab"];
+    let tree = BkTree::from_candidates_with_metric(&candidates, edit_distance);
+    assert_eq!(tree.find_best_match("ba", Some(1)), Some("bca"));
+    let tree = BkTree::from_candidates(&candidates);
+    assert_eq!(tree.find_best_match("ba", Some(1)), Some("ab"));
+    let tree = BkTree::from_candidates_with_metric(&candidates, damerau_levenshtein);
+    assert_eq!(tree.find_best_match("ba", Some(1)), Some("ab"));
+
+    let tree = BkTree::from_candidates_with_metric(WORDS, damerau_levenshtein);
+    for lookup in ["aple", "rnage", "bananna", "xyz"] {
+        for dist in 0..4 {
+            let linear = WORDS
+                .iter()
+                .map(|&c| (damerau_levenshtein(lookup, c, usize::MAX).unwrap(), c))
+                .filter(|&(d, _)| d <= dist)
+                .min()
+                .map(|(_, c)| c);
+            assert_eq!(
+                tree.find_best_match(lookup, Some(dist)),
+                linear,
+                "{lookup:?} {dist}"
+            );
+        }
+    }
+}
+
+#[test]
+fn query_agrees_with_linear_scan() {
+    let tree = BkTree::from_candidates_with_metric(WORDS, damerau_levenshtein);
+    for lookup in ["aple", "grap", "rnage", "xyz", "anger", ""] {
+        for max_dist in 0..4 {
+            let mut expected = WORDS
+                .iter()
+                .filter_map(|&c| Some((c, damerau_levenshtein(lookup, c, max_dist)?)))
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|&(c, d)| (d, c));
+            assert_eq!(
+                tree.query(lookup, max_dist),
+                expected,
+                "lookup = {lookup:?}, max_dist = {max_dist}",
+            );
+        }
+    }
+    assert_eq!(BkTree::from_candidates(&[]).query("a", 5), []);
+
+    // The default tree finds the `edit_distance`s a scan does, even where they break the
+    // triangle inequality: "ca" is 3 from "abc", but both are 1 from "ac".
+    let tree = BkTree::from_candidates(&["ca", "abc"]);
+    assert_eq!(tree.query("ac", 1), [("abc", 1), ("ca", 1)]);
+    let bytes = tree.to_bytes();
+    let loaded = BkTree::from_bytes(&bytes).unwrap();
+    assert_eq!(loaded.query("ac", 1), [("abc", 1), ("ca", 1)]);
+    let tree = BkTree::from_candidates(WORDS);
+    for lookup in ["aple", "grap", "rnage", "xyz", "anger", "", "bnaana"] {
+        for max_dist in 0..4 {
+            let mut expected = WORDS
+                .iter()
+                .filter_map(|&c| Some((c, edit_distance(lookup, c, max_dist)?)))
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|&(c, d)| (d, c));
+            assert_eq!(
+                tree.query(lookup, max_dist),
+                expected,
+                "lookup = {lookup:?}, max_dist = {max_dist}",
+            );
+        }
+    }
+}
+
+#[test]
+fn symspell_agrees_with_linear_scan() {
+    use rustc_edit_distance::SymSpellIndex;
+
+    for max_dist in 0..3 {
+        let index = SymSpellIndex::build(WORDS, max_dist);
+        assert_eq!(index.len(), WORDS.len());
+        for lookup in ["aple", "grap", "rnage", "xyz", "anger", "", "bnaana"] {
+            let mut expected = WORDS
+                .iter()
+                .filter_map(|&c| Some((c, edit_distance(lookup, c, max_dist)?)))
+                .collect::<Vec<_>>();
+            expected.sort_by_key(|&(c, d)| (d, c));
+            assert_eq!(
+                index.lookup(lookup),
+                expected,
+                "lookup = {lookup:?}, max_dist = {max_dist}",
+            );
+        }
+    }
+    assert!(SymSpellIndex::build(&[], 2).lookup("a").is_empty());
+}
+
+#[test]
+fn trie_agrees_with_linear_scan() {
+    use rustc_edit_distance::Trie;
+
+    let trie = Trie::from_candidates(WORDS);
+    assert_eq!(trie.len(), WORDS.len());
+    for lookup in [
+        "aple", "grap", "bananna", "rnage", "xyz", "cabbage", "", "ap",
+    ] {
+        for dist in 0..4 {
+            let expected = linear_best(WORDS, lookup, dist);
+            assert_eq!(
+                trie.nearest(lookup, dist),
+                expected.map(|c| (c, edit_distance(lookup, c, dist).unwrap())),
+                "lookup = {lookup:?}, dist = {dist}",
+            );
+        }
+    }
+    assert_eq!(
+        Trie::from_candidates(&["", "a"]).nearest("", 0),
+        Some(("", 0))
+    );
+    assert!(Trie::from_candidates(&[]).nearest("a", 5).is_none());
+}
+
+#[cfg(feature = "fst")]
+#[test]
+fn fst_agrees_with_linear_scan() {
+    use fst::{IntoStreamer, Streamer};
+    use rustc_edit_distance::{find_best_match_in_fst, LevenshteinAutomaton};
+
+    let mut words = WORDS.to_vec();
+    words.extend(["äpple", "grüße"]);
+    words.sort_unstable();
+    let set = fst::Set::from_iter(&words).unwrap();
+    for lookup in [
+        "aple", "grap", "bananna", "rnage", "xyz", "äple", "gruße", "",
+    ] {
+        for dist in 0..4 {
+            assert_eq!(
+                find_best_match_in_fst(&set, lookup, Some(dist)).as_deref(),
+                linear_best(&words, lookup, dist),
+                "lookup = {lookup:?}, dist = {dist}",
+            );
+        }
+    }
+
+    let automaton = LevenshteinAutomaton::new("grüsse", 2);
+    let mut stream = set.search(&automaton).into_stream();
+    let mut keys = Vec::new();
+    while let Some(key) = stream.next() {
+        keys.push(String::from_utf8(key.to_vec()).unwrap());
+    }
+    assert_eq!(keys, ["grüße"]);
+}
+
+#[test]
+fn indexes_round_trip_through_bytes() {
+    use rustc_edit_distance::SymSpellIndex;
+
+    let tree = BkTree::from_candidates_with_metric(WORDS, damerau_levenshtein);
+    let bytes = tree.to_bytes();
+    let loaded = BkTree::from_bytes_with_metric(&bytes, damerau_levenshtein).unwrap();
+    assert_eq!(loaded.len(), tree.len());
+    assert_eq!(loaded.to_bytes(), bytes);
+    for lookup in ["aple", "grap", "rnage", "xyz"] {
+        assert_eq!(loaded.query(lookup, 3), tree.query(lookup, 3));
+    }
+
+    let index = SymSpellIndex::build(WORDS, 2);
+    let index_bytes = index.to_bytes();
+    let loaded = SymSpellIndex::from_bytes(&index_bytes).unwrap();
+    assert_eq!(loaded.to_bytes(), index_bytes);
+    for lookup in ["aple", "grap", "rnage", "xyz"] {
+        assert_eq!(loaded.lookup(lookup), index.lookup(lookup));
+    }
+
+    // Anything cut short, or of the other type, is rejected rather than misread.
+    for len in 0..bytes.len() {
+        assert!(BkTree::from_bytes(&bytes[..len]).is_err());
+    }
+    for len in (0..index_bytes.len()).step_by(7) {
+        assert!(SymSpellIndex::from_bytes(&index_bytes[..len]).is_err());
+    }
+    assert_eq!(
+        BkTree::from_bytes(&index_bytes).unwrap_err(),
+        rustc_edit_distance::IndexDecodeError { offset: 0 }
+    );
+    let mut extra = bytes.clone();
+    extra.push(0);
+    assert!(BkTree::from_bytes(&extra).is_err());
+}
+
+#[test]
+fn bk_tree_bytes_have_to_be_a_tree() {
+    // The bytes of a tree with the given children of each node.
+    let encode = |edges: &[&[usize]]| {
+        let mut bytes = b"BKT2".to_vec();
+        let push = |bytes: &mut Vec<u8>, n: usize| bytes.extend((n as u64).to_le_bytes());
+        push(&mut bytes, edges.len());
+        for (i, children) in edges.iter().enumerate() {
+            let candidate = format!("n{i}");
+            push(&mut bytes, candidate.len());
+            bytes.extend(candidate.as_bytes());
+            push(&mut bytes, children.len());
+            for (k, &child) in children.iter().enumerate() {
+                push(&mut bytes, k + 1);
+                push(&mut bytes, child);
+            }
+        }
+        bytes
+    };
+
+    assert_eq!(
+        BkTree::from_bytes(&encode(&[&[1, 2], &[], &[]]))
+            .unwrap()
+            .len(),
+        3
+    );
+    // A child shared by two nodes.
+    assert!(BkTree::from_bytes(&encode(&[&[1, 2], &[2], &[]])).is_err());
+    // The root as a child, and a node that isn't anyone's.
+    assert!(BkTree::from_bytes(&encode(&[&[1], &[0]])).is_err());
+    assert!(BkTree::from_bytes(&encode(&[&[1], &[], &[]])).is_err());
+    // Shared children that would make lookups take exponential time.
+    let chain = (1..40).map(|i| [i, i]).collect::<Vec<_>>();
+    let mut edges = chain.iter().map(|e| &e[..]).collect::<Vec<_>>();
+    edges.push(&[]);
+    assert!(BkTree::from_bytes(&encode(&edges)).is_err());
+    // More nodes than the bytes could hold.
+    let mut bytes = b"BKT2".to_vec();
+    bytes.extend(u64::MAX.to_le_bytes());
+    assert!(BkTree::from_bytes(&bytes).is_err());
+}
+
+#[test]
+fn spell_checker() {
+    use rustc_edit_distance::{Correction, SpellChecker, WordListError};
+
+    let words = WORDS
+        .iter()
+        .enumerate()
+        .map(|(i, &w)| (w, i as u64))
+        .collect::<Vec<_>>();
+    let checker = SpellChecker::new(&words, 2);
+    assert_eq!(checker.len(), WORDS.len());
+    assert!(WORDS.iter().all(|w| checker.check(w)));
+    assert!(!checker.check("Apple") && !checker.check("aple"));
+
+    // Ranked by distance, then by frequency, as a scan of all words would be.
+    for lookup in ["aple", "grap", "rnage", "banan", "xyz"] {
+        let mut expected = WORDS
+            .iter()
+            .enumerate()
+            .filter_map(|(i, &word)| {
+                let distance = edit_distance(lookup, word, 2)?;
+                Some((distance, std::cmp::Reverse(i), word))
+            })
+            .collect::<Vec<_>>();
+        expected.sort_unstable();
+        let corrections = checker.correct(lookup, usize::MAX);
+        let found = corrections
+            .iter()
+            .map(|c| (c.distance, std::cmp::Reverse(c.frequency as usize), c.word))
+            .collect::<Vec<_>>();
+        assert_eq!(found, expected, "{lookup}");
+        assert_eq!(
+            checker.correct(lookup, 2),
+            corrections[..corrections.len().min(2)]
+        );
+    }
+    assert_eq!(
+        checker.correct("apple", 1),
+        [Correction {
+            word: "apple",
+            distance: 0,
+            frequency: 0
+        }]
+    );
+
+    let checker = SpellChecker::from_word_list("the 10\n\n  then\t3\nthe 5\nthey\n", 1).unwrap();
+    assert_eq!(checker.len(), 3);
+    assert_eq!(checker.frequency("the"), Some(15));
+    assert_eq!(checker.frequency("they"), Some(1));
+    assert_eq!(checker.frequency("thee"), None);
+    assert_eq!(
+        SpellChecker::from_word_list("the 10\nthen x\n", 1).unwrap_err(),
+        WordListError { line: 2 }
+    );
+    assert!(SpellChecker::from_word_list("the 1 2", 1).is_err());
+    assert!(SpellChecker::new(&[], 1).correct("a", 5).is_empty());
+}
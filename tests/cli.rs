@@ -0,0 +1,84 @@
+#![cfg(feature = "bin")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs `red` with `args` and `stdin`, and returns its exit code and stdout.
+fn red(args: &[&str], stdin: &str) -> (i32, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_red"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.code().unwrap(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+const CANDIDATES: &str = "status\nstart\nstats\nSTAT\nshow\n";
+
+#[test]
+fn suggest() {
+    assert_eq!(
+        red(&["suggest", "strat"], CANDIDATES),
+        (0, "start\n".into())
+    );
+    assert_eq!(
+        red(&["suggest", "stat", "--top", "3"], CANDIDATES),
+        (0, "stats\nstatus\nSTAT\n".into())
+    );
+    assert_eq!(
+        red(&["suggest", "stat", "--json", "-n", "2"], CANDIDATES),
+        (
+            0,
+            "[{\"candidate\":\"stats\",\"distance\":1,\"phase\":\"case-insensitive\"},\
+             {\"candidate\":\"status\",\"distance\":2,\"phase\":\"case-insensitive\"}]\n"
+                .into()
+        )
+    );
+    // Without the other kinds of match, neither `status` nor the case of `STAT` is close.
+    assert_eq!(
+        red(
+            &["suggest", "stat", "-a", "osa", "-d", "1", "--top", "9"],
+            CANDIDATES
+        ),
+        (0, "start\nstats\n".into())
+    );
+    assert_eq!(red(&["suggest", "xyzzy"], CANDIDATES), (1, "".into()));
+}
+
+#[test]
+fn filter() {
+    assert_eq!(
+        red(&["filter", "--max-dist", "2", "stat"], CANDIDATES),
+        (0, "status\nstart\nstats\n".into())
+    );
+    assert_eq!(
+        red(
+            &["filter", "-d", "2", "-n", "1", "--json", "stat"],
+            CANDIDATES
+        ),
+        (0, "[{\"candidate\":\"status\",\"distance\":2}]\n".into())
+    );
+    assert_eq!(
+        red(&["filter", "-d", "0", "stat"], CANDIDATES),
+        (1, "".into())
+    );
+}
+
+#[test]
+fn errors() {
+    let (code, _) = red(&["suggest", "a", "--candidates", "/nonexistent"], "");
+    assert_eq!(code, 2);
+    let (code, _) = red(&["suggest", "a", "--algorithm", "bogus"], "");
+    assert_eq!(code, 2);
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "ffi")]
+
+use std::ffi::{c_char, CString};
+use std::ptr;
+
+use rustc_edit_distance::ffi::*;
+
+fn c(s: &str) -> CString {
+    CString::new(s).unwrap()
+}
+
+#[test]
+fn edit_distance() {
+    let mut distance = 0;
+    unsafe {
+        let status = red_edit_distance(
+            c("kitten").as_ptr(),
+            c("sitting").as_ptr(),
+            3,
+            &mut distance,
+        );
+        assert_eq!((status, distance), (RED_OK, 3));
+        let status = red_edit_distance(
+            c("kitten").as_ptr(),
+            c("sitting").as_ptr(),
+            2,
+            &mut distance,
+        );
+        assert_eq!(status, RED_NOT_FOUND);
+        let status = red_edit_distance(ptr::null(), c("a").as_ptr(), 2, &mut distance);
+        assert_eq!(status, RED_NULL_POINTER);
+        let status = red_edit_distance(c("a").as_ptr(), c("a").as_ptr(), 2, ptr::null_mut());
+        assert_eq!(status, RED_NULL_POINTER);
+        let invalid = CString::new(vec![0xff, b'a']).unwrap();
+        let status = red_edit_distance(invalid.as_ptr(), c("a").as_ptr(), 2, &mut distance);
+        assert_eq!(status, RED_INVALID_UTF8);
+    }
+}
+
+#[test]
+fn similarity() {
+    let mut similarity = 0.0;
+    unsafe {
+        let status = red_similarity(c("abcd").as_ptr(), c("abce").as_ptr(), &mut similarity);
+        assert_eq!(status, RED_OK);
+    }
+    assert_eq!(
+        similarity,
+        rustc_edit_distance::similarity_ratio("abcd", "abce")
+    );
+}
+
+#[test]
+fn find_best_match() {
+    let strings = ["width", "length", "height"].map(c);
+    let candidates = strings.iter().map(|s| s.as_ptr()).collect::<Vec<_>>();
+    let mut index = usize::MAX;
+    unsafe {
+        let find = |lookup: &str, dist, index: &mut usize| {
+            red_find_best_match(
+                candidates.as_ptr(),
+                candidates.len(),
+                c(lookup).as_ptr(),
+                dist,
+                index,
+            )
+        };
+        assert_eq!(find("lenght", RED_DEFAULT_DISTANCE, &mut index), RED_OK);
+        assert_eq!(index, 1);
+        assert_eq!(find("lenght", 0, &mut index), RED_NOT_FOUND);
+        assert_eq!(find("xyz", RED_DEFAULT_DISTANCE, &mut index), RED_NOT_FOUND);
+        let status = red_find_best_match(ptr::null(), 0, c("a").as_ptr(), 1, &mut index);
+        assert_eq!(status, RED_NOT_FOUND);
+        let status = red_find_best_match(ptr::null(), 1, c("a").as_ptr(), 1, &mut index);
+        assert_eq!(status, RED_NULL_POINTER);
+        let with_null: [*const c_char; 2] = [candidates[0], ptr::null()];
+        let status = red_find_best_match(with_null.as_ptr(), 2, c("a").as_ptr(), 1, &mut index);
+        assert_eq!(status, RED_NULL_POINTER);
+    }
+}
+
+#[test]
+fn header_declares_every_function() {
+    let header = include_str!("../include/rustc_edit_distance.h");
+    for declaration in [
+        "int32_t red_edit_distance(",
+        "int32_t red_similarity(",
+        "int32_t red_find_best_match(",
+        "#define RED_OK 0",
+        "#define RED_NOT_FOUND 1",
+        "#define RED_NULL_POINTER (-1)",
+        "#define RED_INVALID_UTF8 (-2)",
+    ] {
+        assert!(header.contains(declaration), "{declaration}");
+    }
+}
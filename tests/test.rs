@@ -11,6 +11,38 @@ fn edit_distance_unchecked(a: &str, b: &str) -> usize {
     rustc_edit_distance::edit_distance(a, b, usize::MAX).unwrap()
 }
 
+/// Textbook optimal string alignment distance over the full matrix, without any of the
+/// shortcuts `edit_distance` takes.
+fn reference_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    d[0] = (0..=b.len()).collect();
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Maps arbitrary strings onto a three-letter alphabet, so that random inputs share
+/// characters often enough to exercise matches and transpositions.
+fn small_alphabet(s: &str) -> String {
+    s.chars()
+        .map(|c| char::from(b'a' + (u32::from(c) % 3) as u8))
+        .collect()
+}
+
 #[test]
 fn simple() {
     assert_eq!(edit_distance_unchecked("kitten", "sitting"), 3);
@@ -98,3 +130,3547 @@ fn triangle_inequality_property() {
 
     quickcheck(triangle_inequality as fn(a: String, b: String, c: String) -> bool);
 }
+
+#[test]
+fn best_match_iter_agrees_with_slice() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_iter};
+
+    let candidates = ["foo_bar", "baz", "FOO", "qux_quux", "quux_qux"];
+    for lookup in ["fob", "foo", "bar_foo", "quux_qux", "bax", "zzzzzz"] {
+        assert_eq!(
+            find_best_match_for_name_iter(candidates.iter().copied(), lookup, None),
+            find_best_match_for_name(&candidates, lookup, None),
+        );
+    }
+}
+
+#[test]
+fn best_match_iter_lazy_input() {
+    let symbols = String::from("push_back,pop_front,emplace");
+    let best =
+        rustc_edit_distance::find_best_match_for_name_iter(symbols.split(','), "pop_frnt", None);
+    assert_eq!(best, Some("pop_front"));
+}
+
+#[test]
+fn banded_agrees_with_reference_property() {
+    fn banded_agrees_with_reference(a: String, b: String, limit: u8) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let limit = usize::from(limit % 5);
+        let expected = Some(reference_distance(&a, &b)).filter(|&d| d <= limit);
+        // Slices don't take the bit-parallel path, so they store only the band for long inputs.
+        let (a_chars, b_chars) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        rustc_edit_distance::edit_distance(&a, &b, limit) == expected
+            && rustc_edit_distance::edit_distance_slices(&a_chars, &b_chars, limit) == expected
+    }
+
+    quickcheck(banded_agrees_with_reference as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[test]
+fn rows_over_the_limit_property() {
+    fn rows_over_the_limit(a: String, b: String, limit: u8) -> bool {
+        // Repeated, to be long enough to give up on well before the last row.
+        let (a, b) = (small_alphabet(&a).repeat(3), small_alphabet(&b).repeat(3));
+        let (a_chars, b_chars) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        let limit = usize::from(limit) % (a_chars.len().max(b_chars.len()) + 1);
+        let expected = Some(reference_distance(&a, &b)).filter(|&d| d <= limit);
+        rustc_edit_distance::edit_distance_slices(&a_chars, &b_chars, limit) == expected
+    }
+
+    quickcheck(rows_over_the_limit as fn(a: String, b: String, limit: u8) -> bool);
+    // Transpositions at both ends, whose cells come from two rows up.
+    let chars = |s: &str| s.chars().collect::<Vec<_>>();
+    assert_eq!(
+        rustc_edit_distance::edit_distance_slices(&chars("xyabcd"), &chars("yxabdc"), 2),
+        Some(2)
+    );
+}
+
+#[test]
+fn limits_around_a_byte() {
+    use rustc_edit_distance::{
+        edit_distance, edit_distance_in, edit_distances, EditDistanceBuffer,
+    };
+
+    // Too long, and not Latin-1, for the bit-parallel path, with distances around 255, and
+    // cells that would hold more than that without the limit.
+    let a = "α".repeat(600);
+    let b = format!("{}{}", "β".repeat(254), "α".repeat(300));
+    let c = format!("{}{}", "β".repeat(256), "α".repeat(344));
+    let mut buffer = EditDistanceBuffer::new();
+    for (other, distance) in [(&b, 300), (&c, 256)] {
+        for limit in [0, 1, 253, 254, 255, 256, 299, 300, 301, 302, 1000] {
+            let expected = Some(distance).filter(|&d| d <= limit);
+            assert_eq!(edit_distance(&a, other, limit), expected, "{limit}");
+            assert_eq!(edit_distance_in(&mut buffer, &a, other, limit), expected);
+            assert_eq!(edit_distances(&a, &[other], limit), [expected]);
+        }
+    }
+    let b = format!("{}{}", "β".repeat(254), "α".repeat(346));
+    assert_eq!(reference_distance(&a, &b), 254);
+    for limit in [253, 254, 255] {
+        let expected = Some(254).filter(|&d| d <= limit);
+        assert_eq!(edit_distance(&a, &b, limit), expected, "{limit}");
+        assert_eq!(edit_distance_in(&mut buffer, &a, &b, limit), expected);
+    }
+}
+
+#[test]
+fn affix_stripping_agrees_with_reference_property() {
+    fn affix_stripping_agrees_with_reference(p: String, a: String, b: String, s: String) -> bool {
+        // A shared prefix and suffix, so that the stripped boundary falls right next to any
+        // transposition at the edges of the differing parts.
+        let (p, s) = (small_alphabet(&p), small_alphabet(&s));
+        let a = format!("{p}{}{s}", small_alphabet(&a));
+        let b = format!("{p}{}{s}", small_alphabet(&b));
+        let expected = reference_distance(&a, &b);
+        edit_distance_unchecked(&a, &b) == expected
+            && rustc_edit_distance::edit_distance_with(&a, &b, usize::MAX, |x, y| {
+                usize::from(x != y)
+            }) == Some(expected)
+    }
+
+    quickcheck(
+        affix_stripping_agrees_with_reference
+            as fn(p: String, a: String, b: String, s: String) -> bool,
+    );
+}
+
+#[test]
+fn transposition_next_to_stripped_affix() {
+    for (a, b) in [
+        ("xab", "xba"),
+        ("abx", "bax"),
+        ("aab", "aba"),
+        ("abb", "bab"),
+        ("aabb", "abab"),
+        ("abab", "baba"),
+        ("aba", "baa"),
+    ] {
+        assert_eq!(
+            edit_distance_unchecked(a, b),
+            reference_distance(a, b),
+            "{a} {b}"
+        );
+    }
+}
+
+#[test]
+fn tight_limit_on_long_strings() {
+    // One substitution near the start and one transposition near the end.
+    let a = "abcdefghij".repeat(20);
+    let mut b = a.chars().collect::<Vec<_>>();
+    b[4] = 'x';
+    b.swap(150, 151);
+    let b = b.into_iter().collect::<String>();
+    assert_eq!(rustc_edit_distance::edit_distance(&a, &b, 1), None);
+    assert_eq!(rustc_edit_distance::edit_distance(&a, &b, 2), Some(2));
+}
+
+#[test]
+fn bytes_agree_with_chars_on_ascii() {
+    use rustc_edit_distance::{edit_distance, edit_distance_bytes};
+
+    for (a, b) in [
+        ("kitten", "sitting"),
+        ("Tier", "Tor"),
+        ("", "abc"),
+        ("ab", "ba"),
+    ] {
+        for limit in 0..4 {
+            assert_eq!(
+                edit_distance_bytes(a.as_bytes(), b.as_bytes(), limit),
+                edit_distance(a, b, limit)
+            );
+        }
+    }
+}
+
+#[test]
+fn bytes_non_utf8() {
+    use rustc_edit_distance::edit_distance_bytes;
+
+    assert_eq!(
+        edit_distance_bytes(b"\xff\xfe\x00", b"\xfe\xff\x00", 5),
+        Some(1)
+    );
+    // Each byte of a multi-byte character is a separate unit of edit.
+    assert_eq!(edit_distance_bytes("ü".as_bytes(), b"u", 5), Some(2));
+}
+
+#[cfg(feature = "unicode-segmentation")]
+#[test]
+fn graphemes() {
+    use rustc_edit_distance::{edit_distance, edit_distance_graphemes};
+
+    // `e` followed by a combining acute accent is one grapheme but two chars.
+    let decomposed = "cafe\u{301}";
+    assert_eq!(edit_distance(decomposed, "cafx", 5), Some(2));
+    assert_eq!(edit_distance_graphemes(decomposed, "cafx", 5), Some(1));
+
+    // A family emoji is several chars joined with zero-width joiners.
+    let family = "\u{1F468}\u{200D}\u{1F469}\u{200D}\u{1F467}";
+    assert_eq!(
+        edit_distance_graphemes(&format!("hi{family}"), "hi", 5),
+        Some(1)
+    );
+    assert_eq!(edit_distance_graphemes(family, family, 0), Some(0));
+
+    // Flags are pairs of regional indicators, and swapping two of them is one transposition.
+    let (de, fr) = ("\u{1F1E9}\u{1F1EA}", "\u{1F1EB}\u{1F1F7}");
+    assert_eq!(
+        edit_distance(&format!("{de}{fr}"), &format!("{fr}{de}"), 5),
+        Some(4)
+    );
+    assert_eq!(
+        edit_distance_graphemes(&format!("{de}{fr}"), &format!("{fr}{de}"), 5),
+        Some(1)
+    );
+}
+
+#[test]
+fn best_match_case_folding() {
+    use rustc_edit_distance::find_best_match_for_name;
+
+    assert_eq!(
+        find_best_match_for_name(&["Foo", "bar"], "FOO", None),
+        Some("Foo")
+    );
+    assert_eq!(
+        find_best_match_for_name(&["hauptstrasse"], "HAUPTSTRAßE", None),
+        Some("hauptstrasse")
+    );
+    assert_eq!(
+        find_best_match_for_name(&["STRAßE"], "strasse", None),
+        Some("STRAßE")
+    );
+    // Final and non-final sigma fold to the same letter.
+    assert_eq!(
+        find_best_match_for_name(&["ΟΔΟΣ"], "οδος", Some(0)),
+        Some("ΟΔΟΣ")
+    );
+}
+
+#[test]
+fn case_insensitive() {
+    use rustc_edit_distance::edit_distance_ci;
+
+    assert_eq!(edit_distance_ci("HELLO", "hello", 0), Some(0));
+    assert_eq!(edit_distance_ci("Tier", "tier", 0), Some(0));
+    assert_eq!(edit_distance_ci("Kitten", "SITTING", 5), Some(3));
+    assert_eq!(edit_distance_ci("HeLLo", "hxllo", 0), None);
+    assert_eq!(edit_distance_ci("HeLLo", "hxllo", 1), Some(1));
+    assert_eq!(edit_distance_ci("STRASSE", "straße", 0), Some(0));
+}
+
+#[test]
+fn ascii_case_insensitive() {
+    use rustc_edit_distance::{edit_distance_ascii_ci, edit_distance_ci};
+
+    assert_eq!(edit_distance_ascii_ci("Kitten", "kitten", 0), Some(0));
+    assert_eq!(edit_distance_ascii_ci("Kitten", "SITTING", 5), Some(3));
+    assert_eq!(edit_distance_ascii_ci("HeLLo", "hxllo", 0), None);
+    assert_eq!(edit_distance_ascii_ci("Äpfel", "äpfel", 5), Some(1));
+    assert_eq!(edit_distance_ci("Äpfel", "äpfel", 5), Some(0));
+    assert_eq!(edit_distance_ascii_ci("STRASSE", "straße", 5), Some(2));
+    for (a, b) in [
+        ("MixedCase", "mIXEDcASE"),
+        ("abc_DEF", "ABC-def"),
+        ("", "XYZ"),
+        // A transposition of letters in another case.
+        ("aB", "bA"),
+        (&"Ab".repeat(40), &"bA".repeat(40)),
+    ] {
+        assert_eq!(
+            edit_distance_ascii_ci(a, b, usize::MAX),
+            edit_distance_ci(a, b, usize::MAX)
+        );
+    }
+    let (a, b) = ("Äpfel".repeat(20), "äPFEL".repeat(20));
+    assert_eq!(edit_distance_ascii_ci(&a, &b, usize::MAX), Some(20));
+}
+
+#[test]
+fn best_match_sorted_words_separators() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_with_separators};
+
+    let candidates = ["level-log", "log.format", "max_width"];
+    assert_eq!(
+        find_best_match_for_name(&candidates, "width_max", None),
+        Some("max_width")
+    );
+    assert_eq!(
+        find_best_match_for_name(&candidates, "log-level", None),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_with_separators(&candidates, "log-level", None, &['-']),
+        Some("level-log")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_separators(&candidates, "format.log", None, &['-', '.']),
+        Some("log.format")
+    );
+}
+
+#[test]
+fn best_match_ties_are_order_independent() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_with_substrings};
+
+    let mut candidates = vec!["cat", "bat", "hat", "rat_"];
+    for _ in 0..candidates.len() {
+        candidates.rotate_left(1);
+        assert_eq!(
+            find_best_match_for_name(&candidates, "mat", None),
+            Some("bat")
+        );
+    }
+
+    // All three tie on both the substring score and the edit distance.
+    let mut candidates = vec!["forced_capturx", "forced_captura", "forcd_capture"];
+    for _ in 0..candidates.len() {
+        candidates.rotate_left(1);
+        assert_eq!(
+            find_best_match_for_name_with_substrings(&candidates, "forced_capture", None),
+            Some("forcd_capture")
+        );
+    }
+}
+
+#[test]
+fn try_edit_distance_cell_cap() {
+    use rustc_edit_distance::{try_edit_distance, EditDistanceError};
+
+    assert_eq!(try_edit_distance("kitten", "sitting", 10, 100), Ok(Some(3)));
+    assert_eq!(
+        try_edit_distance("kitten", "sitting", 10, 41),
+        Err(EditDistanceError::TooLarge {
+            cells: 42,
+            max_cells: 41
+        })
+    );
+    // Only `en` and `ing` remain once the common prefix is removed: 2 * 3 cells.
+    assert_eq!(try_edit_distance("kitten", "kitting", 10, 6), Ok(Some(2)));
+    assert!(try_edit_distance("kitten", "kitting", 10, 5).is_err());
+    // Answered without any cells: equal strings, and a length difference over the limit.
+    let long = "x".repeat(10_000);
+    assert_eq!(try_edit_distance(&long, &long, 0, 0), Ok(Some(0)));
+    assert_eq!(try_edit_distance(&long, "x", 5, 0), Ok(None));
+    assert!(try_edit_distance(&long, &"y".repeat(10_000), 5, 1_000_000).is_err());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_agrees_with_serial() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_parallel};
+
+    let candidates = (0..2000)
+        .map(|i| format!("symbol_{}_{}", i % 37, i))
+        .chain(["max_width".to_string(), "SYMBOL".to_string()])
+        .collect::<Vec<_>>();
+    let candidates = candidates.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+    for lookup in [
+        "symbl_3_14",
+        "symbol_99_12",
+        "width_max",
+        "symbol",
+        "nothing_like_it",
+    ] {
+        assert_eq!(
+            find_best_match_for_name_parallel(&candidates, lookup, None),
+            find_best_match_for_name(&candidates, lookup, None),
+            "lookup = {lookup:?}"
+        );
+    }
+
+    // Many candidates tie at the smallest distance, whichever thread finds them first.
+    let ties = (0..10_000)
+        .map(|i| format!("sym{:04}", i % 9000))
+        .collect::<Vec<_>>();
+    let mut ties = ties.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+    for _ in 0..2 {
+        assert_eq!(
+            find_best_match_for_name_parallel(&ties, "sym9x99", Some(2)),
+            find_best_match_for_name(&ties, "sym9x99", Some(2)),
+        );
+        ties.reverse();
+    }
+}
+
+#[test]
+fn matrix() {
+    use rustc_edit_distance::edit_distance_matrix;
+
+    assert_eq!(
+        edit_distance_matrix("ab", "bca"),
+        vec![vec![0, 1, 2, 3], vec![1, 1, 2, 2], vec![2, 1, 2, 3]]
+    );
+    assert_eq!(edit_distance_matrix("", ""), vec![vec![0]]);
+    assert_eq!(edit_distance_matrix("", "ab"), vec![vec![0, 1, 2]]);
+}
+
+#[test]
+fn matrix_corner_is_distance_property() {
+    fn matrix_corner_is_distance(a: String, b: String) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let matrix = rustc_edit_distance::edit_distance_matrix(&a, &b);
+        matrix.last().and_then(|row| row.last()) == Some(&edit_distance_unchecked(&a, &b))
+    }
+
+    quickcheck(matrix_corner_is_distance as fn(a: String, b: String) -> bool);
+}
+
+#[test]
+fn prefix() {
+    use rustc_edit_distance::prefix_distance;
+
+    assert_eq!(prefix_distance("recv", "receive_message", 5), Some(1));
+    assert_eq!(prefix_distance("rec", "receive_message", 0), Some(0));
+    assert_eq!(prefix_distance("", "anything", 0), Some(0));
+    assert_eq!(prefix_distance("abc", "", 5), Some(3));
+    assert_eq!(prefix_distance("pritn", "println", 1), Some(1));
+    // The match is anchored at the start: `message` in the middle doesn't help.
+    assert_eq!(prefix_distance("message", "receive_message", 3), None);
+}
+
+#[test]
+fn prefix_is_min_over_prefixes_property() {
+    fn prefix_is_min_over_prefixes(a: String, b: String, limit: u8) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let limit = usize::from(limit % 6);
+        let expected = (0..=b.len())
+            .map(|end| reference_distance(&a, &b[..end]))
+            .min()
+            .filter(|&d| d <= limit);
+        rustc_edit_distance::prefix_distance(&a, &b, limit) == expected
+    }
+
+    quickcheck(prefix_is_min_over_prefixes as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[test]
+fn chars_agrees_with_reference_property() {
+    fn chars_agrees_with_reference(a: String, b: String, limit: u8) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let limit = usize::from(limit % 6);
+        let expected = Some(reference_distance(&a, &b)).filter(|&d| d <= limit);
+        rustc_edit_distance::edit_distance_chars(&a, b.chars(), limit) == expected
+            && rustc_edit_distance::edit_distance_chars(&b, a.chars(), limit) == expected
+    }
+
+    quickcheck(chars_agrees_with_reference as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[test]
+fn chars_stops_reading_past_the_limit() {
+    use rustc_edit_distance::edit_distance_chars;
+
+    assert_eq!(edit_distance_chars("kitten", "sitting".chars(), 3), Some(3));
+    assert_eq!(edit_distance_chars("", "abc".chars(), 3), Some(3));
+    assert_eq!(edit_distance_chars("abc", "".chars(), 2), None);
+    assert_eq!(edit_distance_chars("😀ab", "😀ba".chars(), 1), Some(1));
+
+    // An endless `b` is fine, as long as the limit is exceeded at some point.
+    assert_eq!(edit_distance_chars("abc", std::iter::repeat('a'), 3), None);
+    let mut read = 0;
+    let b = "abc"
+        .chars()
+        .chain(std::iter::repeat('x'))
+        .inspect(|_| read += 1);
+    assert_eq!(edit_distance_chars("abc", b, 2), None);
+    assert_eq!(read, 7);
+}
+
+#[test]
+fn substrings_span() {
+    use rustc_edit_distance::{edit_distance_with_substrings, edit_distance_with_substrings_span};
+
+    assert_eq!(
+        edit_distance_with_substrings_span("capture", "force_capture", 10),
+        Some((1, (6, 13)))
+    );
+    assert_eq!(
+        edit_distance_with_substrings_span("ab", "xxabyyab", 10),
+        Some((6, (2, 4)))
+    );
+    assert_eq!(
+        edit_distance_with_substrings_span("cptur", "capture_x", 10).map(|(_, span)| span),
+        Some((0, 6))
+    );
+    assert_eq!(
+        edit_distance_with_substrings_span("ünï", "aünïb", 10).map(|(_, span)| span),
+        Some((1, 4))
+    );
+    assert_eq!(
+        edit_distance_with_substrings_span("capture", "force_capture", 0),
+        None
+    );
+    for (a, b) in [
+        ("forced_capture", "force_capture"),
+        ("a", "cba"),
+        ("", "abc"),
+    ] {
+        assert_eq!(
+            edit_distance_with_substrings_span(a, b, 10).map(|(score, _)| score),
+            edit_distance_with_substrings(a, b, 10)
+        );
+    }
+}
+
+#[test]
+fn custom_substitution_cost() {
+    use rustc_edit_distance::{edit_distance, edit_distance_with};
+
+    // Substituting a digit for a digit is free, everything else costs as usual.
+    let digits_free = |x: char, y: char| {
+        if x == y || (x.is_ascii_digit() && y.is_ascii_digit()) {
+            0
+        } else {
+            1
+        }
+    };
+    assert_eq!(
+        edit_distance_with("v1.2.3", "v4.5.6", 5, digits_free),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_with("v1.2.3", "w4.5.6", 5, digits_free),
+        Some(1)
+    );
+
+    // The arguments are always a char of `a` and a char of `b`, even for a shorter `a`.
+    let only_a_to_b = |x: char, y: char| if x == y || (x, y) == ('a', 'b') { 0 } else { 2 };
+    assert_eq!(edit_distance_with("a", "bcd", 5, only_a_to_b), Some(2));
+    assert_eq!(edit_distance_with("bcd", "a", 5, only_a_to_b), Some(4));
+
+    let unit = |x: char, y: char| usize::from(x != y);
+    let huge = |x: char, y: char| if x == y { 0 } else { usize::MAX };
+    for (a, b) in [("kitten", "sitting"), ("ab", "ba"), ("", "abc")] {
+        assert_eq!(edit_distance_with(a, b, 10, unit), edit_distance(a, b, 10));
+        assert!(edit_distance_with(a, b, 10, huge) >= edit_distance(a, b, 10));
+    }
+}
+
+#[test]
+fn ranking() {
+    use rustc_edit_distance::rank_candidates;
+
+    assert_eq!(
+        rank_candidates(
+            &["sitting", "kitchen", "mitten", "bitten", "kitten"],
+            "kitten"
+        ),
+        vec![
+            ("kitten", 0),
+            ("mitten", 1),
+            ("bitten", 1),
+            ("kitchen", 2),
+            ("sitting", 3)
+        ]
+    );
+    assert_eq!(rank_candidates(&[], "kitten"), vec![]);
+}
+
+#[test]
+fn bit_parallel_agrees_with_reference_property() {
+    fn bit_parallel_agrees_with_reference(a: String, b: String, limit: u8) -> bool {
+        // Short enough for the fast path, with chars below and above 256.
+        let a = small_alphabet(&a).chars().take(70).collect::<String>();
+        let b = small_alphabet(&b).chars().take(70).collect::<String>();
+        let limit = usize::from(limit % 8);
+        let expected = Some(reference_distance(&a, &b)).filter(|&d| d <= limit);
+        let wide = |s: &str| s.replace('c', "ĉ");
+        rustc_edit_distance::edit_distance(&a, &b, limit) == expected
+            && rustc_edit_distance::edit_distance_bytes(a.as_bytes(), b.as_bytes(), limit)
+                == expected
+            && rustc_edit_distance::edit_distance(&wide(&a), &wide(&b), limit) == expected
+    }
+
+    quickcheck(bit_parallel_agrees_with_reference as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[test]
+fn bit_parallel_full_width() {
+    let a = "ab".repeat(32);
+    let b = "ba".repeat(32);
+    assert_eq!(edit_distance_unchecked(&a, &b), reference_distance(&a, &b));
+    assert_eq!(
+        edit_distance_unchecked(&a, &b[1..]),
+        reference_distance(&a, &b[1..])
+    );
+    assert_eq!(
+        edit_distance_unchecked(&"x".repeat(64), &"y".repeat(64)),
+        64
+    );
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn normalized() {
+    use rustc_edit_distance::{edit_distance, edit_distance_normalized};
+
+    let (composed, decomposed) = ("caf\u{e9}", "cafe\u{301}");
+    assert_eq!(edit_distance(composed, decomposed, 5), Some(2));
+    assert_eq!(edit_distance_normalized(composed, decomposed, 0), Some(0));
+    assert_eq!(edit_distance_normalized(composed, "cafe", 5), Some(1));
+    assert_eq!(edit_distance_normalized(decomposed, "cafe", 5), Some(1));
+}
+
+#[test]
+fn longest_common_subsequence() {
+    use rustc_edit_distance::{lcs_length, longest_common_subsequence};
+
+    assert_eq!(longest_common_subsequence("", ""), "");
+    assert_eq!(longest_common_subsequence("abc", ""), "");
+    assert_eq!(longest_common_subsequence("", "abc"), "");
+    assert_eq!(longest_common_subsequence("ABCBDAB", "BDCABA"), "BCBA");
+    assert_eq!(longest_common_subsequence("kitten", "sitting"), "ittn");
+    assert_eq!(longest_common_subsequence("ab", "ba"), "a");
+    assert_eq!(longest_common_subsequence("приветик", "привет"), "привет");
+    assert_eq!(lcs_length("ABCBDAB", "BDCABA"), 4);
+    assert_eq!(lcs_length("", "abc"), 0);
+}
+
+#[test]
+fn indel_distance() {
+    use rustc_edit_distance::{edit_distance, indel_distance};
+
+    assert_eq!(indel_distance("kitten", "sitting", 10), Some(5));
+    assert_eq!(indel_distance("kitten", "sitting", 4), None);
+    // A transposition, or a substitution, takes two edits.
+    assert_eq!(indel_distance("ab", "ba", 10), Some(2));
+    assert_eq!(edit_distance("ab", "ba", 10), Some(1));
+    assert_eq!(indel_distance("abc", "axc", 10), Some(2));
+    assert_eq!(indel_distance("", "abc", 10), Some(3));
+    assert_eq!(indel_distance("abcdef", "a", 4), None);
+    assert_eq!(indel_distance("ünï", "ün", 1), Some(1));
+}
+
+/// Distance with insertions and deletions only, over the full matrix.
+fn reference_indel_distance(a: &[char], b: &[char]) -> usize {
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    d[0] = (0..=b.len()).collect();
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            d[i][j] = if a[i - 1] == b[j - 1] {
+                d[i - 1][j - 1]
+            } else {
+                (d[i - 1][j] + 1).min(d[i][j - 1] + 1)
+            };
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+#[test]
+fn lcs_length_agrees_with_indel_distance_property() {
+    fn is_subsequence(s: &str, of: &str) -> bool {
+        let mut of = of.chars();
+        s.chars().all(|c| of.any(|x| x == c))
+    }
+
+    fn lcs_agrees_with_indel(a: String, b: String) -> bool {
+        let a = small_alphabet(&a).replace('c', "ĉ");
+        let b = small_alphabet(&b).replace('c', "ĉ");
+        let (ac, bc) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        let len = rustc_edit_distance::lcs_length(&a, &b);
+        let lcs = rustc_edit_distance::longest_common_subsequence(&a, &b);
+        let indel = reference_indel_distance(&ac, &bc);
+        len == (ac.len() + bc.len() - indel) / 2
+            && rustc_edit_distance::indel_distance(&a, &b, usize::MAX) == Some(indel)
+            && rustc_edit_distance::indel_distance(&a, &b, indel.saturating_sub(1))
+                == Some(indel).filter(|&d| d == 0)
+            && lcs.chars().count() == len
+            && is_subsequence(&lcs, &a)
+            && is_subsequence(&lcs, &b)
+    }
+
+    quickcheck(lcs_agrees_with_indel as fn(a: String, b: String) -> bool);
+}
+
+#[test]
+fn substrings_with_small_limits() {
+    use rustc_edit_distance::edit_distance_with_substrings;
+
+    assert_eq!(edit_distance_with_substrings("abc", "abc", 0), Some(0));
+    assert_eq!(edit_distance_with_substrings("abc", "abd", 0), None);
+    assert_eq!(edit_distance_with_substrings("abc", "abd", 1), Some(1));
+    // A substring, or any other subsequence, scores 1, not 0.
+    assert_eq!(edit_distance_with_substrings("foo", "foobar", 0), None);
+    assert_eq!(edit_distance_with_substrings("foo", "foobar", 1), Some(1));
+    assert_eq!(edit_distance_with_substrings("foobar", "foo", 1), Some(1));
+    assert_eq!(edit_distance_with_substrings("ünï", "ünïx", 1), Some(1));
+    assert_eq!(edit_distance_with_substrings("fob", "foobar", 1), Some(1));
+    assert_eq!(edit_distance_with_substrings("fxb", "foobar", 2), None);
+    assert_eq!(edit_distance_with_substrings("fxb", "foobar", 3), Some(3));
+    // With a big difference in length, all of it is added back.
+    assert_eq!(edit_distance_with_substrings("ab", "abcdef", 1), None);
+    assert_eq!(edit_distance_with_substrings("ab", "abcdef", 4), Some(4));
+    assert_eq!(edit_distance_with_substrings("", "a", 0), None);
+    assert_eq!(edit_distance_with_substrings("", "a", 1), Some(1));
+    assert_eq!(
+        edit_distance_with_substrings("a", "abc", usize::MAX),
+        Some(2)
+    );
+}
+
+#[test]
+fn best_match_with_substrings() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_with_substrings};
+
+    let candidates = ["capture", "later"];
+    assert_eq!(find_best_match_for_name(&candidates, "cptr", None), None);
+    assert_eq!(
+        find_best_match_for_name_with_substrings(&candidates, "cptr", None),
+        Some("capture")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_substrings(
+            &["force_capture", "forced_captor"],
+            "forced_capture",
+            None
+        ),
+        Some("force_capture")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_substrings(&candidates, "xyz", None),
+        None
+    );
+}
+
+#[test]
+fn best_match_with_options() {
+    use rustc_edit_distance::{
+        find_best_match_for_name, find_best_match_for_name_with, MatchOptions,
+    };
+
+    let candidates = ["LogLevel", "log-level", "capture"];
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "logleve", &MatchOptions::new()),
+        find_best_match_for_name(&candidates, "logleve", None)
+    );
+    assert_eq!(
+        find_best_match_for_name_with(
+            &candidates,
+            "lgolevel",
+            &MatchOptions::new().max_distance(1)
+        ),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_with(
+            &candidates,
+            "lgolevel",
+            &MatchOptions::new().case_insensitive(true).max_distance(1)
+        ),
+        Some("LogLevel")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(
+            &candidates,
+            "cptr",
+            &MatchOptions::new().use_substrings(true)
+        ),
+        Some("capture")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(
+            &candidates,
+            "level-log",
+            &MatchOptions::new().separators(&['-']).max_distance(0)
+        ),
+        Some("log-level")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "captrue", &MatchOptions::new().max_distance(0)),
+        None
+    );
+}
+
+#[test]
+fn damerau_levenshtein() {
+    use rustc_edit_distance::{damerau_levenshtein, edit_distance, osa_distance};
+
+    // The cases where the optimal string alignment distance is larger.
+    for (a, b, osa, dl) in [
+        ("ca", "abc", 3, 2),
+        ("ac", "cba", 3, 2),
+        ("abcd", "bdac", 4, 3),
+        ("ca", "ac", 1, 1),
+        ("kitten", "sitting", 3, 3),
+        ("", "abc", 3, 3),
+        ("привет", "рпивет", 1, 1),
+    ] {
+        assert_eq!(edit_distance(a, b, usize::MAX), Some(osa), "{a} {b}");
+        assert_eq!(osa_distance(a, b, usize::MAX), Some(osa), "{a} {b}");
+        assert_eq!(damerau_levenshtein(a, b, usize::MAX), Some(dl), "{a} {b}");
+        assert_eq!(damerau_levenshtein(b, a, usize::MAX), Some(dl), "{a} {b}");
+        assert_eq!(damerau_levenshtein(a, b, dl - 1), None, "{a} {b}");
+    }
+}
+
+#[test]
+fn damerau_levenshtein_is_a_metric_property() {
+    fn damerau_levenshtein_is_a_metric(a: String, b: String, c: String) -> bool {
+        let d = |x: &str, y: &str| rustc_edit_distance::damerau_levenshtein(x, y, usize::MAX);
+        let (a, b, c) = (small_alphabet(&a), small_alphabet(&b), small_alphabet(&c));
+        let (ab, bc, ac) = (d(&a, &b).unwrap(), d(&b, &c).unwrap(), d(&a, &c).unwrap());
+        ac <= ab + bc
+            && ab == d(&b, &a).unwrap()
+            && (ab == 0) == (a == b)
+            && ab <= reference_distance(&a, &b)
+    }
+
+    quickcheck(damerau_levenshtein_is_a_metric as fn(a: String, b: String, c: String) -> bool);
+}
+
+#[test]
+fn similarity_ratio() {
+    use rustc_edit_distance::similarity_ratio;
+
+    assert_eq!(similarity_ratio("", ""), 1.0);
+    assert_eq!(similarity_ratio("abc", "abc"), 1.0);
+    assert_eq!(similarity_ratio("abc", ""), 0.0);
+    assert_eq!(similarity_ratio("abc", "xyz"), 0.0);
+    assert_eq!(similarity_ratio("abcd", "abxd"), 0.75);
+    assert_eq!(similarity_ratio("ab", "abcd"), 0.5);
+    assert_eq!(similarity_ratio("über", "uber"), 0.75);
+}
+
+#[test]
+fn best_match_by_ratio() {
+    use rustc_edit_distance::find_best_match_for_name_by_ratio;
+
+    // Three edits are fine for a long candidate, but not for a short one.
+    let candidates = ["initialize_all_handlers", "initialize"];
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&candidates, "initalise_all_handler", 0.8),
+        Some("initialize_all_handlers")
+    );
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&["inti", "exit"], "ixnt", 0.8),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&["abcd", "abce", "abxy"], "abcf", 0.75),
+        Some("abcd")
+    );
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&["abcd", "abxy"], "abcf", 0.8),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&["abxy", "abcd"], "abcf", 0.5),
+        Some("abcd")
+    );
+    assert_eq!(
+        find_best_match_for_name_by_ratio(&["level_log"], "log_level", 1.0),
+        Some("level_log")
+    );
+}
+
+#[test]
+fn default_match_distance() {
+    use rustc_edit_distance::{default_match_distance, find_best_match_for_name};
+
+    for (lookup, dist) in [
+        ("", 1),
+        ("a", 1),
+        ("abcde", 1),
+        ("abcdef", 2),
+        ("abcdefghi", 3),
+        ("ääääää", 2),
+    ] {
+        assert_eq!(default_match_distance(lookup), dist, "{lookup}");
+    }
+
+    let candidates = ["abcdxy", "abcdez"];
+    assert_eq!(default_match_distance("abcdef"), 2);
+    assert_eq!(
+        find_best_match_for_name(&candidates, "abcdef", None),
+        find_best_match_for_name(&candidates, "abcdef", Some(2))
+    );
+    assert_eq!(
+        find_best_match_for_name(&candidates, "abcdef", None),
+        Some("abcdez")
+    );
+    // `Some(0)` only allows matches that need no edits.
+    assert_eq!(
+        find_best_match_for_name(&candidates, "abcdef", Some(0)),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name(&candidates, "ABCDEZ", Some(0)),
+        Some("abcdez")
+    );
+}
+
+#[test]
+fn jaro_winkler() {
+    use rustc_edit_distance::{jaro, jaro_winkler, jaro_winkler_with_prefix_weight};
+
+    let close = |x: f64, y: f64| (x - y).abs() < 0.001;
+    // Reference values from Winkler's papers and the Wikipedia article.
+    for (a, b, j, jw) in [
+        ("MARTHA", "MARHTA", 0.944, 0.961),
+        ("DWAYNE", "DUANE", 0.822, 0.840),
+        ("DIXON", "DICKSONX", 0.767, 0.813),
+        ("FAREMVIEL", "FARMVILLE", 0.884, 0.919),
+        ("CRATE", "TRACE", 0.733, 0.733),
+    ] {
+        assert!(close(jaro(a, b), j), "{a} {b} {}", jaro(a, b));
+        assert!(
+            close(jaro_winkler(a, b), jw),
+            "{a} {b} {}",
+            jaro_winkler(a, b)
+        );
+        assert!(close(jaro_winkler(b, a), jw), "{a} {b}");
+    }
+    assert_eq!(jaro_winkler("", ""), 1.0);
+    assert_eq!(jaro_winkler("abc", ""), 0.0);
+    assert_eq!(jaro_winkler("abc", "xyz"), 0.0);
+    assert_eq!(jaro_winkler("ünïcödé", "ünïcödé"), 1.0);
+    let (a, b) = ("DWAYNE", "DUANE");
+    assert_eq!(jaro_winkler_with_prefix_weight(a, b, 0.0), jaro(a, b));
+    assert_eq!(
+        jaro_winkler_with_prefix_weight(a, b, 0.1),
+        jaro_winkler(a, b)
+    );
+    assert!(close(jaro_winkler_with_prefix_weight(a, b, 0.2), 0.858));
+    // The prefix boost stops after four chars.
+    let j = jaro("abcdefx", "abcdefy");
+    assert!(close(
+        jaro_winkler("abcdefx", "abcdefy"),
+        j + 0.4 * (1.0 - j)
+    ));
+}
+
+#[test]
+fn best_match_with_jaro_winkler() {
+    use rustc_edit_distance::{find_best_match_for_name_with, MatchOptions, Scorer};
+
+    let options = MatchOptions::new().scorer(Scorer::JaroWinkler {
+        min_similarity: 0.8,
+    });
+    let candidates = ["status", "stash", "push", "pull"];
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "stat", &options),
+        Some("status")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "stsh", &options),
+        Some("stash")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "commit", &options),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "PUSH", &options),
+        Some("push")
+    );
+}
+
+#[test]
+fn capped() {
+    use rustc_edit_distance::edit_distance_capped;
+
+    assert_eq!(edit_distance_capped("kitten", "sitting", 3), 3);
+    assert_eq!(edit_distance_capped("kitten", "sitting", 5), 3);
+    // `limit + 1` only means the distance is larger than `limit`.
+    assert_eq!(edit_distance_capped("kitten", "sitting", 2), 3);
+    assert_eq!(edit_distance_capped("kitten", "", 2), 3);
+    assert_eq!(edit_distance_capped("abc", "xyz", 0), 1);
+    assert_eq!(edit_distance_capped("abc", "abc", 0), 0);
+    assert_eq!(edit_distance_capped("abc", "xyz", usize::MAX), 3);
+
+    let best = ["sitting", "kitchen", "mitten"]
+        .iter()
+        .map(|c| edit_distance_capped("kitten", c, 1))
+        .min();
+    assert_eq!(best, Some(1));
+}
+
+#[test]
+fn best_match_empty_inputs() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_iter};
+
+    assert_eq!(find_best_match_for_name(&[], "", None), None);
+    assert_eq!(find_best_match_for_name(&[], "abc", None), None);
+    assert_eq!(find_best_match_for_name(&["a", "ab"], "", None), None);
+    assert_eq!(find_best_match_for_name(&["a", ""], "", None), Some(""));
+    assert_eq!(find_best_match_for_name_iter(["a", ""], "", None), Some(""));
+    // An empty candidate isn't a substring match, though it may still be within `dist`.
+    assert_eq!(find_best_match_for_name(&["", "xyz"], "abc", None), None);
+    assert_eq!(
+        find_best_match_for_name(&["", "abd"], "abc", None),
+        Some("abd")
+    );
+    assert_eq!(find_best_match_for_name(&[""], "a", None), Some(""));
+    assert_eq!(find_best_match_for_name(&[""], "a", Some(0)), None);
+}
+
+#[test]
+fn best_match_with_limits() {
+    use rustc_edit_distance::find_best_match_for_name_with_limits;
+
+    // `connect` is a strict public name, the others lenient internal helpers.
+    let candidates = [("connect", 0), ("conn_helper", 4), ("do_connect_impl", 8)];
+    assert_eq!(
+        find_best_match_for_name_with_limits(&candidates, "conect"),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_with_limits(&candidates, "conn_halper"),
+        Some("conn_helper")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_limits(&[("connect", 1), ("conned", 2)], "conect"),
+        Some("connect")
+    );
+    // A closer candidate wins even if a farther one is allowed more edits.
+    assert_eq!(
+        find_best_match_for_name_with_limits(&[("abcxy", 9), ("abcdx", 1)], "abcde"),
+        Some("abcdx")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_limits(&[("level_log", 0)], "log_level"),
+        Some("level_log")
+    );
+    assert_eq!(find_best_match_for_name_with_limits(&[], "abc"), None);
+}
+
+#[test]
+fn substrings_score_around_the_length_boundary() {
+    use rustc_edit_distance::edit_distance_with_substrings;
+
+    for (a, b, score) in [
+        // Up to twice the length, containing `a` scores 1.
+        ("abc", "abcx", 1),
+        ("abc", "abcxyz", 1),
+        ("abcd", "abcdwxyz", 1),
+        // Beyond it, the whole difference in length counts.
+        ("abc", "abcxyzw", 4),
+        ("abc", "abcxyzwv", 5),
+        ("abcd", "abcdvwxyz", 5),
+        // With a typo, half the difference in length counts, and then all of it.
+        ("abc", "abxx", 2),
+        ("abc", "abxxyz", 3),
+        ("abc", "abxxyzw", 5),
+        // A substring is not always better than a closer non-substring.
+        ("ab", "abxyz", 3),
+        ("ab", "xbxy", 2),
+    ] {
+        assert_eq!(
+            edit_distance_with_substrings(a, b, usize::MAX),
+            Some(score),
+            "{a} {b}"
+        );
+        assert_eq!(
+            edit_distance_with_substrings(b, a, usize::MAX),
+            Some(score),
+            "{a} {b}"
+        );
+        assert_eq!(
+            edit_distance_with_substrings(a, b, score - 1),
+            None,
+            "{a} {b}"
+        );
+    }
+}
+
+#[test]
+fn best_match_owned() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_owned};
+    use std::borrow::Cow;
+
+    let owned = vec![
+        String::from("sorted"),
+        String::from("sort_by_key"),
+        String::from("key_by_sort"),
+    ];
+    assert_eq!(
+        find_best_match_for_name_owned(&owned, "sortd", None),
+        Some(0)
+    );
+    assert_eq!(
+        find_best_match_for_name_owned(&owned, "by_sort_key", None),
+        Some(2)
+    );
+    assert_eq!(find_best_match_for_name_owned(&owned, "xyzzy", None), None);
+
+    let cows = [
+        Cow::Borrowed("HashMap"),
+        Cow::Owned(String::from("VecDeque")),
+    ];
+    assert_eq!(
+        find_best_match_for_name_owned(&cows, "vecdeque", None),
+        Some(1)
+    );
+
+    let borrowed = owned.iter().map(String::as_str).collect::<Vec<_>>();
+    for lookup in ["sortd", "sort_by", "key_sort_by", "xyzzy"] {
+        assert_eq!(
+            find_best_match_for_name_owned(&owned, lookup, None).map(|i| borrowed[i]),
+            find_best_match_for_name(&borrowed, lookup, None),
+            "{lookup}"
+        );
+    }
+}
+
+#[test]
+fn slices() {
+    use rustc_edit_distance::{
+        edit_distance_slices, edit_distance_with_substrings, edit_distance_with_substrings_slices,
+    };
+
+    #[derive(PartialEq)]
+    enum Token {
+        Ident(&'static str),
+        Dot,
+        Paren,
+    }
+    use Token::*;
+
+    let a = [Ident("foo"), Dot, Ident("bar"), Paren];
+    let b = [Ident("foo"), Ident("bar"), Dot, Paren];
+    assert_eq!(edit_distance_slices(&a, &b, usize::MAX), Some(1));
+    assert_eq!(edit_distance_slices(&a, &a[..2], usize::MAX), Some(2));
+    assert_eq!(edit_distance_slices(&a, &b, 0), None);
+    assert_eq!(
+        edit_distance_slices::<u64>(&[], &[1, 2, 3], usize::MAX),
+        Some(3)
+    );
+
+    assert_eq!(
+        edit_distance_with_substrings_slices(&a[1..3], &a, usize::MAX),
+        Some(1)
+    );
+    for (x, y) in [("abc", "abcxyzw"), ("abc", "abxxyz"), ("kitten", "sitting")] {
+        let (xs, ys) = (x.chars().collect::<Vec<_>>(), y.chars().collect::<Vec<_>>());
+        assert_eq!(
+            edit_distance_with_substrings_slices(&xs, &ys, 10),
+            edit_distance_with_substrings(x, y, 10)
+        );
+    }
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn best_match_normalized() {
+    use rustc_edit_distance::{
+        edit_distance_nfkc, find_best_match_for_name_with, MatchOptions, Normalization,
+    };
+
+    assert_eq!(edit_distance_nfkc("\u{FB01}le", "file", 0), Some(0));
+    assert_eq!(edit_distance_nfkc("\u{FF21}B", "AB", 0), Some(0));
+    assert_eq!(edit_distance_nfkc("cafe\u{301}", "caf\u{e9}", 0), Some(0));
+
+    // Decomposed candidates, as they may come from another platform.
+    let candidates = ["re\u{301}sume\u{301}", "cafe\u{301}_au_lait"];
+    let nfc = MatchOptions::new().normalization(Normalization::Nfc);
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "r\u{e9}sum", &MatchOptions::new()),
+        None
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "r\u{e9}sum", &nfc),
+        Some("re\u{301}sume\u{301}")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "au_lait_caf\u{e9}", &nfc),
+        Some("cafe\u{301}_au_lait")
+    );
+    let nfkc = MatchOptions::new().normalization(Normalization::Nfkc);
+    assert_eq!(
+        find_best_match_for_name_with(&["\u{FB01}nd"], "find", &nfkc),
+        Some("\u{FB01}nd")
+    );
+}
+
+#[test]
+fn weighted() {
+    use rustc_edit_distance::{weighted_edit_distance, EditCosts};
+
+    let cheap_insert = EditCosts {
+        insert: 1,
+        delete: 3,
+        substitute: 2,
+        transpose: 0,
+    };
+    assert_eq!(
+        weighted_edit_distance("helo", "hello", 10, &cheap_insert),
+        Some(1)
+    );
+    assert_eq!(
+        weighted_edit_distance("hello", "helo", 10, &cheap_insert),
+        Some(3)
+    );
+    assert_eq!(
+        weighted_edit_distance("hlelo", "hello", 0, &cheap_insert),
+        Some(0)
+    );
+    // Substituting costs 2, but deleting then inserting costs 4.
+    assert_eq!(
+        weighted_edit_distance("cat", "cut", 10, &cheap_insert),
+        Some(2)
+    );
+    assert_eq!(weighted_edit_distance("cat", "cut", 1, &cheap_insert), None);
+    assert_eq!(
+        weighted_edit_distance("", "abc", 10, &cheap_insert),
+        Some(3)
+    );
+    assert_eq!(
+        weighted_edit_distance("abc", "", 10, &cheap_insert),
+        Some(9)
+    );
+    assert_eq!(weighted_edit_distance("abc", "", 8, &cheap_insert), None);
+
+    let free = EditCosts {
+        insert: 0,
+        delete: 0,
+        substitute: 0,
+        transpose: 0,
+    };
+    assert_eq!(weighted_edit_distance("abc", "xyzw", 0, &free), Some(0));
+}
+
+#[test]
+fn weighted_with_unit_costs_agrees_with_reference_property() {
+    fn weighted_agrees_with_reference(a: String, b: String, limit: u8) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let limit = usize::from(limit % 8);
+        let expected = Some(reference_distance(&a, &b)).filter(|&d| d <= limit);
+        let costs = rustc_edit_distance::EditCosts::default();
+        rustc_edit_distance::weighted_edit_distance(&a, &b, limit, &costs) == expected
+    }
+
+    quickcheck(weighted_agrees_with_reference as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[test]
+fn per_char_costs() {
+    use rustc_edit_distance::edit_distance_with_costs;
+
+    let digits = |x: char, y: char| {
+        if x.is_ascii_digit() && y.is_ascii_digit() {
+            1
+        } else {
+            3
+        }
+    };
+    // Whitespace is free to insert or delete, anything else costs 2.
+    let space = |c: char| if c.is_whitespace() { 0 } else { 2 };
+    assert_eq!(
+        edit_distance_with_costs("v1.2", "v1.3", 10, space, space, digits),
+        Some(1)
+    );
+    assert_eq!(
+        edit_distance_with_costs("v1.2", "v1.x", 10, space, space, digits),
+        Some(3)
+    );
+    assert_eq!(
+        edit_distance_with_costs("a b", "ab", 0, space, space, digits),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_with_costs("ab", "a b  ", 0, space, space, digits),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_with_costs("ab", "abc", 1, space, space, digits),
+        None
+    );
+    assert_eq!(
+        edit_distance_with_costs("ab", "ba", 1, space, space, digits),
+        Some(1)
+    );
+    assert_eq!(
+        edit_distance_with_costs("kitten", "sitting", 10, |_| 1, |_| 1, |_, _| 1),
+        Some(3)
+    );
+}
+
+/// Applies `ops`, as returned by `edit_ops(a, b, ..)`, to `a`.
+fn apply_edit_ops(a: &str, b: &str, ops: &[rustc_edit_distance::EditOp]) -> String {
+    use rustc_edit_distance::EditOp::*;
+
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut out = Vec::new();
+    let mut i = 0;
+    for op in ops {
+        let (source, target) = match *op {
+            Insert { source, target }
+            | Delete { source, target }
+            | Substitute { source, target }
+            | Transpose { source, target } => (source, target),
+        };
+        // Everything between two edits is kept as it is.
+        out.extend_from_slice(&a[i..source]);
+        assert_eq!(out.len(), target, "{op:?}");
+        match op {
+            Insert { .. } => {
+                out.push(b[target]);
+                i = source;
+            }
+            Delete { .. } => i = source + 1,
+            Substitute { .. } => {
+                assert_ne!(a[source], b[target]);
+                out.push(b[target]);
+                i = source + 1;
+            }
+            Transpose { .. } => {
+                out.extend([a[source + 1], a[source]]);
+                i = source + 2;
+            }
+        }
+    }
+    out.extend_from_slice(&a[i..]);
+    out.into_iter().collect()
+}
+
+#[test]
+fn edit_ops() {
+    use rustc_edit_distance::{edit_ops, EditOp::*};
+
+    assert_eq!(edit_ops("abc", "abc", 0), Some(vec![]));
+    assert_eq!(
+        edit_ops("kitten", "sitting", 3),
+        Some(vec![
+            Substitute {
+                source: 0,
+                target: 0
+            },
+            Substitute {
+                source: 4,
+                target: 4
+            },
+            Insert {
+                source: 6,
+                target: 6
+            },
+        ])
+    );
+    assert_eq!(edit_ops("kitten", "sitting", 2), None);
+    assert_eq!(
+        edit_ops("recieve", "receive", 1),
+        Some(vec![Transpose {
+            source: 3,
+            target: 3
+        }])
+    );
+    assert_eq!(
+        edit_ops("ünï", "nï", 1),
+        Some(vec![Delete {
+            source: 0,
+            target: 0
+        }])
+    );
+    assert_eq!(
+        apply_edit_ops("ünï", "nï", &edit_ops("ünï", "nï", 1).unwrap()),
+        "nï"
+    );
+}
+
+#[test]
+fn edit_ops_turn_a_into_b_property() {
+    fn edit_ops_turn_a_into_b(a: String, b: String) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let ops = rustc_edit_distance::edit_ops(&a, &b, usize::MAX).unwrap();
+        ops.len() == reference_distance(&a, &b) && apply_edit_ops(&a, &b, &ops) == b
+    }
+
+    quickcheck(edit_ops_turn_a_into_b as fn(a: String, b: String) -> bool);
+}
+
+#[test]
+fn streaming_matches() {
+    use rustc_edit_distance::{find_best_match_for_name, matches_iter, MatchPhase};
+
+    assert_eq!(matches_iter([], "foo", None).count(), 0);
+    let found = matches_iter(["foo", "fob", "fo", "food", "foo"], "foo", Some(1))
+        .map(|m| (m.candidate, m.score, m.phase))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        found,
+        [
+            ("foo", 0, MatchPhase::EditDistance),
+            ("foo", 0, MatchPhase::EditDistance)
+        ]
+    );
+    // A lazy, unbounded stream, which is only read up to the exact match.
+    let mut read = 0;
+    let names = ["bar", "baz", "qux"].iter().cycle().map(|&name| {
+        read += 1;
+        name
+    });
+    let exact = matches_iter(names, "qux", None).find(|m| m.score == 0);
+    assert_eq!(exact.map(|m| m.candidate), Some("qux"));
+    assert_eq!(read, 3);
+
+    // Ties are yielded too, so the last match is the last of the closest candidates, and
+    // rustc's suggestion is the smallest of them.
+    let candidates = ["barr", "bax", "bas", "foo", "ba"];
+    let last = matches_iter(candidates, "bar", None).last();
+    assert_eq!(last.map(|m| m.candidate), Some("ba"));
+    assert_eq!(
+        find_best_match_for_name(&["bax", "bas", "foo"], "bar", None),
+        matches_iter(["bax", "bas", "foo"], "bar", None)
+            .filter(|m| m.score == 1)
+            .map(|m| m.candidate)
+            .min()
+    );
+}
+
+#[test]
+fn distance_cache() {
+    use rustc_edit_distance::{
+        damerau_levenshtein, find_best_match_for_name, CacheStats, DistanceCache,
+    };
+
+    let cache = DistanceCache::new(2);
+    assert_eq!(cache.distance("abc", "abd", 0), None);
+    // Only known to be over 0, so computed again.
+    assert_eq!(cache.distance("abc", "abd", 1), Some(1));
+    assert_eq!(cache.distance("abc", "abd", 0), None);
+    assert_eq!(cache.stats(), CacheStats { hits: 1, misses: 2 });
+    assert_eq!(cache.len(), 1);
+
+    // The least recently used pair makes room.
+    assert_eq!(cache.distance("abc", "xyz", 5), Some(3));
+    assert_eq!(cache.distance("abc", "abd", 5), Some(1));
+    assert_eq!(cache.distance("foo", "bar", 5), Some(3));
+    assert_eq!(cache.len(), 2);
+    let misses = cache.stats().misses;
+    assert_eq!(cache.distance("abc", "abd", 5), Some(1));
+    assert_eq!(cache.distance("foo", "bar", 5), Some(3));
+    assert_eq!(cache.stats().misses, misses);
+    assert_eq!(cache.distance("abc", "xyz", 5), Some(3));
+    assert_eq!(cache.stats().misses, misses + 1);
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(cache.stats().misses, misses + 1);
+
+    let cache = DistanceCache::new(0);
+    assert_eq!(cache.distance("abc", "abd", 1), Some(1));
+    assert_eq!(cache.distance("abc", "abd", 1), Some(1));
+    assert_eq!((cache.len(), cache.stats().hits), (0, 0));
+
+    let cache = DistanceCache::with_metric(damerau_levenshtein, 100);
+    assert_eq!(cache.distance("ca", "abc", 5), Some(2));
+    let candidates = ["status", "start", "stats", "static"];
+    for lookup in ["stauts", "stat", "statci", "xyz"] {
+        assert_eq!(
+            DistanceCache::new(100).find_best_match_for_name(&candidates, lookup, None),
+            find_best_match_for_name(&candidates, lookup, None),
+            "{lookup}"
+        );
+    }
+}
+
+#[test]
+fn edit_ops_linear_space() {
+    use rustc_edit_distance::{edit_ops, edit_ops_linear_space, EditOp::*};
+
+    assert_eq!(edit_ops_linear_space("", "", 0), Some(vec![]));
+    assert_eq!(
+        edit_ops_linear_space("ab", "", 2),
+        Some(vec![
+            Delete {
+                source: 0,
+                target: 0
+            },
+            Delete {
+                source: 1,
+                target: 0
+            }
+        ])
+    );
+    // The transposition is across the middle of `a`.
+    assert_eq!(
+        edit_ops_linear_space("abdcef", "abcdef", 1),
+        Some(vec![Transpose {
+            source: 2,
+            target: 2
+        }])
+    );
+    assert_eq!(edit_ops_linear_space("recieve", "receive", 0), None);
+    let (a, b) = ("the quick brown fox", "a quick brwon fax jumps");
+    let ops = edit_ops_linear_space(a, b, usize::MAX).unwrap();
+    assert_eq!(ops.len(), edit_ops(a, b, usize::MAX).unwrap().len());
+    assert_eq!(apply_edit_ops(a, b, &ops), b);
+
+    fn edit_ops_turn_a_into_b(a: String, b: String) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let ops = edit_ops_linear_space(&a, &b, usize::MAX).unwrap();
+        ops.len() == reference_distance(&a, &b) && apply_edit_ops(&a, &b, &ops) == b
+    }
+
+    quickcheck(edit_ops_turn_a_into_b as fn(a: String, b: String) -> bool);
+}
+
+#[test]
+fn alignment() {
+    use rustc_edit_distance::{alignment, AlignedSpan, SpanKind::*};
+
+    let span = |kind, a, b| AlignedSpan { kind, a, b };
+    assert_eq!(
+        alignment("foobra", "foobar", 1),
+        Some(vec![span(Equal, 0..4, 0..4), span(Changed, 4..6, 4..6)])
+    );
+    assert_eq!(
+        alignment("kitten", "sitting", 3),
+        Some(vec![
+            span(Changed, 0..1, 0..1),
+            span(Equal, 1..4, 1..4),
+            span(Changed, 4..5, 4..5),
+            span(Equal, 5..6, 5..6),
+            span(Inserted, 6..6, 6..7),
+        ])
+    );
+    // Byte ranges, so that multi-byte chars can be sliced.
+    let (a, b) = ("grüße", "grüsse");
+    let spans = alignment(a, b, 2).unwrap();
+    assert_eq!(
+        spans,
+        vec![
+            span(Equal, 0..4, 0..4),
+            span(Inserted, 4..4, 4..5),
+            span(Changed, 4..6, 5..6),
+            span(Equal, 6..7, 6..7),
+        ]
+    );
+    assert_eq!(&a[spans[2].a.clone()], "ß");
+    assert_eq!(
+        alignment("abc", "abc", 0),
+        Some(vec![span(Equal, 0..3, 0..3)])
+    );
+    assert_eq!(alignment("", "", 0), Some(vec![]));
+    assert_eq!(
+        alignment("abc", "", 3),
+        Some(vec![span(Deleted, 0..3, 0..0)])
+    );
+    assert_eq!(alignment("abc", "xyz", 2), None);
+}
+
+#[test]
+fn similarity_ratio_at_least() {
+    use rustc_edit_distance::{similarity_ratio, similarity_ratio_at_least};
+
+    assert_eq!(similarity_ratio_at_least("abcd", "abxd", 0.75), Some(0.75));
+    assert_eq!(similarity_ratio_at_least("abcd", "abxd", 0.76), None);
+    assert_eq!(similarity_ratio_at_least("", "", 1.0), Some(1.0));
+    assert_eq!(similarity_ratio_at_least("abc", "", 0.0), Some(0.0));
+    assert_eq!(similarity_ratio_at_least("abc", "", 0.1), None);
+    // 80% similar, whatever the length.
+    for (a, b) in [("abcde", "abcdx"), ("abcdefghij", "abcdefghxy")] {
+        assert_eq!(similarity_ratio_at_least(a, b, 0.8), Some(0.8));
+        assert_eq!(
+            similarity_ratio_at_least(a, b, 0.8),
+            Some(similarity_ratio(a, b))
+        );
+    }
+    assert_eq!(
+        similarity_ratio_at_least("abcdefghij", "abcdefgxyz", 0.8),
+        None
+    );
+}
+
+#[test]
+fn hamming() {
+    use rustc_edit_distance::{hamming_distance, hamming_distance_bytes, LengthMismatch};
+
+    assert_eq!(hamming_distance("karolin", "kathrin", 3), Ok(Some(3)));
+    assert_eq!(hamming_distance("karolin", "kathrin", 2), Ok(None));
+    assert_eq!(hamming_distance("", "", 0), Ok(Some(0)));
+    assert_eq!(hamming_distance("äbc", "abc", 1), Ok(Some(1)));
+    assert_eq!(
+        hamming_distance("abc", "ab", 5),
+        Err(LengthMismatch { a_len: 3, b_len: 2 })
+    );
+    assert_eq!(
+        hamming_distance_bytes(b"1011101", b"1001001", 5),
+        Ok(Some(2))
+    );
+    assert_eq!(
+        hamming_distance_bytes("ä".as_bytes(), b"a", 5),
+        Err(LengthMismatch { a_len: 2, b_len: 1 })
+    );
+    assert_eq!(
+        LengthMismatch { a_len: 3, b_len: 2 }.to_string(),
+        "hamming distance needs inputs of the same length, not 3 and 2"
+    );
+}
+
+#[test]
+fn longest_common_substring() {
+    use rustc_edit_distance::longest_common_substring;
+
+    let (a, b) = ("parse_config", "config_parser");
+    let (len, range_a, range_b) = longest_common_substring(a, b);
+    assert_eq!((len, &a[range_a], &b[range_b]), (6, "config", "config"));
+
+    assert_eq!(longest_common_substring("abc", "xyz"), (0, 0..0, 0..0));
+    assert_eq!(longest_common_substring("", "abc"), (0, 0..0, 0..0));
+    // The first of several in `a` wins.
+    assert_eq!(longest_common_substring("abxcd", "cdab"), (2, 0..2, 2..4));
+    // Byte ranges around multi-byte chars.
+    let (a, b) = ("süßes", "grüße");
+    let (len, range_a, range_b) = longest_common_substring(a, b);
+    assert_eq!((len, &a[range_a], &b[range_b]), (3, "üße", "üße"));
+}
+
+#[test]
+fn dice_coefficient() {
+    use rustc_edit_distance::{
+        dice_coefficient, find_best_match_for_name_with, MatchOptions, Scorer,
+    };
+
+    // 7 of the 9 bigrams of each are shared.
+    assert_eq!(dice_coefficient("night club", "club night"), 14.0 / 18.0);
+    assert_eq!(dice_coefficient("night", "nacht"), 0.25);
+    assert_eq!(dice_coefficient("aaa", "aa"), 2.0 / 3.0);
+    assert_eq!(dice_coefficient("", ""), 1.0);
+    assert_eq!(dice_coefficient("a", "a"), 1.0);
+    assert_eq!(dice_coefficient("a", "ab"), 0.0);
+    assert_eq!(dice_coefficient("abc", "xyz"), 0.0);
+
+    let options = MatchOptions::new().scorer(Scorer::Dice {
+        min_similarity: 0.5,
+    });
+    let candidates = ["club_night_tickets", "night_train", "ticket_office"];
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "night_club_tickets", &options),
+        Some("club_night_tickets")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "daytime", &options),
+        None
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn cosine_similarity() {
+    use rustc_edit_distance::{cosine_similarity, NGramProfile};
+
+    let similarity =
+        |a, b, n| cosine_similarity(&NGramProfile::new(a, n), &NGramProfile::new(b, n));
+    assert_eq!(similarity("banana", "banana", 2), 1.0);
+    // "ab" and "ba" twice each, against "ab" once.
+    assert!((similarity("ababa", "ab", 2) - 0.5f64.sqrt()).abs() < 1e-12);
+    assert_eq!(similarity("abc", "xyz", 2), 0.0);
+    assert_eq!(similarity("", "", 3), 1.0);
+    assert_eq!(similarity("", "abc", 3), 0.0);
+    // Shorter than `n`.
+    assert_eq!(similarity("ab", "ab", 3), 1.0);
+    assert_eq!(similarity("ab", "abc", 3), 0.0);
+    assert_eq!(similarity("äöü", "äöü", 2), 1.0);
+    // Length-insensitive: the same words, repeated, are as similar as ever.
+    assert!(similarity("red shoe", "red shoe red shoe", 3) > 0.85);
+
+    let a = NGramProfile::new("abc", 2);
+    let b = NGramProfile::new("abc", 3);
+    assert_eq!(cosine_similarity(&a, &b), 0.0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+#[should_panic]
+fn ngram_profile_of_zero_length_grams() {
+    rustc_edit_distance::NGramProfile::new("abc", 0);
+}
+
+#[test]
+fn phonetic_codes() {
+    use rustc_edit_distance::{double_metaphone, metaphone, soundex};
+
+    for (s, code) in [
+        ("Robert", "R163"),
+        ("Rupert", "R163"),
+        ("Rubin", "R150"),
+        ("Ashcraft", "A261"),
+        ("Tymczak", "T522"),
+        ("Pfister", "P236"),
+        ("Honeyman", "H555"),
+        ("lee", "L000"),
+        ("", ""),
+        ("_42", ""),
+    ] {
+        assert_eq!(soundex(s), code, "{s}");
+    }
+
+    for (s, code) in [
+        ("phone", "FN"),
+        ("fone", "FN"),
+        ("knight", "NT"),
+        ("night", "NT"),
+        ("smith", "SM0"),
+        ("science", "SNS"),
+        ("school", "SKL"),
+        ("judge", "JJ"),
+        ("Xavier", "SFR"),
+        ("Aesop", "ESP"),
+        ("thumb", "0M"),
+        ("box", "BKS"),
+        ("", ""),
+    ] {
+        assert_eq!(metaphone(s), code, "{s}");
+    }
+
+    for (s, primary, alternate) in [
+        ("Smith", "SM0", "XMT"),
+        ("Schmidt", "XMT", "SMT"),
+        ("Xavier", "SF", "SFR"),
+        ("Catherine", "K0RN", "KTRN"),
+        ("Michael", "MKL", "MXL"),
+        ("Kuczewski", "KSSK", "KXFS"),
+        ("Arnow", "ARN", "ARNF"),
+        ("Jose", "HS", "HS"),
+        ("Biaggi", "PJ", "PK"),
+        ("Danger", "TNJR", "TNKR"),
+        ("Edge", "AJ", "AJ"),
+        ("knight", "NT", "NT"),
+        ("laugh", "LF", "LF"),
+        ("phone", "FN", "FN"),
+        ("", "", ""),
+    ] {
+        assert_eq!(
+            double_metaphone(s),
+            (String::from(primary), String::from(alternate)),
+            "{s}"
+        );
+    }
+}
+
+#[test]
+fn best_match_with_phonetic() {
+    use rustc_edit_distance::{find_best_match_for_name_with, MatchOptions, Phonetic};
+
+    let candidates = ["phone_number", "bone_number", "line_number"];
+    let options = MatchOptions::new();
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "fone_number", &options),
+        Some("bone_number")
+    );
+    let options = options.phonetic(Phonetic::Metaphone);
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "fone_number", &options),
+        Some("phone_number")
+    );
+    // Phonetic matches don't need to be within the maximum distance.
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "fone_number", &options.max_distance(0)),
+        Some("phone_number")
+    );
+
+    let options = MatchOptions::new().phonetic(Phonetic::Soundex);
+    assert_eq!(
+        find_best_match_for_name_with(&["rupee", "robert"], "rupert", &options),
+        Some("robert")
+    );
+
+    // The primary code of `schmidt` is only the alternate one of `smith`.
+    let candidates = ["smitten", "smith"];
+    let options = MatchOptions::new().phonetic(Phonetic::Metaphone);
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "schmidt", &options),
+        None
+    );
+    let options = MatchOptions::new().phonetic(Phonetic::DoubleMetaphone);
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "schmidt", &options),
+        Some("smith")
+    );
+}
+
+#[test]
+fn keyboard_layouts() {
+    use rustc_edit_distance::{edit_distance_with_costs, keyboard_edit_distance, KeyboardLayout};
+
+    let qwerty = KeyboardLayout::qwerty();
+    for (a, b) in [
+        ('q', 'w'),
+        ('s', 'w'),
+        ('s', 'e'),
+        ('s', 'z'),
+        ('s', 'x'),
+        ('Q', 'w'),
+    ] {
+        assert!(qwerty.are_adjacent(a, b), "{a} {b}");
+        assert!(qwerty.are_adjacent(b, a), "{b} {a}");
+    }
+    for (a, b) in [('q', 'p'), ('s', 'r'), ('s', 'c'), ('q', 'z'), ('q', '~')] {
+        assert!(!qwerty.are_adjacent(a, b), "{a} {b}");
+    }
+    assert_eq!(qwerty.substitution_cost('a', 'A'), 1);
+    assert_eq!(qwerty.substitution_cost('~', '~'), 0);
+
+    let azerty = KeyboardLayout::azerty();
+    assert!(azerty.are_adjacent('m', 'l'));
+    assert!(!qwerty.are_adjacent('m', 'l'));
+    assert!(azerty.are_adjacent('m', 'ù'));
+
+    let custom = KeyboardLayout::from_rows(&["abc", "def"]);
+    assert!(custom.are_adjacent('b', 'd'));
+    assert!(!custom.are_adjacent('a', 'f'));
+
+    assert_eq!(
+        keyboard_edit_distance("hello", "hello", 0, &qwerty),
+        Some(0)
+    );
+    assert_eq!(
+        keyboard_edit_distance("hwllo", "hello", 10, &qwerty),
+        Some(1)
+    );
+    assert_eq!(
+        keyboard_edit_distance("hpllo", "hello", 10, &qwerty),
+        Some(2)
+    );
+    assert_eq!(
+        keyboard_edit_distance("helo", "hello", 10, &qwerty),
+        Some(2)
+    );
+    assert_eq!(
+        keyboard_edit_distance("hlelo", "hello", 10, &qwerty),
+        Some(2)
+    );
+    assert_eq!(keyboard_edit_distance("hpllo", "hello", 1, &qwerty), None);
+
+    let with_costs = edit_distance_with_costs(
+        "hwllo",
+        "hello",
+        10,
+        |_| 2,
+        |_| 2,
+        |x, y| qwerty.substitution_cost(x, y),
+    );
+    assert_eq!(with_costs, Some(1));
+}
+
+#[test]
+fn equivalence_classes() {
+    use rustc_edit_distance::{
+        edit_distance_with_costs, edit_distance_with_equivalences, find_best_match_for_name_with,
+        EquivalenceClasses, MatchOptions,
+    };
+
+    let ocr = EquivalenceClasses::new()
+        .class(&['l', '1'])
+        .class(&['O', '0']);
+    assert!(ocr.are_equivalent('l', '1'));
+    assert!(ocr.are_equivalent('x', 'x'));
+    assert!(!ocr.are_equivalent('l', 'O'));
+    assert!(!ocr.are_equivalent('o', '0'));
+    assert_eq!(
+        edit_distance_with_equivalences("he11O", "hell0", 0, &ocr),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_with_equivalences("he11o", "hello", 0, &ocr),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_with_equivalences("he11", "hello", 0, &ocr),
+        None
+    );
+    assert_eq!(
+        edit_distance_with_equivalences("he11", "hello", 1, &ocr),
+        Some(1)
+    );
+    assert_eq!(
+        edit_distance_with_equivalences("hexxo", "hello", 5, &ocr),
+        Some(2)
+    );
+
+    // Classes that share a char are merged.
+    let merged = ocr.clone().class(&['1', 'I']);
+    assert!(merged.are_equivalent('l', 'I'));
+    assert!(!merged.are_equivalent('O', 'I'));
+
+    // Cheaper, rather than free, substitutions.
+    let half = edit_distance_with_costs(
+        "he1lo",
+        "hello",
+        10,
+        |_| 2,
+        |_| 2,
+        |x, y| if ocr.are_equivalent(x, y) { 1 } else { 2 },
+    );
+    assert_eq!(half, Some(1));
+
+    let candidates = ["WOOL_TOOl", "COOL_TOOl"];
+    let options = MatchOptions::new()
+        .max_distance(1)
+        .equivalences(ocr.clone());
+    assert_eq!(
+        find_best_match_for_name_with(&candidates, "C00L_T001", &options),
+        Some("COOL_TOOl")
+    );
+    assert_eq!(
+        find_best_match_for_name_with(
+            &candidates,
+            "C00L_T001",
+            &MatchOptions::new().max_distance(1)
+        ),
+        None
+    );
+    let options = options.use_substrings(true);
+    assert_eq!(
+        find_best_match_for_name_with(&["bell_x"], "be11", &options),
+        Some("bell_x")
+    );
+}
+
+#[cfg(feature = "unicode-security")]
+#[test]
+fn skeleton_distance() {
+    use rustc_edit_distance::{edit_distance, skeleton_distance};
+
+    // Cyrillic `р` and `а`.
+    let lookalike = "\u{440}\u{430}ypal";
+    assert_eq!(edit_distance(lookalike, "paypal", 1), None);
+    assert_eq!(skeleton_distance(lookalike, "paypal", 0), Some(0));
+    assert_eq!(skeleton_distance("rnicrosoft", "microsoft", 0), Some(0));
+    assert_eq!(skeleton_distance("paypa1", "paypal", 0), Some(0));
+    assert_eq!(skeleton_distance("paypals", "paypal", 1), Some(1));
+    assert_eq!(skeleton_distance("serde", "tokio", 1), None);
+}
+
+#[cfg(feature = "unicode-normalization")]
+#[test]
+fn ignoring_diacritics() {
+    use rustc_edit_distance::{
+        edit_distance, edit_distance_ignoring_diacritics, find_best_match_for_name_with,
+        MatchOptions, Normalization,
+    };
+
+    assert_eq!(edit_distance("caf\u{e9}", "cafe", 0), None);
+    assert_eq!(
+        edit_distance_ignoring_diacritics("caf\u{e9}", "cafe", 0),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_ignoring_diacritics("cafe\u{301}", "cafe", 0),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_ignoring_diacritics("\u{c5}ngstr\u{f6}m", "Angstrom", 0),
+        Some(0)
+    );
+    assert_eq!(
+        edit_distance_ignoring_diacritics("na\u{ef}ve", "naive", 0),
+        Some(0)
+    );
+    // Letters that aren't a base letter with a mark are kept.
+    assert_eq!(
+        edit_distance_ignoring_diacritics("stra\u{df}e", "strase", 0),
+        None
+    );
+    assert_eq!(
+        edit_distance_ignoring_diacritics("\u{d8}l", "Ol", 1),
+        Some(1)
+    );
+
+    let options = MatchOptions::new()
+        .max_distance(0)
+        .normalization(Normalization::StripDiacritics);
+    assert_eq!(
+        find_best_match_for_name_with(&["cr\u{e8}me_br\u{fb}l\u{e9}e"], "creme_brulee", &options),
+        Some("cr\u{e8}me_br\u{fb}l\u{e9}e")
+    );
+}
+
+#[test]
+fn distance_algorithms() {
+    use rustc_edit_distance::{
+        edit_distance_with_algorithm, levenshtein_distance, DistanceAlgorithm,
+    };
+
+    for (a, b, lev, osa, dl) in [
+        ("ab", "ba", 2, 1, 1),
+        ("ca", "abc", 3, 3, 2),
+        ("kitten", "sitting", 3, 3, 3),
+        ("", "abc", 3, 3, 3),
+        ("привет", "рпивет", 2, 1, 1),
+    ] {
+        assert_eq!(levenshtein_distance(a, b, usize::MAX), Some(lev), "{a} {b}");
+        assert_eq!(levenshtein_distance(a, b, lev - 1), None, "{a} {b}");
+        for (algorithm, expected) in [
+            (DistanceAlgorithm::Levenshtein, lev),
+            (DistanceAlgorithm::Osa, osa),
+            (DistanceAlgorithm::DamerauLevenshtein, dl),
+        ] {
+            assert_eq!(
+                edit_distance_with_algorithm(algorithm, a, b, usize::MAX),
+                Some(expected),
+                "{algorithm:?} {a} {b}"
+            );
+        }
+    }
+}
+
+#[test]
+fn levenshtein_agrees_with_reference_property() {
+    fn reference_levenshtein(a: &str, b: &str) -> usize {
+        let b = b.chars().collect::<Vec<_>>();
+        let mut prev = (0..=b.len()).collect::<Vec<_>>();
+        for (i, x) in a.chars().enumerate() {
+            let mut current = vec![i + 1; b.len() + 1];
+            for (j, &y) in b.iter().enumerate() {
+                current[j + 1] = (prev[j] + usize::from(x != y))
+                    .min(prev[j + 1] + 1)
+                    .min(current[j] + 1);
+            }
+            prev = current;
+        }
+        prev[b.len()]
+    }
+
+    fn levenshtein_agrees_with_reference(a: String, b: String, limit: u8) -> bool {
+        // Both the bit-parallel path, for short inputs, and the table, for long or non-Latin-1
+        // ones.
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let (long_a, long_b) = (a.repeat(8), b.repeat(8));
+        let (wide_a, wide_b) = (a.replace('a', "\u{101}"), b.replace('a', "\u{101}"));
+        let limit = usize::from(limit % 8);
+        [(a, b), (long_a, long_b), (wide_a, wide_b)]
+            .iter()
+            .all(|(a, b)| {
+                let expected = reference_levenshtein(a, b);
+                rustc_edit_distance::levenshtein_distance(a, b, usize::MAX) == Some(expected)
+                    && rustc_edit_distance::levenshtein_distance(a, b, limit)
+                        == Some(expected).filter(|&d| d <= limit)
+            })
+    }
+
+    quickcheck(levenshtein_agrees_with_reference as fn(a: String, b: String, limit: u8) -> bool);
+}
+
+#[cfg(feature = "simd")]
+#[test]
+fn anti_diagonals_agree_with_rows_property() {
+    fn anti_diagonals_agree_with_rows(a: String, b: String, wide: bool) -> bool {
+        // Long inputs, which skip the bit-parallel path, and may not be Latin-1, which skips it
+        // whatever their length. Slices always take the row by row path.
+        let (a, b) = (small_alphabet(&a).repeat(4), small_alphabet(&b).repeat(4));
+        let (a, b) = if wide {
+            (a.replace('a', "\u{101}"), b.replace('a', "\u{101}"))
+        } else {
+            (a, b)
+        };
+        let (a_chars, b_chars) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+        let expected = rustc_edit_distance::edit_distance_slices(&a_chars, &b_chars, usize::MAX);
+        rustc_edit_distance::edit_distance(&a, &b, usize::MAX) == expected
+            && expected == Some(reference_distance(&a, &b))
+    }
+
+    quickcheck(anti_diagonals_agree_with_rows as fn(a: String, b: String, wide: bool) -> bool);
+}
+
+#[test]
+fn buffer_agrees_with_edit_distance_property() {
+    fn buffer_agrees_with_edit_distance(pairs: Vec<(String, String)>, limit: u8) -> bool {
+        let mut buffer = rustc_edit_distance::EditDistanceBuffer::new();
+        let limit = usize::from(limit % 8);
+        pairs.iter().all(|(a, b)| {
+            // Long enough to need the table, and sometimes not Latin-1.
+            let (a, b) = (small_alphabet(a).repeat(4), small_alphabet(b).repeat(3));
+            let (a, b) = (a.replace('c', "\u{441}"), b.replace("ab", "ba"));
+            [usize::MAX, limit].iter().all(|&limit| {
+                rustc_edit_distance::edit_distance_in(&mut buffer, &a, &b, limit)
+                    == rustc_edit_distance::edit_distance(&a, &b, limit)
+            })
+        })
+    }
+
+    quickcheck(buffer_agrees_with_edit_distance as fn(Vec<(String, String)>, u8) -> bool);
+}
+
+#[test]
+fn const_edit_distance() {
+    use rustc_edit_distance::const_edit_distance;
+
+    const DISTANCES: [usize; 4] = [
+        const_edit_distance("kitten", "sitting"),
+        const_edit_distance("ab", "ba"),
+        const_edit_distance("", "abc"),
+        const_edit_distance("abc", "abc"),
+    ];
+    assert_eq!(DISTANCES, [3, 1, 3, 0]);
+
+    let long = "a".repeat(1000);
+    assert_eq!(const_edit_distance(&long, "b"), 1000);
+}
+
+#[test]
+#[should_panic]
+fn const_edit_distance_of_non_ascii() {
+    rustc_edit_distance::const_edit_distance("abc", "äbc");
+}
+
+#[test]
+#[should_panic]
+fn bk_tree_with_a_metric_without_distances() {
+    rustc_edit_distance::BkTree::from_candidates_with_metric(&["a", "b"], |_, _, _| None);
+}
+
+#[test]
+fn const_agrees_with_edit_distance_property() {
+    fn const_agrees_with_edit_distance(a: String, b: String) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b).repeat(4));
+        rustc_edit_distance::const_edit_distance(&a, &b) == edit_distance_unchecked(&a, &b)
+    }
+
+    quickcheck(const_agrees_with_edit_distance as fn(String, String) -> bool);
+}
+
+#[test]
+fn batch_agrees_with_edit_distance_property() {
+    fn batch_agrees_with_edit_distance(lookup: String, candidates: Vec<String>, limit: u8) -> bool {
+        let lookup = small_alphabet(&lookup).repeat(2);
+        // Long enough to need the table, and sometimes not ASCII.
+        let candidates = candidates
+            .iter()
+            .map(|c| small_alphabet(c).repeat(3).replace("ca", "\u{441}"))
+            .collect::<Vec<_>>();
+        let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+        let limit = usize::from(limit);
+        rustc_edit_distance::edit_distances(&lookup, &candidates, limit)
+            == candidates
+                .iter()
+                .map(|c| rustc_edit_distance::edit_distance(&lookup, c, limit))
+                .collect::<Vec<_>>()
+    }
+
+    quickcheck(batch_agrees_with_edit_distance as fn(String, Vec<String>, u8) -> bool);
+}
+
+#[test]
+fn distance_matrix() {
+    use rustc_edit_distance::{distance_matrix, edit_distance};
+
+    let items = ["kitten", "sitting", "mitten", "kitchen", "", "smitten"];
+    let matrix = distance_matrix(&items, 3);
+    assert_eq!(matrix.len(), items.len());
+    for i in 0..items.len() {
+        for j in 0..items.len() {
+            assert_eq!(matrix.get(i, j), edit_distance(items[i], items[j], 3));
+        }
+        assert!(matrix.row(i).eq(matrix.column(i)));
+    }
+    assert_eq!(matrix.nearest_neighbor(0), Some((2, 1)));
+    assert_eq!(matrix.nearest_neighbor(4), None);
+
+    assert!(distance_matrix(&[], 3).is_empty());
+    assert_eq!(distance_matrix(&["a"], 3).nearest_neighbor(0), None);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parallel_distance_matrix_agrees_with_serial() {
+    use rustc_edit_distance::{distance_matrix, distance_matrix_parallel};
+
+    let items = (0..300)
+        .map(|i| format!("item_{}_{}", i % 17, i))
+        .collect::<Vec<_>>();
+    let items = items.iter().map(|c| c.as_str()).collect::<Vec<_>>();
+    assert_eq!(
+        distance_matrix_parallel(&items, 4),
+        distance_matrix(&items, 4)
+    );
+}
+
+#[test]
+fn incremental_matcher_property() {
+    fn incremental_agrees_with_edit_distance(
+        candidates: Vec<String>,
+        keys: Vec<Option<char>>,
+    ) -> bool {
+        let candidates = candidates
+            .iter()
+            .map(|c| small_alphabet(c))
+            .collect::<Vec<_>>();
+        let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+        let mut matcher = rustc_edit_distance::IncrementalMatcher::new(&candidates);
+        let mut lookup = String::new();
+        // `None` is a backspace.
+        keys.iter().all(|key| {
+            match key {
+                Some(c) => {
+                    let c = small_alphabet(&c.to_string()).chars().next().unwrap();
+                    matcher.push_char(c);
+                    lookup.push(c);
+                }
+                None => assert_eq!(matcher.pop_char(), lookup.pop()),
+            }
+            matcher.lookup() == lookup
+                && matcher
+                    .distances()
+                    .all(|(c, d)| d == edit_distance_unchecked(&lookup, c))
+        })
+    }
+
+    quickcheck(incremental_agrees_with_edit_distance as fn(Vec<String>, Vec<Option<char>>) -> bool);
+}
+
+#[test]
+fn incremental_best_match() {
+    let mut matcher = rustc_edit_distance::IncrementalMatcher::new(&["status", "stash", "show"]);
+    assert_eq!(matcher.pop_char(), None);
+    assert_eq!(matcher.best_match(4), Some(("show", 4)));
+    for c in "stas".chars() {
+        matcher.push_char(c);
+    }
+    assert_eq!(matcher.best_match(1), Some(("stash", 1)));
+    matcher.pop_char();
+    matcher.pop_char();
+    // "stash" and "show" tie, and "stash" comes first.
+    assert_eq!(matcher.best_match(3), Some(("stash", 3)));
+    assert_eq!(matcher.best_match(0), None);
+}
+
+#[test]
+fn find_approx() {
+    use rustc_edit_distance::{find_approx, ApproxMatch};
+
+    let found = |pattern, text, k| {
+        find_approx(pattern, text, k)
+            .map(|m| (&text[m.start..m.end], m.dist))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        found("abc", "xxabcxxabxxbac", 1),
+        [("abc", 0), ("ab", 1), ("bac", 1)]
+    );
+    assert_eq!(found("abc", "xxabcxxabxx", 0), [("abc", 0)]);
+    assert_eq!(
+        found("straße", "die strase, eine Straße", 1),
+        [("strase", 1), ("Straße", 1)]
+    );
+    assert_eq!(found("", "abc", 1), []);
+    assert_eq!(found("abc", "", 1), []);
+    assert_eq!(
+        find_approx("ab", "ab", 5).collect::<Vec<_>>(),
+        [ApproxMatch {
+            start: 0,
+            end: 2,
+            dist: 0
+        }]
+    );
+}
+
+#[test]
+fn find_approx_property() {
+    fn matches_are_at_their_distance(pattern: String, text: String, k: u8) -> bool {
+        let (pattern, text) = (small_alphabet(&pattern), small_alphabet(&text).repeat(3));
+        let k = usize::from(k % 4);
+        let matches = rustc_edit_distance::find_approx(&pattern, &text, k).collect::<Vec<_>>();
+        matches.windows(2).all(|w| w[0].end < w[1].end)
+            && matches.iter().all(|m| {
+                m.dist <= k && edit_distance_unchecked(&pattern, &text[m.start..m.end]) == m.dist
+            })
+    }
+
+    quickcheck(matches_are_at_their_distance as fn(String, String, u8) -> bool);
+}
+
+#[test]
+fn fuzzy_contains_agrees_with_find_approx_property() {
+    fn fuzzy_contains_agrees_with_find_approx(needle: String, haystack: String, k: u8) -> bool {
+        let (needle, haystack) = (small_alphabet(&needle), small_alphabet(&haystack).repeat(3));
+        // Both the bit-parallel path and, for long or non-Latin-1 needles, the table.
+        let (long_needle, wide_needle) = (needle.repeat(8), needle.replace('a', "\u{101}"));
+        let wide_haystack = haystack.replace('a', "\u{101}");
+        let k = usize::from(k % 4);
+        [
+            (&needle, &haystack),
+            (&needle, &wide_haystack),
+            (&long_needle, &haystack),
+            (&wide_needle, &wide_haystack),
+        ]
+        .iter()
+        .all(|(needle, haystack)| {
+            let expected = needle.chars().count() <= k
+                || rustc_edit_distance::find_approx(needle, haystack, k)
+                    .next()
+                    .is_some();
+            rustc_edit_distance::fuzzy_contains(haystack, needle, k) == expected
+        })
+    }
+
+    quickcheck(fuzzy_contains_agrees_with_find_approx as fn(String, String, u8) -> bool);
+}
+
+#[test]
+fn multi_pattern_agrees_with_find_approx_property() {
+    fn multi_pattern_agrees_with_find_approx(patterns: Vec<String>, text: String, k: u8) -> bool {
+        // Short, long and non-Latin-1 patterns, to search for with both algorithms.
+        let patterns = patterns
+            .iter()
+            .enumerate()
+            .map(|(i, p)| match i % 3 {
+                0 => small_alphabet(p),
+                1 => small_alphabet(p).repeat(10),
+                _ => small_alphabet(p).replace('a', "\u{101}"),
+            })
+            .collect::<Vec<_>>();
+        let text = small_alphabet(&text).repeat(5).replace("ba", "\u{101}");
+        let k = usize::from(k % 4);
+
+        let patterns = patterns.iter().map(String::as_str).collect::<Vec<_>>();
+        let matches = rustc_edit_distance::MultiPattern::new(&patterns)
+            .find_iter(&text, k)
+            .collect::<Vec<_>>();
+        // The matches of different patterns are in the order their runs end, not of their ends.
+        patterns.iter().enumerate().all(|(i, pattern)| {
+            matches
+                .iter()
+                .filter(|m| m.pattern == i)
+                .map(|m| (m.end, m.dist))
+                .eq(rustc_edit_distance::find_approx(pattern, &text, k).map(|m| (m.end, m.dist)))
+        })
+    }
+
+    quickcheck(multi_pattern_agrees_with_find_approx as fn(Vec<String>, String, u8) -> bool);
+}
+
+#[test]
+fn pattern_distance() {
+    use rustc_edit_distance::pattern_distance;
+
+    assert_eq!(pattern_distance("*", "", 0), Some(0));
+    assert_eq!(pattern_distance("*", "anything", 0), Some(0));
+    assert_eq!(pattern_distance("?", "", 1), Some(1));
+    assert_eq!(pattern_distance("a*b*c", "aXXbYYc", 0), Some(0));
+    assert_eq!(pattern_distance("a*b*c", "aXXYYc", 2), Some(1));
+    assert_eq!(pattern_distance("*.rs", "main.sr", 2), Some(1));
+    assert_eq!(pattern_distance("?*", "", 2), Some(1));
+    assert_eq!(pattern_distance("a?c", "abbc", 2), Some(1));
+    assert_eq!(pattern_distance("abc*", "xyz", 2), None);
+}
+
+#[test]
+fn pattern_without_wildcards_property() {
+    fn pattern_without_wildcards(a: String, b: String, limit: u8) -> bool {
+        let (a, b) = (small_alphabet(&a), small_alphabet(&b));
+        let limit = usize::from(limit % 8);
+        rustc_edit_distance::pattern_distance(&a, &b, limit)
+            == rustc_edit_distance::edit_distance(&a, &b, limit)
+    }
+
+    quickcheck(pattern_without_wildcards as fn(String, String, u8) -> bool);
+}
+
+#[test]
+fn automaton_agrees_with_edit_distance_property() {
+    fn automaton_agrees_with_edit_distance(lookup: String, s: String, max_dist: u8) -> bool {
+        let (lookup, s) = (small_alphabet(&lookup), small_alphabet(&s));
+        let max_dist = usize::from(max_dist % 4);
+        let automaton = rustc_edit_distance::LevenshteinAutomaton::new(&lookup, max_dist);
+
+        let mut state = automaton.start();
+        let mut could_match = automaton.can_match(&state);
+        for (i, c) in s.char_indices() {
+            let expected = rustc_edit_distance::edit_distance(&lookup, &s[..i], max_dist);
+            if automaton.distance(&state) != expected || (expected.is_some() && !could_match) {
+                return false;
+            }
+            state = automaton.step(&state, c);
+            // Once no extension can match, none does.
+            could_match &= automaton.can_match(&state);
+        }
+        let expected = rustc_edit_distance::edit_distance(&lookup, &s, max_dist);
+        automaton.distance(&state) == expected
+            && automaton.is_match(&state) == expected.is_some()
+            && (expected.is_none() || could_match)
+    }
+
+    quickcheck(automaton_agrees_with_edit_distance as fn(String, String, u8) -> bool);
+}
+
+#[test]
+fn best_matches() {
+    use rustc_edit_distance::{find_best_matches, Match, MatchPhase};
+
+    let m = |candidate, score, phase| Match {
+        candidate,
+        score,
+        phase,
+    };
+    let candidates = ["fooo", "bar", "b_foo", "foob", "FOO", "foo_b"];
+    assert_eq!(
+        find_best_matches(&candidates, "foox", Some(1), 10),
+        [
+            m("FOO", 4, MatchPhase::CaseInsensitive),
+            m("foob", 1, MatchPhase::EditDistance),
+            m("fooo", 1, MatchPhase::EditDistance),
+        ]
+    );
+    assert_eq!(
+        find_best_matches(&candidates, "b_foo", Some(1), 10),
+        [
+            m("b_foo", 0, MatchPhase::CaseInsensitive),
+            m("FOO", 5, MatchPhase::CaseInsensitive),
+            m("foo_b", 4, MatchPhase::SortedWords),
+        ]
+    );
+    assert_eq!(find_best_matches(&candidates, "foox", Some(1), 1).len(), 1);
+    assert_eq!(find_best_matches(&candidates, "foox", Some(1), 0), []);
+    assert_eq!(find_best_matches(&candidates, "", None, 10), []);
+}
+
+#[test]
+fn best_match_with_distance() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_with_distance};
+
+    let candidates = ["config", "confirm", "conflict", "b_a"];
+    for (lookup, expected) in [
+        ("confgi", Some(("config", 1))),
+        ("CONFIRM", Some(("confirm", 7))),
+        ("a_b", Some(("b_a", 2))),
+        ("xyz", None),
+        ("", None),
+    ] {
+        assert_eq!(
+            find_best_match_with_distance(&candidates, lookup, None),
+            expected
+        );
+        assert_eq!(
+            find_best_match_for_name(&candidates, lookup, None),
+            expected.map(|(c, _)| c)
+        );
+    }
+}
+
+#[test]
+fn best_match_indices() {
+    use rustc_edit_distance::{find_best_match_index, find_best_match_indices, find_best_matches};
+
+    struct Field {
+        name: &'static str,
+    }
+    let fields = [
+        Field { name: "width" },
+        Field { name: "height" },
+        Field { name: "weight" },
+        Field { name: "height" },
+    ];
+    let names = fields.iter().map(|f| f.name).collect::<Vec<_>>();
+
+    let best = find_best_match_index(&names, "heigth", None).unwrap();
+    assert_eq!(fields[best].name, "height");
+    assert_eq!(best, 1);
+    assert_eq!(find_best_match_index(&names, "depth", Some(1)), None);
+
+    let indices = find_best_match_indices(&names, "weigth", Some(2), 3);
+    assert_eq!(indices, [2, 1, 3]);
+    let matches = find_best_matches(&names, "weigth", Some(2), 3);
+    assert!(indices
+        .iter()
+        .zip(&matches)
+        .all(|(&i, m)| names[i] == m.candidate));
+}
+
+#[test]
+fn best_match_generic() {
+    use std::{collections::BTreeMap, rc::Rc};
+
+    use rustc_edit_distance::{
+        find_best_match_for_name, find_best_match_for_name_generic, find_best_match_indices,
+    };
+
+    let names = ["to_string", "to_owned", "into_iter", "as_str"];
+    let rcs = names
+        .iter()
+        .map(|&n| Rc::<str>::from(n))
+        .collect::<Vec<_>>();
+    let map = names
+        .iter()
+        .map(|&n| (n.to_owned(), n.len()))
+        .collect::<BTreeMap<_, _>>();
+    for lookup in ["to_strng", "TO_OWNED", "iter_into", "xyzzy", ""] {
+        let expected = find_best_match_for_name(&names, lookup, None);
+        assert_eq!(
+            find_best_match_for_name_generic(rcs.iter().cloned(), lookup, None).as_deref(),
+            expected
+        );
+        assert_eq!(
+            find_best_match_for_name_generic(map.keys(), lookup, None).map(String::as_str),
+            expected
+        );
+        assert_eq!(
+            find_best_match_indices(&rcs, lookup, None, 2),
+            find_best_match_indices(&names, lookup, None, 2)
+        );
+    }
+}
+
+#[test]
+fn all_matches_within() {
+    use rustc_edit_distance::{
+        find_all_matches_within, find_all_matches_within_with, MatchOptions,
+    };
+
+    let candidates = ["capture", "cptr", "force_capture", "Capture", "captured"];
+    assert_eq!(
+        find_all_matches_within(&candidates, "captre", 2),
+        [("capture", 1), ("cptr", 2), ("Capture", 2), ("captured", 2)]
+    );
+    assert_eq!(find_all_matches_within(&candidates, "xyzzy", 2), []);
+
+    let options = MatchOptions::new().case_insensitive(true).max_distance(1);
+    assert_eq!(
+        find_all_matches_within_with(&candidates, "captre", &options),
+        [("capture", 1), ("Capture", 1)]
+    );
+    let options = MatchOptions::new().use_substrings(true).max_distance(1);
+    assert_eq!(
+        find_all_matches_within_with(&candidates, "capture", &options),
+        [
+            ("capture", 0),
+            ("cptr", 1),
+            ("force_capture", 1),
+            ("Capture", 1),
+            ("captured", 1)
+        ]
+    );
+}
+
+#[test]
+fn weighted_best_match() {
+    use rustc_edit_distance::{find_best_match_for_name, find_best_match_for_name_weighted};
+
+    let words = [("form", 10), ("from", 500), ("farm", 40), ("FROM_THE", 1)];
+    // All three are one edit away, so the commonest wins, not the smallest.
+    assert_eq!(
+        find_best_match_for_name_weighted(&words, "frm", None),
+        Some("from")
+    );
+    assert_eq!(
+        find_best_match_for_name_weighted(&words, "fxrm", None),
+        Some("farm")
+    );
+    let unweighted = words.iter().map(|&(w, _)| w).collect::<Vec<_>>();
+    assert_eq!(
+        find_best_match_for_name(&unweighted, "frm", None),
+        Some("farm")
+    );
+
+    // Case insensitive matches still come first, and same weights go lexicographically.
+    assert_eq!(
+        find_best_match_for_name_weighted(&words, "rom_t", None),
+        Some("FROM_THE")
+    );
+    let ties = [("c_a_b", 1), ("b_c_a", 1), ("x", 2)];
+    assert_eq!(
+        find_best_match_for_name_weighted(&ties, "a_b_c", Some(0)),
+        Some("b_c_a")
+    );
+    assert_eq!(find_best_match_for_name_weighted(&ties, "", None), None);
+}
+
+#[test]
+fn match_phases() {
+    use rustc_edit_distance::{find_best_match_for_name, MatchOptions, MatchPhase};
+
+    let candidates = ["Fo", "b_a", "bar_foo", "foo_bar", "fob"];
+    let phases = |phases: &[MatchPhase]| MatchOptions::new().max_distance(1).phases(phases);
+    let ed = MatchPhase::EditDistance;
+    let ci = MatchPhase::CaseInsensitive;
+    let sw = MatchPhase::SortedWords;
+
+    for lookup in ["foo", "a_b", "bar_foo", "x"] {
+        assert_eq!(
+            phases(&[ci, ed, sw]).best(&candidates, lookup),
+            find_best_match_for_name(&candidates, lookup, Some(1))
+        );
+    }
+    assert_eq!(phases(&[ci, ed, sw]).best(&candidates, "foo"), Some("Fo"));
+    assert_eq!(phases(&[ed, ci]).best(&candidates, "foo"), Some("fob"));
+    assert_eq!(phases(&[ed]).best(&candidates, "fooo"), None);
+    assert_eq!(phases(&[ed, ci]).best(&candidates, "fooo"), Some("Fo"));
+    assert_eq!(phases(&[ci, sw]).best(&candidates, "foo_bar"), Some("Fo"));
+    assert_eq!(
+        phases(&[sw, ci]).best(&candidates, "foo_bar"),
+        Some("foo_bar")
+    );
+    assert_eq!(phases(&[]).best(&candidates, "foo"), None);
+}
+
+#[test]
+fn thresholds() {
+    use std::sync::Arc;
+
+    use rustc_edit_distance::{default_match_distance, find_all_matches_within_with, Threshold};
+    use rustc_edit_distance::{find_best_match_for_name, MatchOptions};
+
+    assert_eq!(Threshold::Proportional(0.3).limit(""), 0);
+    assert_eq!(Threshold::Proportional(0.3).limit("abcde"), 2);
+    assert_eq!(Threshold::Proportional(0.25).limit("ab"), 1);
+    assert_eq!(Threshold::Proportional(f64::NAN).limit("abc"), 0);
+    assert_eq!(Threshold::Proportional(-1.0).limit("abc"), 0);
+
+    let candidates = ["read_to_end", "read_to_string", "read_exact", "write"];
+    for lookup in ["read_to_strng", "rd_exct", "wirte", "xyz"] {
+        let options =
+            MatchOptions::new().threshold(Threshold::Custom(Arc::new(default_match_distance)));
+        assert_eq!(options, options.clone());
+        assert_eq!(
+            options.best(&candidates, lookup),
+            find_best_match_for_name(&candidates, lookup, None)
+        );
+    }
+
+    let options = MatchOptions::new().threshold(Threshold::Proportional(0.4));
+    assert_eq!(
+        find_all_matches_within_with(&candidates, "read_to_strin", &options),
+        [("read_to_string", 1), ("read_to_end", 5)]
+    );
+
+    // A custom threshold can capture state, e.g. a limit picked at runtime.
+    let slack = 2;
+    let threshold = Threshold::Custom(Arc::new(move |lookup| lookup.len() / 10 + slack));
+    assert_eq!(threshold.limit("read_to"), 2);
+    assert_eq!(threshold.limit("read_to_string"), 3);
+    let options = MatchOptions::new().threshold(threshold);
+    assert_eq!(options.best(&candidates, "red_to_ed"), Some("read_to_end"));
+    assert_ne!(options, MatchOptions::new().max_distance(2));
+}
+
+#[test]
+fn min_substring_len() {
+    use rustc_edit_distance::{find_best_match_for_name, MatchOptions};
+
+    let candidates = ["as_bytes", "is_empty", "bytes", "as", "by"];
+    let options = MatchOptions::new().min_substring_len(3);
+    // The lookup is too short to match `as_bytes` by containment.
+    assert_eq!(
+        find_best_match_for_name(&candidates, "as", None),
+        Some("as_bytes")
+    );
+    assert_eq!(options.best(&candidates, "as"), Some("as"));
+    assert_eq!(options.best(&candidates, "AS"), Some("as"));
+    assert_eq!(options.best(&candidates, "bx"), Some("by"));
+    assert_eq!(options.best(&candidates, "byt"), Some("as_bytes"));
+    // A candidate contained in the lookup has to be long enough too.
+    assert_eq!(options.best(&candidates, "by_foo"), None);
+    assert_eq!(options.best(&candidates, "bytes_of"), Some("bytes"));
+    assert_eq!(
+        MatchOptions::new()
+            .min_substring_len(0)
+            .best(&candidates, "byz"),
+        find_best_match_for_name(&candidates, "byz", None)
+    );
+}
+
+#[test]
+fn prefix_weight() {
+    use rustc_edit_distance::{find_best_match_for_name, MatchOptions};
+
+    let candidates = ["pant", "print", "sprint"];
+    let options = MatchOptions::new().prefix_weight(0.1);
+    assert_eq!(
+        find_best_match_for_name(&candidates, "prnt", None),
+        Some("pant")
+    );
+    assert_eq!(options.best(&candidates, "prnt"), Some("print"));
+    // The discount doesn't let a candidate past the limit.
+    assert_eq!(
+        options.clone().max_distance(0).best(&candidates, "prnt"),
+        None
+    );
+    // A larger distance can win with a long enough prefix.
+    let candidates = ["prinzy", "brinx"];
+    let options = MatchOptions::new().max_distance(2);
+    assert_eq!(options.best(&candidates, "prinx"), Some("brinx"));
+    assert_eq!(
+        options.prefix_weight(0.2).best(&candidates, "prinx"),
+        Some("prinzy")
+    );
+}
+
+#[test]
+fn abbreviations() {
+    use rustc_edit_distance::{abbreviation_score, MatchOptions, MatchPhase};
+
+    assert_eq!(abbreviation_score("hm", "HashMap"), Some(0));
+    assert_eq!(abbreviation_score("HM", "hash_map"), Some(0));
+    assert_eq!(abbreviation_score("hsm", "HashMap"), Some(1));
+    assert_eq!(abbreviation_score("hs", "HTTPServer"), Some(0));
+    assert_eq!(abbreviation_score("u8a", "u8_array"), Some(0));
+    assert_eq!(abbreviation_score("gl", "get-log-level"), Some(0));
+    assert_eq!(abbreviation_score("hashmap", "HashMap"), Some(5));
+    assert_eq!(abbreviation_score("am", "HashMap"), None);
+    assert_eq!(abbreviation_score("hmx", "HashMap"), None);
+    assert_eq!(abbreviation_score("", "HashMap"), None);
+    assert_eq!(abbreviation_score("h", ""), None);
+
+    let candidates = ["HashMap", "HashSet", "BTreeMap", "BinaryHeap"];
+    let options = MatchOptions::new().phases(&[
+        MatchPhase::CaseInsensitive,
+        MatchPhase::EditDistance,
+        MatchPhase::Abbreviation,
+    ]);
+    assert_eq!(options.best(&candidates, "hm"), Some("HashMap"));
+    assert_eq!(options.best(&candidates, "btm"), Some("BTreeMap"));
+    assert_eq!(options.best(&candidates, "bh"), Some("BinaryHeap"));
+    assert_eq!(options.best(&candidates, "bhm"), None);
+    // Not matched without the phase.
+    assert_eq!(MatchOptions::new().best(&candidates, "btm"), None);
+}
+
+#[test]
+fn identifier_words() {
+    use rustc_edit_distance::{split_identifier, MatchOptions};
+
+    assert_eq!(
+        split_identifier("foo_bar-baz qux"),
+        ["foo", "bar", "baz", "qux"]
+    );
+    assert_eq!(
+        split_identifier("XMLHttpRequest"),
+        ["XML", "Http", "Request"]
+    );
+    assert_eq!(
+        split_identifier("utf8ToUtf16"),
+        ["utf", "8", "To", "Utf", "16"]
+    );
+    assert_eq!(split_identifier("__init__"), ["init"]);
+    assert_eq!(split_identifier("ÉtéChaud"), ["Été", "Chaud"]);
+    assert_eq!(split_identifier(""), Vec::<&str>::new());
+
+    let candidates = ["config_parse", "parse_args"];
+    let options = MatchOptions::new().max_distance(0);
+    assert_eq!(options.best(&candidates, "parseConfig"), None);
+    let options = options.split_case(true);
+    assert_eq!(
+        options.best(&candidates, "parseConfig"),
+        Some("config_parse")
+    );
+    assert_eq!(options.best(&candidates, "ArgsParse"), Some("parse_args"));
+    // Separators still apply.
+    let options = options.separators(&['-']);
+    assert_eq!(
+        options.best(&["config-parse"], "parseConfig"),
+        Some("config-parse")
+    );
+    assert_eq!(options.best(&candidates, "parseConfig"), None);
+}
+
+#[test]
+fn word_splitter() {
+    use rustc_edit_distance::MatchOptions;
+
+    let commands = ["git-log", "log-level", "docker compose up"];
+    let options = MatchOptions::new()
+        .max_distance(0)
+        .splitter(|name| name.split(['-', ' ']).collect());
+    assert_eq!(options.best(&commands, "level-log"), Some("log-level"));
+    assert_eq!(options, options.clone());
+    assert_eq!(
+        options.best(&commands, "up compose docker"),
+        Some("docker compose up")
+    );
+    assert_eq!(
+        MatchOptions::new()
+            .max_distance(0)
+            .best(&commands, "level-log"),
+        None
+    );
+    // The splitter replaces the separators, and case splitting still applies to its words.
+    let options = options.separators(&['_']).split_case(true);
+    assert_eq!(options.best(&commands, "Level-Log"), Some("log-level"));
+    assert_eq!(options.best(&["file_name"], "name_file"), None);
+
+    // The splitter can capture state, e.g. separators picked at runtime.
+    let separators = String::from("-.");
+    let options = MatchOptions::new()
+        .max_distance(0)
+        .splitter(move |name| name.split(|c| separators.contains(c)).collect());
+    assert_eq!(
+        options.best(&["report-final.txt"], "txt.final-report"),
+        Some("report-final.txt")
+    );
+    assert_eq!(options.clone(), MatchOptions::new().max_distance(0));
+}
+
+#[test]
+fn tie_breaks() {
+    use rustc_edit_distance::{find_best_match_for_name, MatchOptions, TieBreak};
+
+    let by = |tie_break| MatchOptions::new().max_distance(2).tie_break(tie_break);
+    let candidates = ["mist", "muxt", "bust", "mst"];
+    assert_eq!(
+        find_best_match_for_name(&candidates, "must", Some(2)),
+        Some("bust")
+    );
+    assert_eq!(
+        by(TieBreak::Lexicographic).best(&candidates, "must"),
+        Some("bust")
+    );
+    assert_eq!(
+        by(TieBreak::FirstInOrder).best(&candidates, "must"),
+        Some("mist")
+    );
+    assert_eq!(
+        by(TieBreak::Shortest).best(&candidates, "must"),
+        Some("mst")
+    );
+    assert_eq!(
+        by(TieBreak::LongestCommonPrefix).best(&candidates, "must"),
+        Some("muxt")
+    );
+
+    // Matches ignoring case and of sorted words are tie broken too.
+    let candidates = ["Map_Hash", "hash_map", "ap"];
+    assert_eq!(
+        find_best_match_for_name(&candidates, "map_hash", Some(0)),
+        Some("Map_Hash")
+    );
+    assert_eq!(
+        by(TieBreak::Lexicographic).best(&candidates, "map_hash"),
+        Some("Map_Hash")
+    );
+    assert_eq!(by(TieBreak::Shortest).best(&candidates, "map"), Some("ap"));
+    let candidates = ["b_a_c", "a_c_b", "c_b_a"];
+    let options = |tie_break| MatchOptions::new().max_distance(0).tie_break(tie_break);
+    assert_eq!(
+        find_best_match_for_name(&candidates, "a_b_c", Some(0)),
+        Some("c_b_a")
+    );
+    assert_eq!(
+        options(TieBreak::FirstInOrder).best(&candidates, "a_b_c"),
+        Some("b_a_c")
+    );
+    assert_eq!(
+        options(TieBreak::LongestCommonPrefix).best(&candidates, "a_b_c"),
+        Some("a_c_b")
+    );
+}
+
+#[test]
+fn candidate_set_agrees_with_find_best_match_property() {
+    fn candidate_set_agrees(candidates: Vec<String>, lookup: String, dist: Option<u8>) -> bool {
+        // Underscores and case, for the other kinds of match.
+        let alphabet = |s: &str| {
+            s.chars()
+                .map(|c| ['a', 'b', 'B', '_'][(u32::from(c) % 4) as usize])
+                .collect::<String>()
+        };
+        let candidates = candidates.iter().map(|c| alphabet(c)).collect::<Vec<_>>();
+        let candidates = candidates.iter().map(String::as_str).collect::<Vec<_>>();
+        let lookup = alphabet(&lookup);
+        let dist = dist.map(|d| usize::from(d % 4));
+
+        let set = rustc_edit_distance::CandidateSet::new(&candidates);
+        set.len() == candidates.len()
+            && set.best(&lookup, dist)
+                == rustc_edit_distance::find_best_match_for_name(&candidates, &lookup, dist)
+    }
+
+    quickcheck(candidate_set_agrees as fn(Vec<String>, String, Option<u8>) -> bool);
+}
+
+#[test]
+fn caseless_equality() {
+    use rustc_edit_distance::{caseless_eq, edit_distance_ci};
+
+    assert!(caseless_eq("", ""));
+    assert!(caseless_eq("HashMap", "hashmap"));
+    assert!(caseless_eq("ﬁle", "FILE"));
+    assert!(caseless_eq("ǅemal", "ǆEMAL"));
+    assert!(!caseless_eq("hash_map", "hashmap"));
+    // The approximation doesn't fold the capital sharp s, which the tables do, and folds the
+    // dotless i through `I`, which they don't.
+    assert_eq!(caseless_eq("ẞ", "ß"), cfg!(feature = "caseless"));
+    assert_eq!(caseless_eq("ı", "i"), !cfg!(feature = "caseless"));
+    assert_eq!(
+        edit_distance_ci("GROẞ", "gross", 2),
+        Some(if cfg!(feature = "caseless") { 0 } else { 2 })
+    );
+}
+
+#[test]
+fn token_distances() {
+    use rustc_edit_distance::{
+        edit_distance, token_edit_distance, token_edit_distance_with, word_edit_distance,
+        word_edit_distance_with,
+    };
+
+    let a = ["open", "the", "file"];
+    assert_eq!(token_edit_distance(&a, &a, 0), Some(0));
+    assert_eq!(
+        token_edit_distance(&a, &["open", "file"], usize::MAX),
+        Some(1)
+    );
+    assert_eq!(
+        token_edit_distance(&a, &["the", "open", "file"], usize::MAX),
+        Some(1)
+    );
+    assert_eq!(
+        token_edit_distance(&a, &["open", "fiel"], usize::MAX),
+        Some(2)
+    );
+    assert_eq!(token_edit_distance::<&str>(&[], &a, usize::MAX), Some(3));
+    assert_eq!(token_edit_distance(&a, &["close"], 2), None);
+
+    let owned = ["open".to_string(), "file".to_string()];
+    assert_eq!(
+        token_edit_distance(&owned, &owned[..1], usize::MAX),
+        Some(1)
+    );
+
+    let typo = |x: &str, y: &str| edit_distance(x, y, 1).map_or(2, |_| 1);
+    assert_eq!(
+        token_edit_distance_with(&a, &["open", "teh", "fiel"], usize::MAX, typo),
+        Some(2)
+    );
+    assert_eq!(
+        token_edit_distance_with(&a, &["open", "a", "dir"], usize::MAX, typo),
+        Some(4)
+    );
+    // Costs above 2 are capped, as a deletion and an insertion would do instead.
+    assert_eq!(
+        token_edit_distance_with(&a, &["open", "a", "file"], usize::MAX, |x, y| {
+            if x == y {
+                0
+            } else {
+                10
+            }
+        }),
+        Some(2)
+    );
+
+    assert_eq!(word_edit_distance("", "", 0), Some(0));
+    assert_eq!(
+        word_edit_distance(" open\tthe file\n", "open the  file", 0),
+        Some(0)
+    );
+    assert_eq!(
+        word_edit_distance("open the file", "open file", usize::MAX),
+        Some(1)
+    );
+    assert_eq!(
+        word_edit_distance_with("open fiel", "open file", usize::MAX, typo),
+        Some(1)
+    );
+}
+
+#[test]
+fn line_distances() {
+    use rustc_edit_distance::{line_edit_distance, line_edit_distance_hashed};
+
+    let a = "use std::io;\n\nfn main() {\n    let x = 1;\n}\n";
+    let cases = [
+        (a, 0),
+        ("use std::io;\r\n\r\nfn main() {\r\n    let x = 1;\r\n}", 0),
+        ("use std::io;\n\nfn main() {\n    let x = 2;\n}\n", 1),
+        ("fn main() {\n    let x = 1;\n}\n", 2),
+        ("use std::io;\nfn main() {\n\n    let x = 1;\n}\n", 1),
+        ("", 5),
+    ];
+    for (b, expected) in cases {
+        assert_eq!(
+            line_edit_distance(a, b, usize::MAX),
+            Some(expected),
+            "{b:?}"
+        );
+        assert_eq!(
+            line_edit_distance_hashed(a, b, usize::MAX),
+            Some(expected),
+            "{b:?}"
+        );
+        assert_eq!(
+            line_edit_distance(b, a, usize::MAX),
+            Some(expected),
+            "{b:?}"
+        );
+    }
+    assert_eq!(line_edit_distance(a, "", 4), None);
+    assert_eq!(line_edit_distance_hashed(a, "", 4), None);
+    // Trailing whitespace makes a line different.
+    assert_eq!(line_edit_distance("a\nb", "a \nb", 1), Some(1));
+}
+
+#[test]
+fn phrase_distances() {
+    use rustc_edit_distance::{
+        edit_distance, find_all_matches_within_with, find_best_match_for_name_with,
+        phrase_distance, MatchOptions,
+    };
+
+    let command = "open file in editor";
+    assert_eq!(phrase_distance(command, command, 0), Some(0));
+    assert_eq!(
+        phrase_distance("  open file\tin editor ", command, 0),
+        Some(0)
+    );
+    assert_eq!(
+        phrase_distance("open fiel in editr", command, usize::MAX),
+        Some(2)
+    );
+    assert_eq!(phrase_distance("open fiel in editr", command, 1), None);
+    assert_eq!(phrase_distance("open editor", command, usize::MAX), Some(6));
+    assert_eq!(
+        phrase_distance("in editor open file", command, usize::MAX),
+        Some(16)
+    );
+    assert_eq!(phrase_distance("", command, usize::MAX), Some(16));
+    // One word behaves like `edit_distance`.
+    assert_eq!(
+        phrase_distance("editr", "editor", usize::MAX),
+        edit_distance("editr", "editor", usize::MAX)
+    );
+
+    let commands = [
+        "Open File in Editor",
+        "Open Folder",
+        "Close Editor",
+        "Open File",
+    ];
+    let options = MatchOptions::new()
+        .phrases(true)
+        .case_insensitive(true)
+        .max_distance(4);
+    assert_eq!(
+        find_all_matches_within_with(&commands, "open fiel", &options),
+        [("Open File", 1), ("Open Folder", 4)]
+    );
+    assert_eq!(
+        find_best_match_for_name_with(&commands, "clse editr", &options),
+        Some("Close Editor")
+    );
+    // Words are scored as the rest of the options say.
+    let options = options.case_insensitive(false);
+    assert_eq!(
+        find_all_matches_within_with(&commands, "open fiel", &options),
+        [("Open File", 3)]
+    );
+}
+
+#[test]
+fn clusters() {
+    use rustc_edit_distance::{cluster, cluster_with, edit_distance, Linkage};
+
+    assert!(cluster(&[], 1).is_empty());
+    assert_eq!(cluster(&["a"], 0), [vec![0]]);
+    assert_eq!(cluster(&["a", "a", "b"], 0), [vec![0, 1], vec![2]]);
+
+    let tags = [
+        "kotlin",
+        "rust",
+        "rustlang",
+        "rust-lang",
+        "rusty",
+        "kotlinx",
+        "Rust",
+    ];
+    assert_eq!(cluster(&tags, 1), [vec![0, 5], vec![1, 4, 6], vec![2, 3]]);
+
+    // A chain of close strings is one cluster by single linkage only.
+    let chain = ["aaa", "aab", "abb", "bbb", "zzz"];
+    assert_eq!(
+        cluster_with(&chain, 1, Linkage::Representative),
+        [vec![0, 1], vec![2, 3], vec![4]]
+    );
+    assert_eq!(
+        cluster_with(&chain, 1, Linkage::Single),
+        [vec![0, 1, 2, 3], vec![4]]
+    );
+    assert_eq!(
+        cluster_with(&chain, 1, Linkage::Complete),
+        [vec![0, 1], vec![2, 3], vec![4]]
+    );
+    // Merging clusters keeps them in the order of their first members.
+    let bridge = ["ab", "xy", "xb"];
+    assert_eq!(cluster_with(&bridge, 1, Linkage::Single), [vec![0, 1, 2]]);
+
+    for linkage in [Linkage::Representative, Linkage::Single, Linkage::Complete] {
+        let clusters = cluster_with(&tags, 2, linkage);
+        let mut members = clusters.concat();
+        members.sort_unstable();
+        assert_eq!(members, (0..tags.len()).collect::<Vec<_>>(), "{linkage:?}");
+        if linkage == Linkage::Complete {
+            for cluster in &clusters {
+                for &i in cluster {
+                    for &j in cluster {
+                        assert!(edit_distance(tags[i], tags[j], 2).is_some());
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn suggestions() {
+    use rustc_edit_distance::{SpanKind, Suggestion};
+
+    assert_eq!(Suggestion::new("x", &[]).to_string(), "");
+    assert_eq!(
+        Suggestion::new("x", &["y"]).to_string(),
+        "did you mean `y`?"
+    );
+    assert_eq!(
+        Suggestion::new("x", &["y", "z"]).to_string(),
+        "did you mean one of `y` or `z`?"
+    );
+    assert_eq!(
+        Suggestion::new("x", &["a", "b", "c"]).to_string(),
+        "did you mean one of `a`, `b` or `c`?"
+    );
+    let many = Suggestion::new("x", &["a", "b", "c", "d"]);
+    assert_eq!(
+        many.clone().max_listed(3).to_string(),
+        "did you mean one of `a`, `b`, `c` or 1 other?"
+    );
+    assert_eq!(
+        many.clone().max_listed(0).to_string(),
+        "did you mean one of `a` or 3 others?"
+    );
+    assert_eq!(many.matches(), ["a", "b", "c", "d"]);
+    assert_eq!(
+        many.prefixes("", "")
+            .quotes("\"", "\"")
+            .suffix(".")
+            .to_string(),
+        "\"a\", \"b\", \"c\" or \"d\"."
+    );
+
+    let candidates = ["length", "len", "height"];
+    assert_eq!(
+        Suggestion::find(&candidates, "lenght", None).to_string(),
+        "did you mean one of `len`, `length` or `height`?"
+    );
+    assert_eq!(Suggestion::find(&candidates, "xyz", None).to_string(), "");
+
+    let changes = Suggestion::new("colour", &["color", "collar"])
+        .max_listed(1)
+        .changes();
+    assert_eq!(changes.len(), 1);
+    let (name, spans) = &changes[0];
+    assert_eq!(*name, "color");
+    let deleted = spans
+        .iter()
+        .find(|span| span.kind == SpanKind::Deleted)
+        .unwrap();
+    assert_eq!(&"colour"[deleted.a.clone()], "u");
+}
+
+#[test]
+fn custom_metrics() {
+    use rustc_edit_distance::{
+        damerau_levenshtein, edit_distance, find_best_match_for_name,
+        find_best_match_for_name_with_metric, EditCosts, MatchOptions, Metric,
+    };
+
+    let candidates = ["cat", "act", "tac", "CATS", "cart"];
+    // `edit_distance` as the metric is the same as no metric at all.
+    for lookup in ["cta", "ca", "tca", "cats", "xyz", "car"] {
+        for dist in [None, Some(0), Some(1), Some(2)] {
+            assert_eq!(
+                find_best_match_for_name_with_metric(&candidates, lookup, dist, &edit_distance),
+                find_best_match_for_name(&candidates, lookup, dist),
+                "{lookup} {dist:?}"
+            );
+        }
+    }
+    assert_eq!(damerau_levenshtein.distance("ca", "abc", 3), Some(2));
+
+    // Only substituting is cheap, so the candidate with a substitution wins.
+    let costs = EditCosts {
+        insert: 3,
+        delete: 3,
+        substitute: 1,
+        transpose: 3,
+    };
+    let candidates = ["bart", "brt"];
+    assert_eq!(
+        find_best_match_for_name(&candidates, "bat", Some(1)),
+        Some("bart")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_metric(&candidates, "bat", Some(3), &costs),
+        Some("brt")
+    );
+    assert_eq!(
+        find_best_match_for_name_with_metric(&["bart", "bet"], "bat", Some(3), &costs),
+        Some("bet")
+    );
+
+    // A closure, with the other options still applied.
+    let options = MatchOptions::new()
+        .case_insensitive(true)
+        .use_substrings(true);
+    let vowels_free = |a: &str, b: &str, limit| {
+        let strip = |s: &str| {
+            s.chars()
+                .filter(|c| !"aeiou".contains(*c))
+                .collect::<String>()
+        };
+        edit_distance(&strip(a), &strip(b), limit)
+    };
+    assert_eq!(
+        options.best_by(&["Bottle", "Bitter"], "btl", &vowels_free),
+        Some("Bottle")
+    );
+    assert_eq!(options.best_by(&[], "btl", &vowels_free), None);
+    let dyn_metric: &dyn Metric = &vowels_free;
+    assert_eq!(
+        options.best_by(&["Bottle", "Button"], "BTN", dyn_metric),
+        Some("Button")
+    );
+}
+
+#[test]
+fn str_extension() {
+    use rustc_edit_distance::{
+        edit_distance, find_best_match_for_name, similarity_ratio, EditDistanceExt,
+    };
+
+    let lookup = String::from("recieve");
+    let candidates = ["receive", "relieve", "deceive"];
+    for c in candidates {
+        assert_eq!(lookup.edit_distance_to(c, 1), edit_distance(&lookup, c, 1));
+        assert_eq!(lookup.similarity_to(c), similarity_ratio(&lookup, c));
+    }
+    assert_eq!(
+        lookup.best_match_in(&candidates),
+        find_best_match_for_name(&candidates, &lookup, None)
+    );
+    assert_eq!("".best_match_in(&candidates), None);
+    assert_eq!("".similarity_to(""), 1.0);
+}
+
+#[test]
+fn path_distances() {
+    use rustc_edit_distance::{
+        edit_distance, find_best_match_for_path, os_str_edit_distance, path_edit_distance,
+        PathScoring,
+    };
+    use std::ffi::OsStr;
+    use std::path::Path;
+
+    for (a, b) in [
+        ("", ""),
+        ("main.rs", "mian.rs"),
+        ("lib", "library"),
+        ("ß", "ss"),
+    ] {
+        assert_eq!(
+            os_str_edit_distance(OsStr::new(a), OsStr::new(b), usize::MAX),
+            edit_distance(a, b, usize::MAX)
+        );
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::ffi::OsStrExt;
+
+        // Invalid bytes are units of their own, unlike after a lossy conversion.
+        let a = OsStr::from_bytes(b"file\xff.txt");
+        let b = OsStr::from_bytes(b"file\xfe.txt");
+        assert_eq!(os_str_edit_distance(a, b, usize::MAX), Some(1));
+        assert_eq!(os_str_edit_distance(a, a, 0), Some(0));
+        assert_eq!(
+            os_str_edit_distance(a, OsStr::new("file.txt"), usize::MAX),
+            Some(1)
+        );
+        // A truncated multi-byte char is one unit per byte.
+        let c = OsStr::from_bytes(b"caf\xc3");
+        assert_eq!(
+            os_str_edit_distance(c, OsStr::new("caf\u{e9}"), usize::MAX),
+            Some(1)
+        );
+    }
+
+    fn paths(paths: &[&'static str]) -> Vec<&'static Path> {
+        paths.iter().map(|&path| Path::new(path)).collect()
+    }
+    let lookup = Path::new("src/amin.rs");
+    let distance = |p: &str, scoring| path_edit_distance(lookup, Path::new(p), usize::MAX, scoring);
+    assert_eq!(distance("src/main.rs", PathScoring::Components), Some(1));
+    assert_eq!(distance("src/amin.rs", PathScoring::Components), Some(0));
+    assert_eq!(distance("./src//amin.rs", PathScoring::Components), Some(1));
+    assert_eq!(distance("amin.rs", PathScoring::Components), Some(3));
+    assert_eq!(distance("amin.rs", PathScoring::Whole), Some(4));
+    assert_eq!(
+        path_edit_distance(lookup, Path::new("amin.rs"), 2, PathScoring::Components),
+        None
+    );
+
+    let candidates = paths(&["src/lib.rs", "src/main.rs", "tests/main.rs", "src/a.rs"]);
+    for scoring in [PathScoring::Whole, PathScoring::Components] {
+        assert_eq!(
+            find_best_match_for_path(&candidates, lookup, None, scoring),
+            Some(Path::new("src/main.rs"))
+        );
+        assert_eq!(
+            find_best_match_for_path(&candidates, Path::new("zzz"), None, scoring),
+            None
+        );
+    }
+    // Ties go to the smallest path.
+    let candidates = paths(&["src/amin.rsx", "src/mains.rs", "src/amin.r"]);
+    assert_eq!(
+        find_best_match_for_path(&candidates, lookup, Some(1), PathScoring::Components),
+        Some(Path::new("src/amin.r"))
+    );
+}
+
+/// Checks that `alignment` lines up all of `a` and `b` in order, and sums up its score.
+fn check_alignment(
+    alignment: &rustc_edit_distance::Alignment,
+    a: &str,
+    b: &str,
+    scheme: &rustc_edit_distance::ScoringScheme,
+) -> bool {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut score = 0;
+    let (mut i, mut j) = (alignment.a.start, alignment.b.start);
+    // The run of gaps the last column was part of, and how long it is so far.
+    let mut run = (None, 0);
+    for &column in &alignment.columns {
+        let gap_in = match column {
+            (Some(x), Some(y)) => {
+                score += i64::from(scheme.substitution(a[x], b[y]));
+                None
+            }
+            (None, None) => return false,
+            (Some(_), None) => Some(false),
+            (None, Some(_)) => Some(true),
+        };
+        if gap_in != run.0 {
+            score += scheme.gap_run(run.1);
+            run = (gap_in, 0);
+        }
+        if gap_in.is_some() {
+            run.1 += 1;
+        }
+        if let Some(x) = column.0 {
+            if x != i {
+                return false;
+            }
+            i += 1;
+        }
+        if let Some(y) = column.1 {
+            if y != j {
+                return false;
+            }
+            j += 1;
+        }
+    }
+    score += scheme.gap_run(run.1);
+    (i, j) == (alignment.a.end, alignment.b.end) && score == alignment.score
+}
+
+#[test]
+fn global_alignment() {
+    use rustc_edit_distance::{global_align, lcs_length, levenshtein_distance, ScoringScheme};
+
+    let scheme = ScoringScheme::default();
+    let alignment = global_align("", "", &scheme);
+    assert_eq!((alignment.score, alignment.columns.len()), (0, 0));
+    let alignment = global_align("ab", "", &scheme);
+    assert_eq!(alignment.score, -2);
+    assert_eq!(alignment.columns, [(Some(0), None), (Some(1), None)]);
+
+    // A cheap transition in the matrix beats a gap.
+    let scheme = ScoringScheme::new(2, -3, -2)
+        .score('A', 'G', 1)
+        .score('C', 'T', 1);
+    let alignment = global_align("ACGT", "GTGT", &scheme);
+    assert_eq!(alignment.score, 6);
+    assert_eq!(
+        alignment.gapped("ACGT", "GTGT", '-'),
+        ("ACGT".into(), "GTGT".into())
+    );
+    assert!(check_alignment(&alignment, "ACGT", "GTGT", &scheme));
+
+    fn known_scores(a: String, b: String) -> bool {
+        let levenshtein = ScoringScheme::new(0, -1, -1);
+        let lcs = ScoringScheme::new(1, -1, 0);
+        let by_distance = global_align(&a, &b, &levenshtein);
+        let by_lcs = global_align(&a, &b, &lcs);
+        by_distance.score == -(levenshtein_distance(&a, &b, usize::MAX).unwrap() as i64)
+            && by_lcs.score == lcs_length(&a, &b) as i64
+            && check_alignment(&by_distance, &a, &b, &levenshtein)
+            && check_alignment(&by_lcs, &a, &b, &lcs)
+            && by_distance.a == (0..a.chars().count())
+    }
+
+    quickcheck(known_scores as fn(String, String) -> bool);
+}
+
+#[test]
+fn global_alignment_linear_space() {
+    use rustc_edit_distance::{global_align, global_align_linear_space, ScoringScheme};
+
+    let scheme = ScoringScheme::new(2, -1, -2);
+    let alignment = global_align_linear_space("", "ab", &scheme);
+    assert_eq!(
+        (alignment.score, alignment.a, alignment.b),
+        (-4, 0..0, 0..2)
+    );
+    assert_eq!(alignment.columns, [(None, Some(0)), (None, Some(1))]);
+    let (a, b) = ("GATTACAGATTACA", "GCATGCUGCA");
+    let alignment = global_align_linear_space(a, b, &scheme);
+    assert_eq!(alignment.score, global_align(a, b, &scheme).score);
+    assert!(check_alignment(&alignment, a, b, &scheme));
+    // A run of gaps opens once, even where the halves are split in the middle of it.
+    let affine = ScoringScheme::new(1, -1, -3).gap_extension(-1);
+    for (a, b) in [
+        (a, b),
+        ("ACGTTA", "ACA"),
+        ("AAAAXXXXXXXXAAAA", "AAAAAAAA"),
+        ("x", ""),
+    ] {
+        let alignment = global_align_linear_space(a, b, &affine);
+        assert_eq!(alignment.score, global_align(a, b, &affine).score);
+        assert!(check_alignment(&alignment, a, b, &affine));
+    }
+    let alignment = global_align_linear_space("AAAAXXXXXXXXAAAA", "AAAAAAAA", &affine);
+    assert_eq!(alignment.score, 8 - 3 - 7);
+
+    fn same_scores(a: String, b: String) -> bool {
+        // Over a small alphabet, for chars to match often.
+        let fold = |s: String| s.chars().map(|c| ['a', 'b', 'c'][c as usize % 3]).collect();
+        let (a, b): (String, String) = (fold(a), fold(b));
+        let scheme = ScoringScheme::new(2, -1, -2).score('a', 'b', 1);
+        let affine = scheme.clone().gap_extension(-1);
+        [scheme, affine].iter().all(|scheme| {
+            let alignment = global_align_linear_space(&a, &b, scheme);
+            alignment.score == global_align(&a, &b, scheme).score
+                && check_alignment(&alignment, &a, &b, scheme)
+        })
+    }
+
+    quickcheck(same_scores as fn(String, String) -> bool);
+}
+
+#[test]
+fn local_alignment() {
+    use rustc_edit_distance::{global_align, local_align, longest_common_substring, ScoringScheme};
+
+    let scheme = ScoringScheme::default();
+    let alignment = local_align("abc", "xyz", &scheme);
+    assert_eq!((alignment.score, alignment.a, alignment.b), (0, 0..0, 0..0));
+    assert!(alignment.columns.is_empty());
+    assert_eq!(local_align("", "abc", &scheme).score, 0);
+
+    // The region in common, with a gap, and nothing of the rest.
+    let (a, b) = ("zzzHELLOWORLDzzz", "qqHELLWORLDqq");
+    let alignment = local_align(a, b, &ScoringScheme::new(3, -3, -2));
+    assert_eq!((alignment.a.clone(), alignment.b.clone()), (3..13, 2..11));
+    assert_eq!(alignment.score, 25);
+    assert_eq!(
+        alignment.gapped(a, b, '-'),
+        ("HELLOWORLD".into(), "HELL-WORLD".into())
+    );
+    // Ties go to the one that ends first.
+    let alignment = local_align("ab", "abxab", &scheme);
+    assert_eq!((alignment.a, alignment.b), (0..2, 0..2));
+
+    fn known_scores(a: String, b: String) -> bool {
+        let substring = ScoringScheme::new(1, -1000, -1000);
+        let by_substring = local_align(&a, &b, &substring);
+        let scheme = ScoringScheme::new(2, -1, -1);
+        let local = local_align(&a, &b, &scheme);
+        by_substring.score == longest_common_substring(&a, &b).0 as i64
+            && check_alignment(&by_substring, &a, &b, &substring)
+            && check_alignment(&local, &a, &b, &scheme)
+            && local.score >= global_align(&a, &b, &scheme).score.max(0)
+    }
+
+    quickcheck(known_scores as fn(String, String) -> bool);
+}
+
+#[test]
+fn affine_gaps() {
+    use rustc_edit_distance::{
+        global_align, local_align, weighted_edit_distance, weighted_edit_distance_affine,
+        EditCosts, ScoringScheme,
+    };
+
+    // One run of gaps instead of two scattered ones.
+    let scheme = ScoringScheme::new(2, -2, -4).gap_extension(-1);
+    assert_eq!(scheme.gap_run(0), 0);
+    assert_eq!(scheme.gap_run(3), -6);
+    let alignment = global_align("AAACCCGGG", "AAAGGG", &scheme);
+    assert_eq!(
+        alignment.gapped("AAACCCGGG", "AAAGGG", '-'),
+        ("AAACCCGGG".into(), "AAA---GGG".into())
+    );
+    assert_eq!(alignment.score, 12 - 6);
+    assert!(check_alignment(&alignment, "AAACCCGGG", "AAAGGG", &scheme));
+    let scheme = ScoringScheme::new(3, -2, -4).gap_extension(-1);
+    let alignment = local_align("xxAAACCCGGGyy", "AAAGGG", &scheme);
+    assert_eq!((alignment.a, alignment.score), (2..11, 18 - 6));
+
+    let costs = EditCosts::default();
+    assert_eq!(weighted_edit_distance_affine("", "", 0, &costs, 5), Some(0));
+    assert_eq!(
+        weighted_edit_distance_affine("", "abc", 10, &costs, 5),
+        Some(8)
+    );
+    assert_eq!(
+        weighted_edit_distance_affine("abc", "abc", 0, &costs, 5),
+        Some(0)
+    );
+    assert_eq!(
+        weighted_edit_distance_affine("the cat", "the big cat", 10, &costs, 2),
+        Some(6)
+    );
+    assert_eq!(
+        weighted_edit_distance_affine("the cat", "the big cat", 5, &costs, 2),
+        None
+    );
+    // Substituting beats opening two gaps.
+    assert_eq!(
+        weighted_edit_distance_affine("cat", "cut", 10, &costs, 2),
+        Some(1)
+    );
+    assert_eq!(
+        weighted_edit_distance_affine("hlelo", "hello", 10, &costs, 2),
+        Some(1)
+    );
+
+    fn properties(a: String, b: String, gap_open: u8) -> bool {
+        let costs = EditCosts {
+            insert: 2,
+            delete: 1,
+            substitute: 2,
+            transpose: 1,
+        };
+        let gap_open = usize::from(gap_open % 4);
+        let plain = weighted_edit_distance(&a, &b, usize::MAX, &costs).unwrap();
+        let affine = weighted_edit_distance_affine(&a, &b, usize::MAX, &costs, gap_open).unwrap();
+        let lower =
+            weighted_edit_distance_affine(&a, &b, affine.saturating_sub(1), &costs, gap_open);
+        (gap_open > 0 || affine == plain)
+            && affine >= plain
+            && lower.is_none()
+            && weighted_edit_distance_affine(&a, &b, affine, &costs, gap_open) == Some(affine)
+    }
+
+    fn alignments(a: String, b: String) -> bool {
+        let linear = ScoringScheme::new(2, -1, -2);
+        let affine = linear.clone().gap_extension(-1);
+        let by_linear = global_align(&a, &b, &linear);
+        let by_affine = global_align(&a, &b, &affine);
+        let local = local_align(&a, &b, &affine);
+        check_alignment(&by_affine, &a, &b, &affine)
+            && check_alignment(&local, &a, &b, &affine)
+            && by_affine.score >= by_linear.score
+            && local.score >= by_affine.score.max(0)
+    }
+
+    quickcheck(properties as fn(String, String, u8) -> bool);
+    quickcheck(alignments as fn(String, String) -> bool);
+}
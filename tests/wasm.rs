@@ -0,0 +1,28 @@
+#![cfg(feature = "wasm")]
+
+use rustc_edit_distance::wasm::*;
+
+fn strings(strings: &[&str]) -> Vec<String> {
+    strings.iter().map(|&s| s.into()).collect()
+}
+
+#[test]
+fn distances() {
+    assert_eq!(edit_distance("kitten", "sitting", None), Some(3));
+    assert_eq!(edit_distance("kitten", "sitting", Some(2)), None);
+    assert_eq!(similarity("abcd", "abce"), 0.75);
+}
+
+#[test]
+fn matches() {
+    let candidates = strings(&["fooo", "bar", "foob", "FOO"]);
+    assert_eq!(
+        find_best_match(candidates.clone(), "foox", Some(1)),
+        Some("FOO".into())
+    );
+    assert_eq!(find_best_match(candidates.clone(), "xyz", None), None);
+    assert_eq!(
+        find_best_matches(candidates, "foox", Some(1), 2),
+        strings(&["FOO", "foob"])
+    );
+}